@@ -0,0 +1,92 @@
+use crate::i18n::tr;
+use crate::index::SearchIndex;
+use crate::meta::MetaResult;
+
+pub fn escape_html(s: &str) -> String {
+    s.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+pub fn grep_page(index: &SearchIndex, query: &str) -> String {
+    let hits = index.search(query);
+    let mut html = format!("<h1>grep: {}</h1><p>{} matching page(s)</p><ul>", escape_html(query), hits.len());
+    for hit in hits {
+        html.push_str(&format!(
+            "<li><a href=\"{}\">{}</a><br>{}<br>{}</li>",
+            escape_html(&hit.url),
+            escape_html(if hit.title.is_empty() { &hit.url } else { &hit.title }),
+            escape_html(&hit.url),
+            escape_html(&hit.snippet)
+        ));
+    }
+    html.push_str("</ul>");
+    html
+}
+
+pub fn meta_page(query: &str, results: &[MetaResult]) -> String {
+    let mut html = format!("<h1>meta: {}</h1><p>{} result(s)</p><ol>", escape_html(query), results.len());
+    for r in results {
+        html.push_str(&format!(
+            "<li><a href=\"{}\">{}</a> <em>[{}]</em><br>{}</li>",
+            escape_html(&r.url),
+            escape_html(&r.title),
+            escape_html(&r.sources.join(", ")),
+            escape_html(&r.url)
+        ));
+    }
+    html.push_str("</ol>");
+    html
+}
+
+pub fn frames_page(frames: &[String]) -> String {
+    let mut html = format!("<h1>Frames</h1><p>{} frame(s) on the previous page</p><ol>", frames.len());
+    for src in frames {
+        html.push_str(&format!("<li><a href=\"{0}\">{0}</a></li>", escape_html(src)));
+    }
+    html.push_str("</ol>");
+    html
+}
+
+// What a status code means for the reader, in a sentence
+fn status_explanation(status: u16, has_location: bool) -> String {
+    match status {
+        300..=399 => match (status, has_location) {
+            (300, _) => tr!("The server offers several versions of this page; its own page below lists them."),
+            (304, _) => tr!("The server says the page hasn't changed, and sent no copy of it."),
+            (_, true) => tr!("This redirect isn't one that is followed automatically; the link below leads on."),
+            (_, false) => tr!("The server answered with a redirect but didn't say where to."),
+        },
+        400 => tr!("The server couldn't make sense of the request."),
+        401 => tr!("This page needs you to sign in."),
+        403 => tr!("The server refuses to show this page."),
+        404 => tr!("There is no page at this address. It may have moved, or the link may be mistyped."),
+        410 => tr!("This page has been removed for good."),
+        429 => tr!("Too many requests: the server asks you to wait before trying again."),
+        402..=499 => tr!("The server turned the request down."),
+        502..=504 => tr!("The server, or one in front of it, is down or overloaded. Trying again later may work."),
+        _ => tr!("The server ran into an error answering the request."),
+    }
+}
+
+// The page shown for a 4xx/5xx response, or a redirect that wasn't followed: the status, what
+// it means and where to go on, followed by the page the server sent with it. `stopped` says why
+// the client's redirect policy refused to go on, when it did.
+pub fn status_page(status: (u16, &str), url: &str, location: Option<&str>, retry_after: Option<&str>, stopped: Option<String>, server_page: Option<&str>) -> String {
+    let (code, reason) = status;
+    let explanation = stopped.unwrap_or_else(|| status_explanation(code, location.is_some()));
+    let mut html = format!("<h1>{} {}</h1><p>{}</p>", code, escape_html(reason), escape_html(&explanation));
+    html.push_str(&format!("<p>{} <a href=\"{1}\">{1}</a></p>", escape_html(&tr!("Address:")), escape_html(url)));
+    if let Some(location) = location {
+        html.push_str(&format!("<p>{} <a href=\"{1}\">{1}</a></p>", escape_html(&tr!("Redirects to:")), escape_html(location)));
+    }
+    if let Some(retry_after) = retry_after {
+        html.push_str(&format!("<p>{}</p>", escape_html(&tr!("Retry after: {}", retry_after))));
+    }
+    if code >= 400 {
+        html.push_str(&format!("<p>{}</p>", escape_html(&tr!(":reload tries again."))));
+    }
+    if let Some(server_page) = server_page {
+        html.push_str(&format!("<hr><p><em>{}</em></p>", escape_html(&tr!("The server's own page follows."))));
+        html.push_str(server_page);
+    }
+    html
+}
@@ -0,0 +1,57 @@
+use crate::types::Settings;
+use url::Url;
+
+// The publisher URL behind a Google AMP cache or viewer URL:
+//   https://www-example-com.cdn.ampproject.org/c/s/www.example.com/a  -> https://www.example.com/a
+//   https://www.google.com/amp/s/example.com/a                      -> https://example.com/a
+pub fn unwrap_cache_url(url: &Url) -> Option<String> {
+    let host = url.host_str()?;
+    let path = url.path();
+    let rest = if host.ends_with(".cdn.ampproject.org") {
+        path.strip_prefix("/c/").or_else(|| path.strip_prefix("/v/"))?
+    } else if host.starts_with("www.google.") || host.starts_with("google.") {
+        path.strip_prefix("/amp/")?
+    } else {
+        return None;
+    };
+    let (scheme, rest) = match rest.strip_prefix("s/") {
+        Some(r) => ("https", r),
+        None => ("http", rest),
+    };
+    let mut target = format!("{}://{}", scheme, rest);
+    if let Some(q) = url.query() {
+        target = format!("{}?{}", target, q);
+    }
+    Url::parse(&target).ok().map(|u| u.to_string())
+}
+
+// Guesses the non-AMP URL of an AMP page that doesn't name its canonical version
+pub fn strip_amp(url: &Url) -> Option<String> {
+    let mut out = url.clone();
+    if let Some(host) = url.host_str().and_then(|h| h.strip_prefix("amp.")) {
+        out.set_host(Some(host)).ok()?;
+    }
+    let path = url.path();
+    let path = path.strip_suffix("/amp").or_else(|| path.strip_suffix("/amp/")).map(str::to_string)
+        .or_else(|| path.strip_suffix(".amp.html").map(|p| format!("{}.html", p)))
+        .or_else(|| path.contains("/amp/").then(|| path.replacen("/amp/", "/", 1)))
+        .unwrap_or_else(|| path.to_string());
+    out.set_path(&path);
+    let pairs: Vec<(String, String)> = url.query_pairs()
+        .filter(|(k, v)| !(k == "amp" || k == "outputType" && v == "amp"))
+        .map(|(k, v)| (k.into_owned(), v.into_owned()))
+        .collect();
+    if pairs.is_empty() {
+        out.set_query(None);
+    } else {
+        out.query_pairs_mut().clear().extend_pairs(pairs);
+    }
+    (out != *url).then(|| out.to_string())
+}
+
+impl Settings {
+    pub fn deamp_enabled(&self, url: &Url) -> bool {
+        let host = url.host_str().unwrap_or("").trim_start_matches("www.");
+        self.deamp && !self.deamp_skip.iter().any(|site| host == site || host.ends_with(&format!(".{}", site)))
+    }
+}
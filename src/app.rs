@@ -1,10 +1,72 @@
+use crate::about;
+use crate::amp;
+use crate::bookmarks::{self, Bookmarks};
+use crate::cache::{looks_binary, Body, PageCache, Prefetched, RenderedCache};
+use crate::caret::Caret;
+use crate::cert::{self, CertStore};
+use crate::chunked::{PartialBody, LOAD_MORE_URL};
+use crate::colors::{self, ColorDepth};
+use crate::config::Config;
+use crate::cookies::{self, CookieJar};
+use crate::dialog::Dialog;
+use crate::download::{Download, DownloadDone, HeldResponse};
+use crate::forms::FIELD_PREFIX;
+use crate::graphics::{self, Graphics, ImagePreview};
+use crate::har::{Exchange, Incoming, Outgoing};
+use crate::help;
+use crate::hints::Hints;
+use crate::highlight;
+use crate::history::HistoryTree;
+use crate::homograph;
+use crate::i18n::{self, tr};
+use crate::index::SearchIndex;
+use crate::inspector::Inspector;
+use crate::jobs::{self, Job, Received};
+use crate::loader::{Fetched, Loading};
+use crate::meta;
+use crate::mirror::MirrorEvent;
+use crate::monitor::BookmarkCopies;
+use crate::net::Net;
+use crate::page::{self, PageInfo, TextStats};
+use crate::picker::Picker;
+use crate::proxy;
+use crate::reader;
+use crate::readlist::{self, ReadingList};
+use crate::recovery::Autosave;
+use crate::redirect::{self, RedirectLog, Stop};
+use crate::scroll::SmoothScroll;
+use crate::search::Search;
+use crate::shortlink;
+use crate::sitemap;
+use crate::table;
+use crate::tabs::Tab;
+use crate::stats;
+use crate::storage;
+use crate::stream::{is_streaming, LiveStream};
+use crate::theme::{Background, Palette};
+use crate::timing::{ConnectTiming, TimedResolver};
+use crate::typeset;
 use crate::types::*;
+use crate::urlbar::UrlBar;
+use crate::visits::{self, VisitLog};
+use crate::watch::Watch;
+use crate::zoom::{self, SiteZoom};
+use crate::ui::format_size;
 use ratatui::{style::{Color, Modifier, Style}, text::{Line, Span}};
+use std::collections::{BTreeMap, VecDeque};
 use std::error::Error;
+use std::sync::Arc;
+use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
+use std::time::{Duration, Instant, SystemTime};
 use url::Url;
-use html2text::render::text_renderer::RichAnnotation;
+use html2text::render::text_renderer::{RichAnnotation, TaggedLine};
 use image::GenericImageView;
 
+const MAX_REFRESH_HOPS: usize = 5;
+const MESSAGE_LOG_SIZE: usize = 500;
+const TOAST_DURATION: Duration = Duration::from_secs(4);
+pub const USER_AGENT: &str = "Voyager-Browser/0.1.0";
+
 pub struct App {
     pub current_url: String,
     pub content_lines: Vec<Line<'static>>,
@@ -12,15 +74,266 @@ pub struct App {
     pub selected_link_idx: usize,
     pub scroll: u16,
     pub status: String,
+    pub messages: VecDeque<(SystemTime, String)>,
+    pub toasts: Vec<Toast>,
     pub mode: Mode,
     pub command_buffer: String,
-    pub history: Vec<String>,
-    pub future: Vec<String>,
-    pub image_preview: Option<Vec<String>>,
+    pub history: HistoryTree,
+    pub visited_at: SystemTime,
+    pub image_preview: Option<ImagePreview>,
+    pub index: SearchIndex,
+    pub settings: Settings,
+    // Every request goes out through this (see net.rs)
+    pub net: Net,
+    // Shared with the client, which reads and fills it on every request (see cookies.rs)
+    pub cookies: Arc<CookieJar>,
+    // Filled by the client's redirect policy, read by page loads (see redirect.rs)
+    pub redirect_log: RedirectLog,
+    pub page_info: PageInfo,
+    pub pending_refresh: Option<(Instant, String)>,
+    pub popup: Option<Popup>,
+    pub raw_html: String,
+    // The body as it came, when raw_html is a page of our own around it (status pages, plain
+    // text); the source view shows this instead
+    pub fetched_source: Option<String>,
+    pub stats: TextStats,
+    pub translation: Option<Translation>,
+    pub caret: Caret,
+    pub viewport_height: u16,
+    pub saved_view: Option<SavedView>,
+    pub inspector: Option<Inspector>,
+    pub request_buffer: String,
+    pub picker: Option<Picker>,
+    pub url_bar: Option<UrlBar>,
+    pub dialog: Option<Dialog>,
+    pub session_log: Vec<Exchange>,
+    pub network_pane: bool,
+    pub pending_key: Option<char>,
+    // Every open tab; the shown one's page is in the fields above (see tabs.rs)
+    pub tabs: Vec<Tab>,
+    pub active_tab: usize,
+    // The last `/` search, highlighted until Esc or the next page (see search.rs)
+    pub search: Option<Search>,
+    pub hints: Option<Hints>,
+    // The form field being typed into in insert mode: (form, field)
+    pub editing_field: Option<(usize, usize)>,
+    // A smooth scroll still being drawn (see scroll.rs)
+    pub smooth: Option<SmoothScroll>,
+    // Named registers for yanked text and URLs, and the one the next yank or paste uses
+    pub registers: BTreeMap<char, String>,
+    // As reported by the terminal at startup (see theme.rs)
+    pub terminal_background: Background,
+    pub terminal_colors: ColorDepth,
+    pub terminal_graphics: Graphics,
+    // The loaded `theme`; None uses the built-in palette for the background
+    pub theme: Option<Palette>,
+    // Key bindings and command aliases from voyager.toml
+    pub config: Config,
+    pub register: Option<char>,
+    pub appended_pages: Vec<String>,
+    pub cache: PageCache,
+    pub rendered: RenderedCache,
+    pub partial: Option<PartialBody>,
+    pub download: Option<Download>,
+    pub held: Option<HeldResponse>,
+    pub stream: Option<LiveStream>,
+    // The indexed text of this URL from before the current load, for `:diff`
+    pub previous_text: Option<String>,
+    pub watch: Option<Watch>,
+    // The current page came over a verified TLS connection
+    pub tls_verified: bool,
+    pub certs: CertStore,
+    pub download_tx: UnboundedSender<DownloadDone>,
+    download_rx: UnboundedReceiver<DownloadDone>,
+    pub reading_list: ReadingList,
+    pub bookmarks: Bookmarks,
+    pub visits: VisitLog,
+    pub zoom: SiteZoom,
+    pub autosave: Autosave,
+    // Set by --sandbox: nothing goes over the network, only file://, cached and saved pages load
+    pub sandbox: bool,
+    pub bookmark_copies: BookmarkCopies,
+    pub last_monitor: Instant,
+    pub monitor_tx: UnboundedSender<(String, String)>,
+    pub monitor_rx: UnboundedReceiver<(String, String)>,
+    pub mirror_tx: UnboundedSender<MirrorEvent>,
+    pub mirror_rx: UnboundedReceiver<MirrorEvent>,
+    // The page load in flight, if any (see loader.rs)
+    pub loading: Option<Loading>,
+    pub load_tx: UnboundedSender<(u64, Result<Fetched, String>)>,
+    pub load_rx: UnboundedReceiver<(u64, Result<Fetched, String>)>,
+    pub next_load_id: u64,
+    // URL and history node of the page on screen, gone back to when a load is cancelled
+    pub shown: (String, usize),
+    // URLs an AMP or meta refresh redirect has gone through, to stop loops
+    pub load_chain: Vec<String>,
+    // Reading position to put back once a history entry has loaded again
+    pub restore_on_load: Option<PagePosition>,
+    prefetch_tx: UnboundedSender<Prefetched>,
+    prefetch_rx: UnboundedReceiver<Prefetched>,
+    exchange_rx: UnboundedReceiver<Exchange>,
+    // Results of commands that wait on the network (see jobs.rs)
+    pub job_tx: UnboundedSender<Job>,
+    pub job_rx: UnboundedReceiver<Job>,
+}
+
+fn build_client(certs: &CertStore, settings: &Settings, cookies: &Arc<CookieJar>, redirects: &RedirectLog) -> reqwest::Client {
+    let alpn: &[&[u8]] = match settings.http_version {
+        HttpVersion::Auto => &[b"h2", b"http/1.1"],
+        HttpVersion::Http1 => &[b"http/1.1"],
+        HttpVersion::Http2 => &[b"h2"],
+        HttpVersion::Http3 => &[b"h3"],
+    };
+    let mut builder = reqwest::Client::builder();
+    if let Ok(tls) = cert::tls_config(certs, alpn) {
+        builder = builder.use_preconfigured_tls(tls);
+    }
+    let builder = builder
+        .user_agent(settings.user_agent.as_str())
+        .tls_info(true)
+        .cookie_provider(cookies.clone())
+        .redirect(redirect::policy(settings.redirects, redirects.clone()))
+        .dns_resolver(Arc::new(TimedResolver))
+        .connector_layer(ConnectTiming);
+    let builder = proxy::apply(builder, &settings.proxy);
+    let builder = match settings.http_version {
+        HttpVersion::Auto => builder,
+        HttpVersion::Http1 => builder.http1_only(),
+        HttpVersion::Http2 => builder.http2_prior_knowledge(),
+        #[cfg(feature = "http3")]
+        HttpVersion::Http3 => builder.http3_prior_knowledge(),
+        #[cfg(not(feature = "http3"))]
+        HttpVersion::Http3 => builder,
+    };
+    builder.build().unwrap_or_default()
+}
+
+// What the user typed as an address: local paths become file:// URLs, bare hosts https://
+pub fn address_to_url(text: &str) -> String {
+    if ["http://", "https://", "about:", "file://"].iter().any(|p| text.starts_with(p)) {
+        return text.to_string();
+    }
+    if text.starts_with(['/', '.', '~']) {
+        let expanded = match text.strip_prefix('~') {
+            Some(rest) => format!("{}{}", std::env::var("HOME").unwrap_or_default(), rest),
+            None => text.to_string(),
+        };
+        if let Ok(url) = std::path::absolute(&expanded).map_err(|_| ()).and_then(Url::from_file_path) {
+            return url.to_string();
+        }
+    }
+    format!("https://{}", text)
+}
+
+fn superscript(n: usize) -> String {
+    n.to_string().chars().map(|d| ['⁰', '¹', '²', '³', '⁴', '⁵', '⁶', '⁷', '⁸', '⁹'][d as usize - '0' as usize]).collect()
+}
+
+struct LaidOut {
+    lines: Vec<Line<'static>>,
+    links: Vec<LinkData>,
+    // (line, span) of each link's [N] label, in link order
+    labels: Vec<(usize, usize)>,
+}
+
+// With showurls on, a link's URL follows its text, once the link's last piece is laid out
+fn flush_url(pending: &mut Option<(usize, String)>, out: &mut LaidOut, spans: &mut Vec<Span<'static>>) {
+    let Some((line, url)) = pending.take() else { return };
+    let span = Span::styled(format!(" <{}>", url), Style::default().fg(Color::DarkGray));
+    match out.lines.get_mut(line) {
+        Some(l) => l.spans.push(span),
+        None => spans.push(span),
+    }
+}
+
+// Styles html2text's lines and collects their links; `media` holds the <audio>/<video> sources
+// page::prepare turned into links
+fn style_lines(rich_lines: &[TaggedLine<Vec<RichAnnotation>>], base_url: &Url, settings: &Settings, media: &[String]) -> LaidOut {
+    let mut out = LaidOut { lines: Vec::with_capacity(rich_lines.len()), links: Vec::new(), labels: Vec::new() };
+    let mut pending_url: Option<(usize, String)> = None;
+    for line in rich_lines {
+        let mut spans = Vec::new();
+        for tagged_string in line.tagged_strings() {
+            let mut style = Style::default();
+            let mut current_link = None;
+            let mut hidden = false;
+
+            for annotation in &tagged_string.tag {
+                match annotation {
+                    RichAnnotation::Link(target) => {
+                        let abs = base_url.join(target).map(|u| u.to_string()).unwrap_or_else(|_| target.clone());
+                        let ltype = if abs.starts_with(FIELD_PREFIX) {
+                            LinkType::Field
+                        } else if media.contains(&abs) {
+                            LinkType::Media
+                        } else {
+                            LinkType::Web
+                        };
+                        current_link = Some((abs, ltype));
+                        hidden = false;
+                    }
+                    RichAnnotation::Image(_) if !settings.images || settings.ascii => hidden = current_link.is_none(),
+                    RichAnnotation::Image(src) => {
+                        let abs = base_url.join(src).map(|u| u.to_string()).unwrap_or_else(|_| src.clone());
+                        current_link = Some((abs, LinkType::Image));
+                    }
+                    RichAnnotation::Strong => style = style.add_modifier(Modifier::BOLD),
+                    _ => {}
+                }
+            }
+
+            if hidden {
+                continue;
+            }
+            if pending_url.as_ref().is_some_and(|(_, u)| current_link.as_ref().is_none_or(|(url, _)| url != u)) {
+                flush_url(&mut pending_url, &mut out, &mut spans);
+            }
+            if let Some((url, ltype)) = current_link {
+                if settings.link_numbers && !settings.footnotes {
+                    out.labels.push((out.lines.len(), spans.len()));
+                    spans.push(Span::styled("", Style::default().fg(Color::DarkGray)));
+                }
+
+                let link_style = match ltype {
+                    LinkType::Web => Style::default().fg(LINK_COLOR_WEB).add_modifier(Modifier::UNDERLINED),
+                    LinkType::Image => Style::default().fg(LINK_COLOR_IMG).add_modifier(Modifier::ITALIC),
+                    LinkType::Media => Style::default().fg(LINK_COLOR_MEDIA).add_modifier(Modifier::UNDERLINED),
+                    LinkType::Field => Style::default().fg(LINK_COLOR_FIELD),
+                };
+                spans.push(Span::styled(tagged_string.s.clone(), link_style));
+                if settings.show_urls && ltype != LinkType::Field {
+                    pending_url = Some((out.lines.len(), url.clone()));
+                }
+                out.links.push(LinkData { url, link_type: ltype });
+                if settings.footnotes {
+                    out.labels.push((out.lines.len(), spans.len()));
+                    spans.push(Span::styled("", Style::default().fg(Color::DarkGray)));
+                }
+            } else {
+                spans.push(Span::styled(tagged_string.s.clone(), style));
+            }
+        }
+        out.lines.push(Line::from(spans));
+    }
+    flush_url(&mut pending_url, &mut out, &mut Vec::new());
+    out
 }
 
 impl App {
     pub fn new(start_url: &str) -> Self {
+        let certs = CertStore::default();
+        let settings = Settings::default();
+        let cookies = Arc::new(CookieJar::load(storage::data_file("cookies.json")));
+        let redirect_log = RedirectLog::default();
+        let (prefetch_tx, prefetch_rx) = mpsc::unbounded_channel();
+        let (load_tx, load_rx) = mpsc::unbounded_channel();
+        let (download_tx, download_rx) = mpsc::unbounded_channel();
+        let (monitor_tx, monitor_rx) = mpsc::unbounded_channel();
+        let (mirror_tx, mirror_rx) = mpsc::unbounded_channel();
+        let (exchange_tx, exchange_rx) = mpsc::unbounded_channel();
+        let (job_tx, job_rx) = mpsc::unbounded_channel();
+        let config = Config::default();
+        let client = build_client(&certs, &settings, &cookies, &redirect_log);
         Self {
             current_url: start_url.to_string(),
             content_lines: Vec::new(),
@@ -28,91 +341,720 @@ impl App {
             selected_link_idx: 0,
             scroll: 0,
             status: String::from("Voyager Ready"),
+            messages: VecDeque::new(),
+            toasts: Vec::new(),
             mode: Mode::Normal,
             command_buffer: String::new(),
-            history: Vec::new(),
-            future: Vec::new(),
+            history: HistoryTree::new(start_url),
+            visited_at: SystemTime::now(),
             image_preview: None,
+            index: SearchIndex::load(storage::data_file("index.jsonl")),
+            net: Net::new(client, config.headers.clone(), &settings, redirect_log.clone(), exchange_tx),
+            cookies,
+            redirect_log,
+            settings,
+            page_info: PageInfo::default(),
+            pending_refresh: None,
+            popup: None,
+            raw_html: String::new(),
+            fetched_source: None,
+            stats: TextStats::default(),
+            translation: None,
+            caret: Caret::default(),
+            viewport_height: 0,
+            saved_view: None,
+            inspector: None,
+            request_buffer: String::new(),
+            picker: None,
+            url_bar: None,
+            dialog: None,
+            session_log: Vec::new(),
+            network_pane: false,
+            pending_key: None,
+            tabs: vec![Tab::new(start_url)],
+            active_tab: 0,
+            search: None,
+            hints: None,
+            editing_field: None,
+            smooth: None,
+            registers: BTreeMap::new(),
+            terminal_background: Background::Dark,
+            terminal_colors: colors::detect(),
+            terminal_graphics: graphics::detect(),
+            theme: None,
+            config,
+            register: None,
+            appended_pages: Vec::new(),
+            cache: PageCache::default(),
+            rendered: RenderedCache::default(),
+            partial: None,
+            download: None,
+            held: None,
+            stream: None,
+            previous_text: None,
+            watch: None,
+            tls_verified: false,
+            certs,
+            download_tx,
+            download_rx,
+            reading_list: ReadingList::load(storage::data_file("reading-list.jsonl")),
+            bookmarks: Bookmarks::load(storage::config_file("bookmarks.json")),
+            visits: VisitLog::load(storage::data_file("history.jsonl")),
+            zoom: SiteZoom::load(storage::data_file("zoom.json")),
+            autosave: Autosave::load(storage::data_file("session.json")),
+            sandbox: false,
+            bookmark_copies: BookmarkCopies::load(storage::data_file("bookmark-copies.json")),
+            last_monitor: Instant::now(),
+            monitor_tx,
+            monitor_rx,
+            mirror_tx,
+            mirror_rx,
+            loading: None,
+            load_tx,
+            load_rx,
+            next_load_id: 0,
+            shown: (start_url.to_string(), 0),
+            load_chain: Vec::new(),
+            restore_on_load: None,
+            prefetch_tx,
+            prefetch_rx,
+            exchange_rx,
+            job_tx,
+            job_rx,
         }
     }
 
-    pub async fn navigate(&mut self, mut url: String) -> Result<(), Box<dyn Error>> {
-        if !url.starts_with("http://") && !url.starts_with("https://") {
-            url = format!("https://{}", url);
+    pub async fn navigate(&mut self, url: String) -> Result<(), Box<dyn Error>> {
+        let mut url = address_to_url(&url);
+        if let Ok(parsed) = Url::parse(&url)
+            && let Some(publisher) = amp::unwrap_cache_url(&parsed).filter(|p| Url::parse(p).is_ok_and(|p| self.settings.deamp_enabled(&p)))
+        {
+            url = publisher;
+        }
+        if let Some(warning) = Url::parse(&url).ok().as_ref().and_then(homograph::check) {
+            self.confirm("Lookalike Domain", tr!("{}\n\nThis may be a phishing site. Open it anyway?", warning), format!("url! {}", url));
+            return Ok(());
         }
-        if !self.current_url.is_empty() {
-            self.history.push(self.current_url.clone());
+        if self.settings.expand_short && shortlink::is_shortened(&url) {
+            return self.preview_short_url(url);
         }
-        self.future.clear();
+        self.go_to(url).await
+    }
+
+    // Navigates without the AMP and short link checks
+    pub async fn go_to(&mut self, url: String) -> Result<(), Box<dyn Error>> {
+        let url = address_to_url(&url);
+        self.push_history(&url);
         self.current_url = url;
         self.fetch_page().await
     }
 
+    // The position of the rendered page, even while an alternate view covers it
+    pub fn position(&self) -> PagePosition {
+        match self.saved_view {
+            Some(ref v) => PagePosition { scroll: v.scroll, selected_link_idx: v.selected_link_idx },
+            None => PagePosition { scroll: self.scroll, selected_link_idx: self.selected_link_idx },
+        }
+    }
+
+    pub fn current_entry(&self) -> HistoryEntry {
+        HistoryEntry {
+            url: self.current_url.clone(),
+            title: self.page_info.title.clone(),
+            visited_at: self.visited_at,
+            position: self.position(),
+        }
+    }
+
+    // Before moving in the history: drops a load in flight, so the entry saved is the page on screen
+    fn leave_page(&mut self) {
+        if self.loading.is_some() {
+            self.cancel_load();
+        }
+        self.history.save(self.current_entry());
+        self.keep_rendered();
+    }
+
+    pub fn push_history(&mut self, url: &str) {
+        self.leave_page();
+        self.history.visit(url);
+    }
+
+    pub async fn go_back(&mut self) -> Result<(), Box<dyn Error>> {
+        self.leave_page();
+        match self.history.back() {
+            Some(entry) => self.revisit(entry).await,
+            None => { self.status = tr!("No previous page"); Ok(()) }
+        }
+    }
+
+    pub async fn go_forward(&mut self) -> Result<(), Box<dyn Error>> {
+        self.leave_page();
+        match self.history.forward() {
+            Some(entry) => self.revisit(entry).await,
+            None => { self.status = tr!("No next page in history"); Ok(()) }
+        }
+    }
+
+    pub async fn jump_history(&mut self, idx: usize) -> Result<(), Box<dyn Error>> {
+        self.leave_page();
+        match self.history.jump(idx) {
+            Some(entry) => self.revisit(entry).await,
+            None => Ok(()),
+        }
+    }
+
+    pub async fn revisit(&mut self, entry: HistoryEntry) -> Result<(), Box<dyn Error>> {
+        self.current_url = entry.url;
+        if self.restore_rendered() {
+            self.restore_position(entry.position);
+            return Ok(());
+        }
+        self.restore_on_load = Some(entry.position);
+        self.fetch_page().await
+    }
+
+    // Clamped, since the re-fetched page may have changed since it was read
+    fn restore_position(&mut self, pos: PagePosition) {
+        self.scroll = pos.scroll.min(self.max_scroll());
+        self.selected_link_idx = pos.selected_link_idx.min(self.links.len().saturating_sub(1));
+    }
+
+    // Network loads finish in the background (see loader.rs); everything else is done on return
     pub async fn fetch_page(&mut self) -> Result<(), Box<dyn Error>> {
-        self.status = format!("Fetching {}...", self.current_url);
-        let client = reqwest::Client::builder().user_agent("Voyager-Browser/0.1.0").build()?;
-        let res = client.get(&self.current_url).send().await?;
+        self.pending_refresh = None;
+        self.load_chain = vec![self.current_url.clone()];
+        self.load_current().await?;
+        self.finish_load().await
+    }
+
+    // Runs once the page is rendered: follows AMP and zero-delay refresh redirects, which
+    // may start another background load and come back here when it is done
+    pub async fn finish_load(&mut self) -> Result<(), Box<dyn Error>> {
+        loop {
+            if self.loading.is_some() {
+                return Ok(());
+            }
+            let target = if let Some(target) = self.canonical_for_amp() && !self.load_chain.contains(&target) && self.load_chain.len() <= MAX_REFRESH_HOPS {
+                self.notify(ToastKind::Info, tr!("AMP page, loading {} instead", target));
+                target
+            } else {
+                let Some((delay, target)) = self.page_info.refresh.clone() else { break };
+                if delay > 0 {
+                    self.status = tr!("Refresh to {} in {}s (R: follow now, X: cancel)", target, delay);
+                    self.pending_refresh = Some((Instant::now() + Duration::from_secs(delay), target));
+                    break;
+                }
+                if self.load_chain.contains(&target) || self.load_chain.len() > MAX_REFRESH_HOPS {
+                    self.status = tr!("Refresh loop stopped at {}", self.current_url);
+                    break;
+                }
+                target
+            };
+            self.load_chain.push(target.clone());
+            self.current_url = target;
+            self.load_current().await?;
+        }
+        if let Some(position) = self.restore_on_load.take() {
+            self.restore_position(position);
+        }
+        self.check_js_shell();
+        self.prefetch_pagination();
+        Ok(())
+    }
+
+    // Where an AMP page should be replaced by its regular version
+    fn canonical_for_amp(&self) -> Option<String> {
+        let url = Url::parse(&self.current_url).ok()?;
+        if !self.page_info.amp || !self.settings.deamp_enabled(&url) {
+            return None;
+        }
+        let target = match self.page_info.canonical.clone() {
+            Some(canonical) => canonical,
+            None => amp::strip_amp(&url)?,
+        };
+        (target != self.current_url && target.starts_with("http")).then_some(target)
+    }
+
+    pub async fn follow_refresh(&mut self) -> Result<(), Box<dyn Error>> {
+        match self.pending_refresh.take() {
+            Some((_, target)) if target == self.current_url => self.fetch_page().await,
+            Some((_, target)) => self.navigate(target).await,
+            None => Ok(()),
+        }
+    }
+
+    async fn load_current(&mut self) -> Result<(), Box<dyn Error>> {
         let base_url = Url::parse(&self.current_url)?;
-        let html = res.text().await?;
+        self.tls_verified = false;
+        if base_url.scheme() == "about" {
+            if let Some(html) = self.internal_page(&base_url) {
+                self.render_html(&html, &base_url);
+            }
+            return Ok(());
+        }
+        if base_url.scheme() == "file" {
+            return self.load_file(&base_url);
+        }
+        if let Some(page) = self.cache.take(&self.current_url) {
+            let outgoing = Outgoing::capture(&self.net.build(self.net.get(&self.current_url))?);
+            let render_start = Instant::now();
+            self.tls_verified = page.response.tls;
+            let result = self.render_received(&page.response, &page.body, &base_url);
+            self.log_exchange(outgoing, page.response, (Duration::ZERO, Duration::ZERO, render_start.elapsed()), page.body.len(), true);
+            self.status = tr!("Loaded from cache: {}", self.current_url);
+            return result;
+        }
+        if self.sandbox && let Some(page) = self.reading_list.get(&self.current_url) {
+            let html = page.html.clone();
+            self.render_html(&html, &base_url);
+            self.status = tr!("Loaded from reading list: {}", self.current_url);
+            return Ok(());
+        }
+        self.status = tr!("Fetching {}...", self.current_url);
+        self.start_fetch(self.net.get(&self.current_url), base_url)
+    }
 
-        let mut new_lines = Vec::new();
-        let mut new_links = Vec::new();
-        let mut link_counter = 0;
+    // Warms the cache with the page's next/prev targets without blocking the UI
+    pub fn prefetch_pagination(&mut self) {
+        if self.sandbox {
+            return;
+        }
+        for url in [self.page_info.next.clone(), self.page_info.prev.clone()].into_iter().flatten() {
+            if url != self.current_url && !self.cache.contains(&url) {
+                self.prefetch(&url);
+            }
+        }
+    }
 
-        let width = 100;
-        let rich_lines = html2text::from_read_rich(html.as_bytes(), width);
+    // Fetches `url` in the background into the page cache, picked up by `drain_prefetches`
+    pub fn prefetch(&self, url: &str) {
+        let Ok(req) = self.net.build(self.net.get(url)) else { return };
+        let net = self.net.clone();
+        let tx = self.prefetch_tx.clone();
+        tokio::spawn(async move {
+            let request = Outgoing::capture(&req);
+            let Ok(res) = net.execute(req, &request.probe).await else { return };
+            let ttfb = request.started.elapsed();
+            let response = Incoming::capture(&res);
+            let body_start = Instant::now();
+            let Ok(body) = Body::read(res, &response).await else { return };
+            let _ = tx.send(Prefetched { request, response, body, ttfb, receive: body_start.elapsed() });
+        });
+    }
 
-        for line in rich_lines {
-            let mut spans = Vec::new();
-            for tagged_string in line.tagged_strings() {
-                let mut style = Style::default();
-                let mut current_link = None;
-
-                for annotation in &tagged_string.tag {
-                    match annotation {
-                        RichAnnotation::Link(target) => {
-                            let abs = base_url.join(target).map(|u| u.to_string()).unwrap_or_else(|_| target.clone());
-                            current_link = Some((abs, LinkType::Web));
-                        }
-                        RichAnnotation::Image(src) => {
-                            let abs = base_url.join(src).map(|u| u.to_string()).unwrap_or_else(|_| src.clone());
-                            current_link = Some((abs, LinkType::Image));
-                        }
-                        RichAnnotation::Strong => style = style.add_modifier(Modifier::BOLD),
-                        _ => {}
-                    }
-                }
+    // Called every tick from the event loop to pick up finished background fetches
+    pub fn drain_prefetches(&mut self) {
+        while let Ok(p) = self.prefetch_rx.try_recv() {
+            let bytes = p.body.len();
+            if (200..300).contains(&p.response.status) {
+                self.cache.insert(p.request.url.clone(), p.response.clone(), p.body);
+            }
+            self.session_log.push(Exchange::new(p.request, p.response, (p.ttfb, p.receive, Duration::ZERO), bytes, false));
+        }
+    }
 
-                if let Some((url, ltype)) = current_link {
-                    let label = format!("[{}]", link_counter);
-                    spans.push(Span::styled(label, Style::default().fg(Color::DarkGray)));
-                    
-                    let link_style = match ltype {
-                        LinkType::Web => Style::default().fg(LINK_COLOR_WEB).add_modifier(Modifier::UNDERLINED),
-                        LinkType::Image => Style::default().fg(LINK_COLOR_IMG).add_modifier(Modifier::ITALIC),
-                    };
-                    spans.push(Span::styled(tagged_string.s.clone(), link_style));
-                    new_links.push(LinkData { url, link_type: ltype });
-                    link_counter += 1;
-                } else {
-                    spans.push(Span::styled(tagged_string.s.clone(), style));
-                }
+    // Called every tick from the event loop: logs what background fetches sent (see net.rs)
+    pub fn drain_exchanges(&mut self) {
+        while let Ok(exchange) = self.exchange_rx.try_recv() {
+            self.session_log.push(exchange);
+        }
+    }
+
+    // Reports background downloads that have finished
+    pub fn drain_downloads(&mut self) {
+        while let Ok(done) = self.download_rx.try_recv() {
+            match done.result {
+                Ok(bytes) => self.notify(ToastKind::Info, tr!("Downloaded {} to {}", format_size(bytes as usize), done.path)),
+                Err(e) => self.notify(ToastKind::Error, tr!("Download to {} failed: {}", done.path, e)),
+            }
+        }
+    }
+
+    // Called once per event-loop pass: keeps each distinct status line for `:messages`
+    pub fn log_status(&mut self) {
+        self.toasts.retain(|t| t.expires > Instant::now());
+        if self.messages.back().is_some_and(|(_, m)| *m == self.status) {
+            return;
+        }
+        if self.messages.len() >= MESSAGE_LOG_SIZE {
+            self.messages.pop_front();
+        }
+        self.messages.push_back((SystemTime::now(), self.status.clone()));
+    }
+
+    // Transient corner notification for events worth more than a status line
+    pub fn notify(&mut self, kind: ToastKind, message: String) {
+        if self.messages.len() >= MESSAGE_LOG_SIZE {
+            self.messages.pop_front();
+        }
+        self.messages.push_back((SystemTime::now(), message.clone()));
+        self.toasts.push(Toast { kind, message, expires: Instant::now() + TOAST_DURATION });
+    }
+
+    // What anything that goes over the network is sent through; refused under --sandbox
+    pub fn network(&self) -> Result<&Net, Box<dyn Error>> {
+        if self.sandbox {
+            return Err("network access is disabled (--sandbox)".into());
+        }
+        Ok(&self.net)
+    }
+
+    pub fn rebuild_client(&mut self) {
+        let client = build_client(&self.certs, &self.settings, &self.cookies, &self.redirect_log);
+        self.net.set_client(client, &self.settings);
+    }
+
+    // Sends a page the caller renders and logs, with its body timings, once it is read
+    pub async fn send(&mut self, builder: reqwest::RequestBuilder) -> Result<(reqwest::Response, Outgoing), Box<dyn Error>> {
+        let net = self.network()?;
+        let req = net.build(builder)?;
+        let outgoing = Outgoing::capture(&req);
+        let res = net.execute(req, &outgoing.probe).await?;
+        Ok((res, outgoing))
+    }
+
+    // Content-type dispatch shared by page loads and composed requests
+    pub async fn render_response(&mut self, res: reqwest::Response, base_url: &Url, outgoing: Outgoing) -> Result<(), Box<dyn Error>> {
+        let ttfb = outgoing.started.elapsed();
+        let limit = self.size_limit();
+        if limit > 0 && res.content_length().is_some_and(|len| len > limit) {
+            self.hold_response(HeldResponse { response: res, outgoing, ttfb, base_url: base_url.clone() });
+            return Ok(());
+        }
+        self.receive_response(res, base_url, outgoing, ttfb).await
+    }
+
+    pub async fn receive_response(&mut self, res: reqwest::Response, base_url: &Url, outgoing: Outgoing, ttfb: Duration) -> Result<(), Box<dyn Error>> {
+        let incoming = Incoming::capture(&res);
+        self.tls_verified = incoming.tls;
+        if is_streaming(&res, &incoming.mime_type) {
+            let render_start = Instant::now();
+            self.start_stream(res, base_url);
+            self.log_exchange(outgoing, incoming, (ttfb, Duration::ZERO, render_start.elapsed()), 0, false);
+            return Ok(());
+        }
+        // A body that doesn't say how long it is gets read up to the limit too, and is shown in
+        // full if it ends before that
+        let limit = self.chunk_limit();
+        let oversized = limit > 0 && res.content_length().is_none_or(|len| len as usize > limit);
+        if oversized && incoming.status < 300 && !incoming.mime_type.to_ascii_lowercase().starts_with("image/") {
+            let body_start = Instant::now();
+            let mut partial = PartialBody::new(&self.current_url, &incoming.mime_type, Vec::new(), res);
+            partial.read_more(limit).await?;
+            if looks_binary(&partial.received) {
+                partial.read_more(usize::MAX).await?;
+            }
+            let receive = body_start.elapsed();
+            let render_start = Instant::now();
+            let bytes = partial.received.len();
+            let result = self.render_partial(partial, base_url);
+            self.log_exchange(outgoing, incoming, (ttfb, receive, render_start.elapsed()), bytes, false);
+            return result;
+        }
+        let body_start = Instant::now();
+        let body = Body::read(res, &incoming).await?;
+        let receive = body_start.elapsed();
+        let render_start = Instant::now();
+        let result = self.render_received(&incoming, &body, base_url);
+        self.log_exchange(outgoing, incoming, (ttfb, receive, render_start.elapsed()), body.len(), false);
+        result
+    }
+
+    // Error responses, and redirects that weren't followed, get a page saying so with the
+    // server's own page below it; they are kept out of the search index and visit counts
+    pub fn render_received(&mut self, incoming: &Incoming, body: &Body, base_url: &Url) -> Result<(), Box<dyn Error>> {
+        if incoming.status < 300 {
+            return self.render_body(&incoming.mime_type, body, base_url);
+        }
+        let mime_type = incoming.mime_type.to_ascii_lowercase();
+        let server_page = match body {
+            Body::Text(text) if text.trim().is_empty() => None,
+            Body::Text(text) if mime_type.is_empty() || mime_type.contains("html") => Some(text.clone()),
+            Body::Text(text) => Some(format!("<pre>{}</pre>", about::escape_html(text))),
+            Body::Bytes(_) | Body::Binary(_) => None,
+        };
+        let header = |name: &str| incoming.headers.iter().find(|(k, _)| k.eq_ignore_ascii_case(name)).map(|(_, v)| v.clone());
+        let location = header("location").map(|l| base_url.join(&l).map(|u| u.to_string()).unwrap_or(l));
+        let retry_after = header("retry-after");
+        let stopped = self.redirect_log.take_stopped(base_url.as_str()).map(|stop| match stop {
+            Stop::Limit => tr!("This redirect wasn't followed: it would go past the limit of {} redirects (:set redirects) or back to an address already visited.", self.settings.redirects),
+            Stop::Lookalike(warning) => tr!("This redirect wasn't followed: it leads to a lookalike domain. {}", warning),
+            Stop::SiteHeaders { .. } => tr!("This redirect wasn't followed: it leads to another site, and the request can't be sent again there without the headers voyager.toml sets for this one."),
+        });
+        let html = about::status_page(
+            (incoming.status, &incoming.status_text),
+            &self.current_url,
+            location.as_deref(),
+            retry_after.as_deref(),
+            stopped,
+            server_page.as_deref(),
+        );
+        self.render_html(&html, base_url);
+        if let Body::Text(text) = body {
+            self.fetched_source = Some(text.clone());
+        }
+        self.status = tr!("HTTP {} {}: {}", incoming.status, incoming.status_text, self.current_url);
+        Ok(())
+    }
+
+    pub fn render_body(&mut self, mime_type: &str, body: &Body, base_url: &Url) -> Result<(), Box<dyn Error>> {
+        let content_type = if mime_type.is_empty() { "text/html".to_string() } else { mime_type.to_ascii_lowercase() };
+        match body {
+            Body::Bytes(bytes) => {
+                self.render_html("", base_url);
+                return self.preview_image_bytes(bytes);
+            }
+            Body::Binary(bytes) => {
+                self.offer_download(&content_type, bytes.clone(), base_url);
+                return Ok(());
+            }
+            Body::Text(text) if content_type.contains("html") || content_type.contains("xml") => self.render_html(text, base_url),
+            Body::Text(text) => {
+                self.render_html(&format!("<pre>{}</pre>", about::escape_html(text)), base_url);
+                self.fetched_source = Some(text.clone());
             }
-            new_lines.push(Line::from(spans));
         }
+        let text = self.page_text();
+        let title = self.page_info.title.clone().unwrap_or_default();
+        self.previous_text = self.index.text_of(&self.current_url).map(str::to_string);
+        self.index.add_page(&self.current_url, &title, &text);
+        self.visits.record(&self.current_url, &title);
+        self.bookmark_seen();
+        Ok(())
+    }
+
+    // `durations` is (ttfb, body download, render)
+    pub fn log_exchange(&mut self, request: Outgoing, response: Incoming, durations: (Duration, Duration, Duration), bytes: usize, from_cache: bool) {
+        self.session_log.push(Exchange::new(request, response, durations, bytes, from_cache));
+    }
+
+    // None when the page is being built on a task, which renders it when it's done
+    fn internal_page(&mut self, url: &Url) -> Option<String> {
+        let query = |key: &str| url.query_pairs().find(|(k, _)| k == key).map(|(_, v)| v.into_owned()).unwrap_or_default();
+        let html = match url.path() {
+            "grep" => about::grep_page(&self.index, &query("q")),
+            "frames" => about::frames_page(&self.page_info.frames),
+            "help" => help::help_page(&self.settings, &self.config),
+            "stats" => stats::stats_page(&self.visits.visits),
+            "reading-list" => readlist::reading_list_page(&self.reading_list, self.sandbox),
+            "cookies" => cookies::cookies_page(&self.cookies, &query("domain")),
+            "history" => visits::history_page(&self.visits, &query("q")),
+            "bookmarks" => bookmarks::bookmarks_page(&self.bookmarks, &query("tag"), &query("folder")),
+            "meta" if self.sandbox => "<h1>meta</h1><p>Search engines can't be queried in sandbox mode.</p>".to_string(),
+            "meta" => {
+                let (q, engines, net) = (query("q"), self.settings.meta_engines.clone(), self.net.clone());
+                self.status = tr!("Querying {} engines...", engines.len());
+                self.start_internal(url.clone(), async move {
+                    let results = meta::meta_search(&net, &engines, &q).await;
+                    about::meta_page(&q, &results)
+                });
+                return None;
+            }
+            "sitemap" | "robots" if self.sandbox => format!("<h1>{}</h1><p>Sites can't be fetched in sandbox mode.</p>", url.path()),
+            path @ ("sitemap" | "robots") => {
+                let Ok(target) = Url::parse(&query("url")) else {
+                    return Some(format!("<h1>{}</h1><p>No site given.</p>", path));
+                };
+                self.status = tr!("Fetching the {} for {}...", path, target.host_str().unwrap_or_default());
+                let (robots, net) = (path == "robots", self.net.clone());
+                self.start_internal(url.clone(), async move {
+                    match robots {
+                        true => sitemap::robots_page(&net, &target).await,
+                        false => sitemap::sitemap_page(&net, &target).await,
+                    }
+                });
+                return None;
+            }
+            other => format!("<h1>Unknown page</h1><p>about:{}</p>", about::escape_html(other)),
+        };
+        Some(html)
+    }
+
+    pub fn open_popup(&mut self, title: &str, lines: Vec<Line<'static>>) {
+        self.popup = Some(Popup { title: format!(" {} ", i18n::lookup(title)), lines, scroll: 0, actions: Vec::new(), selected: 0 });
+    }
+
+    pub fn open_menu(&mut self, title: &str, entries: Vec<(Line<'static>, PopupAction)>) {
+        let (lines, actions) = entries.into_iter().unzip();
+        self.popup = Some(Popup { title: format!(" {} ", i18n::lookup(title)), lines, scroll: 0, actions, selected: 0 });
+    }
+
+    pub async fn run_popup_action(&mut self, action: PopupAction) -> Result<(), Box<dyn Error>> {
+        match action {
+            PopupAction::JumpToLine(line) => self.scroll = line,
+            PopupAction::JumpHistory(idx) => self.jump_history(idx).await?,
+            PopupAction::Resource(url) => self.resource_menu(&url),
+            PopupAction::FormOption(form, field, option) => self.choose_option(form, field, option),
+        }
+        Ok(())
+    }
+
+    pub fn page_text(&self) -> String {
+        self.content_lines.iter()
+            .map(|line| line.spans.iter().map(|s| s.content.as_ref()).collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
 
-        self.content_lines = new_lines;
-        self.links = new_links;
+    pub fn render_html(&mut self, html: &str, base_url: &Url) {
+        self.raw_html = html.to_string();
+        self.fetched_source = None;
+        self.partial = None;
+        self.download = None;
+        self.held = None;
+        self.stream = None;
+        self.visited_at = SystemTime::now();
+        self.translation = None;
+        self.saved_view = None;
+        self.inspector = None;
+        self.search = None;
+        if self.hints.take().is_some() {
+            self.mode = Mode::Normal;
+        }
+        self.shown = (self.current_url.clone(), self.history.current);
+        self.page_info.forms.clear();
+        self.layout_page(base_url);
         self.selected_link_idx = 0;
         self.scroll = 0;
-        self.status = format!("Loaded: {}", self.current_url);
+        self.status = tr!("Loaded: {}", self.current_url);
+    }
+
+    // Re-lays out the retained HTML after a render setting changes, keeping the reading position
+    pub fn rerender(&mut self) {
+        let Ok(base_url) = Url::parse(&self.current_url) else { return };
+        self.restore_view();
+        self.translation = None;
+        self.layout_page(&base_url);
+        self.selected_link_idx = self.selected_link_idx.min(self.links.len().saturating_sub(1));
+        self.scroll = self.scroll.min(self.content_lines.len().saturating_sub(1) as u16);
+    }
+
+    fn layout_page(&mut self, base_url: &Url) {
+        let (html, info) = page::prepare(&self.raw_html, base_url, &self.page_info.forms);
+        // Reader mode lays out just the article; the page's metadata still comes from all of it
+        let html = match self.settings.reader && base_url.scheme() != "about" {
+            true => reader::extract(&html).unwrap_or(html),
+            false => html,
+        };
+        self.page_info = info;
+        self.appended_pages.clear();
+        let (lines, links) = self.layout_html(&html, base_url, 0);
+        self.content_lines = lines;
+        self.links = links;
+        self.stats = TextStats::from_text(&self.page_text());
+    }
+
+    // `first_link` offsets the [N] labels for content appended after already numbered links
+    pub fn layout_html(&self, html: &str, base_url: &Url, first_link: usize) -> (Vec<Line<'static>>, Vec<LinkData>) {
+        let width = self.zoomed_width().unwrap_or(zoom::BASE_WIDTH).saturating_sub(typeset::reserved_width(&self.settings)).max(20);
+        // Tables are drawn before html2text sees them (see table.rs); with showurls on, the URLs
+        // after links would push their columns apart, so html2text lays them out instead
+        let drawn = match self.settings.show_urls {
+            true => None,
+            false => table::draw_tables(html, width, table::label_width(html, &self.settings, first_link)),
+        };
+        let html = drawn.as_deref().unwrap_or(html);
+        let rich_lines = html2text::from_read_rich(html.as_bytes(), width);
+
+        let settings = &self.settings;
+        let mut laid_out = style_lines(&rich_lines, base_url, settings, &self.page_info.media);
+        for (i, &(line, span)) in laid_out.labels.iter().enumerate() {
+            let n = first_link + i;
+            let label = if settings.footnotes { superscript(n) } else { format!("[{}]", n) };
+            table::make_room(&mut laid_out.lines[line].spans[span + 1..], label.chars().count());
+            laid_out.lines[line].spans[span].content = label.into();
+        }
+        let new_links = laid_out.links;
+        let mut new_lines = typeset::apply(laid_out.lines, settings);
+        // Footnote style: the URLs are listed after the text, numbered like the markers
+        if settings.footnotes && !new_links.is_empty() {
+            let dim = Style::default().fg(Color::DarkGray);
+            new_lines.push(Line::from(""));
+            new_lines.push(Line::from(Span::styled(tr!("References"), Style::default().add_modifier(Modifier::BOLD))));
+            new_lines.push(Line::from(""));
+            for (i, link) in new_links.iter().enumerate().filter(|(_, l)| l.link_type != LinkType::Field) {
+                new_lines.push(Line::from(Span::styled(format!("{:>4}. {}", first_link + i, link.url), dim)));
+            }
+        }
+        (new_lines, new_links)
+    }
+
+    // Replaces the content pane with an alternate view, stashing the rendered page
+    pub fn show_view(&mut self, kind: ViewKind, lines: Vec<Line<'static>>, links: Vec<LinkData>) {
+        if self.saved_view.is_some() {
+            self.restore_view();
+        }
+        self.saved_view = Some(SavedView {
+            kind,
+            lines: std::mem::replace(&mut self.content_lines, lines),
+            links: std::mem::replace(&mut self.links, links),
+            scroll: std::mem::take(&mut self.scroll),
+            selected_link_idx: std::mem::take(&mut self.selected_link_idx),
+        });
+    }
+
+    pub fn restore_view(&mut self) {
+        if let Some(saved) = self.saved_view.take() {
+            self.content_lines = saved.lines;
+            self.links = saved.links;
+            self.scroll = saved.scroll;
+            self.selected_link_idx = saved.selected_link_idx;
+        }
+    }
+
+    pub fn toggle_source(&mut self) {
+        if self.saved_view.as_ref().is_some_and(|v| v.kind == ViewKind::Source) {
+            self.restore_view();
+            self.status = tr!("Rendered view: {}", self.current_url);
+            return;
+        }
+        let source = highlight::highlight_html(self.fetched_source.as_deref().unwrap_or(&self.raw_html));
+        self.show_view(ViewKind::Source, source, Vec::new());
+        self.status = tr!("Source view ({} lines) — \\ to toggle", self.content_lines.len());
+    }
+
+    pub async fn follow_link(&mut self, idx: usize) -> Result<(), Box<dyn Error>> {
+        let Some(link) = self.links.get(idx).cloned() else { return Ok(()) };
+        if link.url == LOAD_MORE_URL {
+            return self.load_more().await;
+        }
+        match link.link_type {
+            LinkType::Image => self.preview_image(&link.url),
+            LinkType::Web => self.navigate(link.url).await,
+            LinkType::Media => { self.open_media(&link.url); Ok(()) }
+            LinkType::Field => self.activate_field(&link.url).await,
+        }
+    }
+
+    pub fn preview_image(&mut self, url: &str) -> Result<(), Box<dyn Error>> {
+        if self.settings.ascii {
+            self.status = tr!("Image previews are off in ascii mode: {}", url);
+            return Ok(());
+        }
+        self.status = tr!("Processing Image AA: {}...", url);
+        let (net, url) = (self.network()?.clone(), url.to_string());
+        self.spawn_job(async move {
+            let result = jobs::receive(&net, net.get(&url), true).await;
+            Job::Image { url, result }
+        });
         Ok(())
     }
 
-    pub async fn preview_image(&mut self, url: &str) -> Result<(), Box<dyn Error>> {
-        self.status = format!("Processing Image AA: {}...", url);
-        let res = reqwest::get(url).await?.bytes().await?;
-        let img = image::load_from_memory(&res)?;
+    pub fn show_image(&mut self, received: Received) -> Result<(), Box<dyn Error>> {
+        let render_start = Instant::now();
+        let result = self.preview_image_bytes(&received.body);
+        let durations = (received.ttfb, received.receive, render_start.elapsed());
+        self.log_exchange(received.outgoing, received.incoming, durations, received.body.len(), false);
+        result
+    }
+
+    fn preview_image_bytes(&mut self, bytes: &[u8]) -> Result<(), Box<dyn Error>> {
+        if self.settings.ascii {
+            self.status = tr!("Image previews are off in ascii mode");
+            return Ok(());
+        }
+        let img = image::load_from_memory(bytes)?;
         
         let (w, h) = img.dimensions();
         let new_w = 80u32;
@@ -131,27 +1073,68 @@ impl App {
             }
             aa.push(row);
         }
-        self.image_preview = Some(aa);
-        self.status = "Image AA Loaded. Press ESC to close.".to_string();
+        self.image_preview = Some(ImagePreview::new(aa, img));
+        self.status = match self.graphics() {
+            Graphics::Text => tr!("Image AA Loaded. Press ESC to close."),
+            _ => tr!("Image loaded. Press ESC to close."),
+        };
         Ok(())
     }
 
+    pub fn max_scroll(&self) -> u16 {
+        self.max_scroll_in(self.viewport_height)
+    }
+
+    fn max_scroll_in(&self, height: u16) -> u16 {
+        self.content_lines.len().saturating_sub(height.max(1) as usize) as u16
+    }
+
+    // "line 120/2,340 (5%)" for the status bar, with the page drawn `height` rows high
+    pub fn position_summary(&self, height: u16) -> String {
+        let total = self.content_lines.len().max(1);
+        let line = (self.scroll as usize + 1).min(total);
+        let group = |n: usize| {
+            let digits = n.to_string();
+            let mut out = String::new();
+            for (i, c) in digits.chars().enumerate() {
+                if i > 0 && (digits.len() - i).is_multiple_of(3) { out.push(','); }
+                out.push(c);
+            }
+            out
+        };
+        let percent = if self.scroll >= self.max_scroll_in(height) { 100 } else { line * 100 / total };
+        tr!("line {}/{} ({}%)", group(line), group(total), percent)
+    }
+
+    // Visible text of each link, parallel to `links`
+    pub fn link_texts(&self) -> Vec<String> {
+        self.content_lines.iter()
+            .flat_map(|line| line.spans.iter())
+            .filter(|s| is_link_style(s.style))
+            .map(|s| s.content.to_string())
+            .collect()
+    }
+
     pub fn render_content(&self) -> Vec<Line<'static>> {
+        let highlight = self.palette().highlight();
         let mut rendered = Vec::new();
         let mut current_idx = 0;
-        for line in &self.content_lines {
+        for (row, line) in self.content_lines.iter().enumerate() {
             let mut spans = Vec::new();
             for span in &line.spans {
                 let mut s = span.clone();
-                if s.style.fg == Some(LINK_COLOR_WEB) || s.style.fg == Some(LINK_COLOR_IMG) {
+                if is_link_style(s.style) {
+                    if let Some(label) = self.hint_label(current_idx) {
+                        spans.push(label);
+                    }
                     if current_idx == self.selected_link_idx {
-                        s.style = s.style.bg(Color::Yellow).fg(Color::Black).add_modifier(Modifier::BOLD);
+                        s.style = s.style.patch(highlight).add_modifier(Modifier::BOLD);
                     }
                     current_idx += 1;
                 }
                 spans.push(s);
             }
-            rendered.push(Line::from(spans));
+            rendered.push(self.caret_overlay(row, self.search_overlay(row, Line::from(spans))));
         }
         rendered
     }
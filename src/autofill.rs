@@ -0,0 +1,114 @@
+use crate::app::App;
+use crate::forms::{Field, FieldKind};
+use crate::i18n::tr;
+use crate::storage;
+use ratatui::{style::{Color, Style}, text::{Line, Span}};
+use std::collections::BTreeMap;
+use std::error::Error;
+
+pub type Profile = BTreeMap<String, String>;
+
+// Other spellings sites use for a profile key
+const SYNONYMS: &[(&str, &[&str])] = &[
+    ("name", &["fullname", "yourname"]),
+    ("email", &["mail"]),
+    ("phone", &["tel", "telephone", "mobile"]),
+    ("street", &["address", "addressline1", "streetaddress"]),
+    ("postalcode", &["zip", "zipcode", "postcode"]),
+    ("city", &["town", "locality"]),
+    ("country", &["countryname"]),
+];
+
+// Named profiles from autofill.json in the config directory, e.g.
+// {"home": {"name": "Ada Lovelace", "email": "ada@example.org", "postal-code": "W1"}}
+pub fn load_profiles() -> Result<BTreeMap<String, Profile>, Box<dyn Error>> {
+    let path = storage::config_file("autofill.json");
+    let data = std::fs::read_to_string(&path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    Ok(serde_json::from_str(&data)?)
+}
+
+fn normalize(s: &str) -> String {
+    s.chars().filter(|c| c.is_alphanumeric()).flat_map(char::to_lowercase).collect()
+}
+
+fn fillable(field: &Field) -> bool {
+    matches!(field.kind, FieldKind::Text | FieldKind::TextArea | FieldKind::Select) && !field.name.is_empty()
+}
+
+// Normalized autocomplete token, then name, id, aria-label, label text and placeholder
+fn hints(field: &Field) -> Vec<String> {
+    let mut hints = field.hints.clone();
+    hints.insert(1, field.name.clone());
+    hints.push(field.caption.clone());
+    hints.iter().map(|h| normalize(h)).collect()
+}
+
+// 3: the autocomplete token is the key, 2: another hint is, 1: a hint contains the key
+fn score(hints: &[String], key: &str) -> u8 {
+    let key = normalize(key);
+    let mut names = vec![key.clone()];
+    if let Some((_, alts)) = SYNONYMS.iter().find(|(k, _)| *k == key) {
+        names.extend(alts.iter().map(|a| a.to_string()));
+    }
+    let mut best = 0;
+    for (i, hint) in hints.iter().enumerate().filter(|(_, h)| !h.is_empty()) {
+        for name in &names {
+            let s = if hint == name && i == 0 { 3 } else if hint == name { 2 } else if name.len() >= 3 && hint.contains(name.as_str()) { 1 } else { 0 };
+            best = best.max(s);
+        }
+    }
+    best
+}
+
+// The value a field takes: a <select> only takes one of its options, matched by value or label
+fn value_for(field: &Field, value: &str) -> Option<String> {
+    if field.kind != FieldKind::Select {
+        return Some(value.to_string());
+    }
+    field.options.iter()
+        .find(|(v, l)| v.eq_ignore_ascii_case(value) || l.eq_ignore_ascii_case(value))
+        .map(|(v, _)| v.clone())
+}
+
+// (field index, profile key, value), one per fillable field that some profile key matches
+pub fn match_profile(fields: &[Field], profile: &Profile) -> Vec<(usize, String, String)> {
+    fields.iter().enumerate().filter(|(_, f)| fillable(f)).filter_map(|(i, field)| {
+        let hints = hints(field);
+        let (key, value) = profile.iter().max_by_key(|(k, _)| score(&hints, k)).filter(|(k, _)| score(&hints, k) > 0)?;
+        Some((i, key.clone(), value_for(field, value)?))
+    }).collect()
+}
+
+impl App {
+    pub fn fill_form(&mut self, profile_name: &str) -> Result<(), Box<dyn Error>> {
+        let profiles = load_profiles()?;
+        if profile_name.is_empty() {
+            let names: Vec<&str> = profiles.keys().map(String::as_str).collect();
+            self.status = tr!("fill: profiles are {}", names.join(", "));
+            return Ok(());
+        }
+        let profile = profiles.get(profile_name).ok_or_else(|| format!("no autofill profile {:?}", profile_name))?;
+        let total = self.page_info.forms.iter().flat_map(|f| &f.fields).filter(|f| fillable(f)).count();
+        if total == 0 {
+            self.status = tr!("fill: no form fields on this page");
+            return Ok(());
+        }
+        // Values go straight into the page's forms, as if typed, and show on the next render
+        let mut lines = Vec::new();
+        for form in &mut self.page_info.forms {
+            for (i, key, value) in match_profile(&form.fields, profile) {
+                let field = &mut form.fields[i];
+                lines.push(Line::from(vec![
+                    Span::styled(format!("{:<24}", field.name), Style::default().fg(Color::Yellow)),
+                    Span::raw(format!(" ← {} = {}", key, value)),
+                ]));
+                field.value = value;
+            }
+        }
+        let filled = lines.len();
+        self.rerender();
+        self.open_popup(&tr!("Autofill: {}", profile_name), lines);
+        self.status = tr!("fill: filled {} of {} field(s) from {}", filled, total, format!("{:?}", profile_name));
+        Ok(())
+    }
+}
@@ -0,0 +1,64 @@
+use crate::app::App;
+use crate::cache::Body;
+use crate::har::Incoming;
+use crate::i18n::tr;
+use crate::page;
+use crate::types::ToastKind;
+use ratatui::{style::{Color, Style}, text::{Line, Span}};
+use std::error::Error;
+use std::time::{Duration, Instant};
+use url::Url;
+
+impl App {
+    // With `autopage` on, reaching the end of the buffer appends the rel=next page below it
+    pub async fn auto_paginate(&mut self) {
+        if !self.settings.auto_paginate || self.saved_view.is_some() || self.translation.is_some() || self.content_lines.is_empty() {
+            return;
+        }
+        if (self.scroll as usize) + (self.viewport_height as usize) < self.content_lines.len() {
+            return;
+        }
+        let Some(next) = self.page_info.next.clone() else { return };
+        if next == self.current_url || self.appended_pages.contains(&next) {
+            return;
+        }
+        // Recorded before fetching so a failing page is not retried on every tick
+        self.appended_pages.push(next.clone());
+        let Ok(base) = Url::parse(&next) else { return };
+        self.status = tr!("Appending {}...", next);
+        let html = match self.fetch_html(&next).await {
+            Ok(html) => html,
+            Err(e) => {
+                self.notify(ToastKind::Error, tr!("autopage: {}", e));
+                return;
+            }
+        };
+        let (html, info) = page::prepare(&html, &base, &[]);
+        let (lines, links) = self.layout_html(&html, &base, self.links.len());
+        let separator = format!("──── page {}: {} ────", self.appended_pages.len() + 1, next);
+        self.content_lines.push(Line::from(""));
+        self.content_lines.push(Line::from(Span::styled(separator, Style::default().fg(Color::DarkGray))));
+        self.content_lines.extend(lines);
+        self.links.extend(links);
+        self.page_info.next = info.next;
+        self.page_info.prev = None;
+        self.status = tr!("Appended page {} of {}", self.appended_pages.len() + 1, self.current_url);
+        self.prefetch_pagination();
+    }
+
+    async fn fetch_html(&mut self, url: &str) -> Result<String, Box<dyn Error>> {
+        if let Some(page) = self.cache.take(url) {
+            return match page.body {
+                Body::Text(text) => Ok(text),
+                Body::Bytes(_) | Body::Binary(_) => Err("next page is not HTML".into()),
+            };
+        }
+        let (res, outgoing) = self.send(self.net.get(url)).await?;
+        let ttfb = outgoing.started.elapsed();
+        let incoming = Incoming::capture(&res);
+        let body_start = Instant::now();
+        let text = res.text().await?;
+        self.log_exchange(outgoing, incoming, (ttfb, body_start.elapsed(), Duration::ZERO), text.len(), false);
+        Ok(text)
+    }
+}
@@ -0,0 +1,87 @@
+use crate::app::App;
+use crate::download::{spawn_download, suggested_name};
+use crate::i18n::tr;
+use crate::pattern::Pattern;
+use std::collections::HashSet;
+use std::error::Error;
+use std::path::Path;
+
+// A free file name in the working directory: "name", then "name-1.ext", "name-2.ext", ...
+fn free_name(name: &str, taken: &HashSet<String>) -> String {
+    let (stem, ext) = match name.rsplit_once('.') {
+        Some((stem, ext)) if !stem.is_empty() => (stem, format!(".{}", ext)),
+        _ => (name, String::new()),
+    };
+    let mut candidate = name.to_string();
+    let mut n = 1;
+    while taken.contains(&candidate) || Path::new(&candidate).exists() {
+        candidate = format!("{}-{}{}", stem, n, ext);
+        n += 1;
+    }
+    candidate
+}
+
+impl App {
+    // Distinct link URLs in page order whose text or URL matches `pattern` (all links when empty)
+    pub fn matching_links(&self, pattern: &str) -> Result<Vec<String>, String> {
+        let pattern = match pattern.trim() {
+            "" => None,
+            p => Some(Pattern::parse(p)?),
+        };
+        let mut urls: Vec<String> = Vec::new();
+        for (text, link) in self.link_texts().iter().zip(&self.links) {
+            let wanted = pattern.as_ref().is_none_or(|p| p.is_match(text) || p.is_match(&link.url));
+            if wanted && !urls.contains(&link.url) {
+                urls.push(link.url.clone());
+            }
+        }
+        Ok(urls)
+    }
+
+    fn batch_links(&mut self, command: &str, pattern: &str) -> Option<Vec<String>> {
+        if pattern.trim().is_empty() {
+            self.status = tr!("{}: expected a pattern", command);
+            return None;
+        }
+        match self.matching_links(pattern) {
+            Ok(urls) if urls.is_empty() => self.status = tr!("{}: no matching links", command),
+            Ok(urls) => return Some(urls),
+            Err(e) => self.status = format!("{}: {}", command, e),
+        }
+        None
+    }
+
+    // Each link gets a tab after the shown one, in page order, and loads while this page stays up
+    pub async fn open_all(&mut self, pattern: &str, force: bool) -> Result<(), Box<dyn Error>> {
+        let Some(urls) = self.batch_links("open-all", pattern) else { return Ok(()) };
+        self.network()?;
+        if !force {
+            self.confirm("Open All", tr!("Open {} matching links in background tabs?", urls.len()), format!("open-all! {}", pattern));
+            return Ok(());
+        }
+        for (i, url) in urls.iter().enumerate() {
+            self.background_tab(self.active_tab + 1 + i, url).await?;
+        }
+        self.status = tr!("Opened {} background tabs (gt to go through them)", urls.len());
+        Ok(())
+    }
+
+    pub fn download_all(&mut self, pattern: &str, force: bool) -> Result<(), Box<dyn Error>> {
+        let Some(urls) = self.batch_links("download-all", pattern) else { return Ok(()) };
+        let net = self.network()?.clone();
+        if !force {
+            let dir = std::env::current_dir().map(|d| d.display().to_string()).unwrap_or_else(|_| ".".to_string());
+            let message = tr!("Download {} matching links into {}?\nExisting files are kept; new names get a -N suffix.", urls.len(), dir);
+            self.confirm("Download All", message, format!("download-all! {}", pattern));
+            return Ok(());
+        }
+        let mut taken = HashSet::new();
+        for url in &urls {
+            let path = free_name(&suggested_name(url), &taken);
+            taken.insert(path.clone());
+            spawn_download(net.clone(), url.clone(), path, self.download_tx.clone());
+        }
+        self.status = tr!("Downloading {} files in the background", urls.len());
+        Ok(())
+    }
+}
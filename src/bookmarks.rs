@@ -0,0 +1,277 @@
+use crate::about::escape_html;
+use crate::app::App;
+use crate::i18n::tr;
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Bookmark {
+    pub url: String,
+    #[serde(default)]
+    pub title: String,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    // Slash-separated path of nested folders, e.g. "dev/rust"; empty at the top level
+    #[serde(default)]
+    pub folder: String,
+    pub added_at: u64,
+    // Re-fetched in the background and compared with the copy last seen (see monitor.rs)
+    #[serde(default)]
+    pub watch: bool,
+    // The live page no longer renders the same as that copy
+    #[serde(default)]
+    pub changed: bool,
+}
+
+impl Bookmark {
+    // In `folder` itself or any folder below it
+    pub fn in_folder(&self, folder: &str) -> bool {
+        folder.is_empty() || self.folder == folder || self.folder.starts_with(&format!("{}/", folder))
+    }
+}
+
+// Kept in the config directory as one JSON array, so it can be edited by hand
+pub struct Bookmarks {
+    path: PathBuf,
+    pub entries: Vec<Bookmark>,
+    // Why the file couldn't be read; until it's fixed nothing is written over it
+    pub unreadable: Option<String>,
+}
+
+// "dev / rust/" -> "dev/rust"
+pub fn normalize_folder(folder: &str) -> String {
+    folder.split('/').map(str::trim).filter(|p| !p.is_empty()).collect::<Vec<_>>().join("/")
+}
+
+pub fn parse_tags(list: &str) -> Vec<String> {
+    let mut tags: Vec<String> = Vec::new();
+    for tag in list.split(',').map(|t| t.trim().trim_start_matches('#').to_lowercase()).filter(|t| !t.is_empty()) {
+        if !tags.contains(&tag) {
+            tags.push(tag);
+        }
+    }
+    tags
+}
+
+impl Bookmarks {
+    pub fn load(path: PathBuf) -> Self {
+        let loaded = match fs::read_to_string(&path) {
+            Ok(data) => serde_json::from_str(&data).map_err(|e| e.to_string()),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Vec::new()),
+            Err(e) => Err(e.to_string()),
+        };
+        match loaded {
+            Ok(entries) => Self { path, entries, unreadable: None },
+            Err(e) => {
+                let unreadable = Some(format!("{}: {}", path.display(), e));
+                Self { path, entries: Vec::new(), unreadable }
+            }
+        }
+    }
+
+    // Bookmarking a page again moves it to `folder` and adds `tags` to the ones it had
+    pub fn add(&mut self, url: &str, title: &str, tags: Vec<String>, folder: &str) -> io::Result<()> {
+        let added_at = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+        let folder = normalize_folder(folder);
+        match self.entries.iter_mut().find(|b| b.url == url) {
+            Some(existing) => {
+                existing.title = title.to_string();
+                existing.folder = folder;
+                for tag in tags {
+                    if !existing.tags.contains(&tag) {
+                        existing.tags.push(tag);
+                    }
+                }
+            }
+            None => self.entries.push(Bookmark { url: url.to_string(), title: title.to_string(), tags, folder, added_at, watch: false, changed: false }),
+        }
+        self.write()
+    }
+
+    pub fn remove(&mut self, url: &str) -> io::Result<bool> {
+        let before = self.entries.len();
+        self.entries.retain(|b| b.url != url);
+        if self.entries.len() == before {
+            return Ok(false);
+        }
+        self.write().map(|_| true)
+    }
+
+    // By the 1-based number shown on the bookmarks page
+    pub fn remove_at(&mut self, n: usize) -> io::Result<Option<Bookmark>> {
+        if n == 0 || n > self.entries.len() {
+            return Ok(None);
+        }
+        let removed = self.entries.remove(n - 1);
+        self.write().map(|_| Some(removed))
+    }
+
+    // Returns the new watch state, or None when the page isn't bookmarked
+    pub fn toggle_watch(&mut self, url: &str) -> io::Result<Option<bool>> {
+        let Some(bookmark) = self.entries.iter_mut().find(|b| b.url == url) else { return Ok(None) };
+        bookmark.watch = !bookmark.watch;
+        bookmark.changed = false;
+        let watch = bookmark.watch;
+        self.write().map(|_| Some(watch))
+    }
+
+    pub fn set_changed(&mut self, url: &str, changed: bool) -> io::Result<()> {
+        match self.entries.iter_mut().find(|b| b.url == url) {
+            Some(bookmark) if bookmark.changed != changed => bookmark.changed = changed,
+            _ => return Ok(()),
+        }
+        self.write()
+    }
+
+    pub fn get(&self, url: &str) -> Option<&Bookmark> {
+        self.entries.iter().find(|b| b.url == url)
+    }
+
+    // Every tag in use, with how many bookmarks carry it, most used first
+    pub fn tag_counts(&self) -> Vec<(String, usize)> {
+        let mut counts: Vec<(String, usize)> = Vec::new();
+        for tag in self.entries.iter().flat_map(|b| &b.tags) {
+            match counts.iter_mut().find(|(t, _)| t == tag) {
+                Some((_, n)) => *n += 1,
+                None => counts.push((tag.clone(), 1)),
+            }
+        }
+        counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        counts
+    }
+
+    fn write(&self) -> io::Result<()> {
+        // An empty list would replace bookmarks that are only unreadable for now
+        if let Some(ref e) = self.unreadable {
+            return Err(io::Error::other(format!("not saved, since {} couldn't be read; fix it and restart", e)));
+        }
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(&self.path, serde_json::to_string_pretty(&self.entries)?)
+    }
+}
+
+fn encode(value: &str) -> String {
+    url::form_urlencoded::byte_serialize(value.as_bytes()).collect()
+}
+
+fn folder_link(path: &str, name: &str) -> String {
+    format!("<a href=\"about:bookmarks?folder={}\">{}</a>", escape_html(&encode(path)), escape_html(name))
+}
+
+// The bookmark manager: the folder tree, narrowed to one tag and/or folder, with a tag index
+pub fn bookmarks_page(bookmarks: &Bookmarks, tag: &str, folder: &str) -> String {
+    let tag = tag.trim().to_lowercase();
+    let folder = normalize_folder(folder);
+    // Numbered by position in the file, which is what `:bm del <n>` takes
+    let mut shown: Vec<(usize, &Bookmark)> = bookmarks.entries.iter().enumerate()
+        .map(|(i, b)| (i + 1, b))
+        .filter(|(_, b)| tag.is_empty() || b.tags.contains(&tag))
+        .filter(|(_, b)| b.in_folder(&folder))
+        .collect();
+    // Compared a level at a time, so a folder's subfolders follow it directly
+    shown.sort_by(|(_, a), (_, b)| a.folder.split('/').cmp(b.folder.split('/')).then_with(|| a.title.to_lowercase().cmp(&b.title.to_lowercase())));
+
+    let mut html = String::from("<title>Bookmarks</title><h1>Bookmarks</h1><p>");
+    match (tag.is_empty(), folder.is_empty()) {
+        (true, true) => html.push_str(&format!("{} bookmark(s)", shown.len())),
+        _ => {
+            html.push_str(&format!("{} of {} bookmark(s)", shown.len(), bookmarks.entries.len()));
+            if !tag.is_empty() { html.push_str(&format!(" tagged <b>#{}</b>", escape_html(&tag))); }
+            if !folder.is_empty() { html.push_str(&format!(" in <b>{}</b>", escape_html(&folder))); }
+            html.push_str(" — <a href=\"about:bookmarks\">show all</a>");
+        }
+    }
+    html.push_str("</p>");
+
+    let tags = bookmarks.tag_counts();
+    if !tags.is_empty() {
+        let links: Vec<String> = tags.iter()
+            .map(|(t, n)| format!("<a href=\"about:bookmarks?tag={}\">#{}</a> ({})", escape_html(&encode(t)), escape_html(t), n))
+            .collect();
+        html.push_str(&format!("<p>Tags: {}</p>", links.join(", ")));
+    }
+
+    // A heading for each folder level on the way down, the first time it is reached
+    let mut open: Vec<&str> = Vec::new();
+    let mut in_list = false;
+    for (n, bookmark) in shown {
+        let parts: Vec<&str> = if bookmark.folder.is_empty() { Vec::new() } else { bookmark.folder.split('/').collect() };
+        let common = open.iter().zip(&parts).take_while(|(a, b)| a == b).count();
+        if common < open.len() || parts.len() > open.len() {
+            if in_list {
+                html.push_str("</ul>");
+                in_list = false;
+            }
+            for depth in common..parts.len() {
+                let level = (depth + 2).min(6);
+                html.push_str(&format!("<h{0}>{1}</h{0}>", level, folder_link(&parts[..=depth].join("/"), parts[depth])));
+            }
+            open = parts;
+        }
+        if !in_list {
+            html.push_str("<ul>");
+            in_list = true;
+        }
+        let title = if bookmark.title.is_empty() { &bookmark.url } else { &bookmark.title };
+        let tags: Vec<String> = bookmark.tags.iter().map(|t| format!("#{}", escape_html(t))).collect();
+        let flags = match (bookmark.watch, bookmark.changed) {
+            (_, true) => " <b>[changed]</b>",
+            (true, false) => " [watched]",
+            (false, false) => "",
+        };
+        html.push_str(&format!("<li>[{3}] <a href=\"{0}\">{1}</a>{4} {2}<br>{0}</li>", escape_html(&bookmark.url), escape_html(title), tags.join(" "), n, flags));
+    }
+    if in_list {
+        html.push_str("</ul>");
+    }
+    html
+}
+
+impl App {
+    // `-t a,b` anywhere in the arguments gives the tags; the rest is the folder
+    pub fn add_bookmark(&mut self, arg: &str) -> Result<(), Box<dyn Error>> {
+        let mut words = arg.split_whitespace();
+        let mut tags = Vec::new();
+        let mut folder = Vec::new();
+        while let Some(word) = words.next() {
+            match word {
+                "-t" => tags.extend(parse_tags(words.next().unwrap_or_default())),
+                w => folder.push(w),
+            }
+        }
+        let title = self.page_info.title.clone().unwrap_or_default();
+        self.bookmarks.add(&self.current_url, &title, tags, &folder.join(" "))?;
+        let saved = self.bookmarks.get(&self.current_url).map(|b| (b.folder.clone(), b.tags.len())).unwrap_or_default();
+        self.status = match saved {
+            (folder, n) if folder.is_empty() => tr!("Bookmarked ({} tags)", n),
+            (folder, n) => tr!("Bookmarked in {} ({} tags)", folder, n),
+        };
+        Ok(())
+    }
+
+    pub async fn delete_bookmark(&mut self, arg: &str) -> Result<(), Box<dyn Error>> {
+        let Ok(n) = arg.trim().parse::<usize>() else {
+            self.status = tr!("bm del: expected a bookmark number, as shown by :bm list");
+            return Ok(());
+        };
+        match self.bookmarks.remove_at(n)? {
+            Some(removed) => {
+                // Redraw the list if it's what is on screen, keeping the place in it
+                if self.current_url.starts_with("about:bookmarks") {
+                    let scroll = self.scroll;
+                    self.fetch_page().await?;
+                    self.scroll = scroll.min(self.max_scroll());
+                }
+                self.status = tr!("Bookmark removed: {}", removed.url);
+            }
+            None => self.status = tr!("bm del: no bookmark {} ({} saved)", n, self.bookmarks.entries.len()),
+        }
+        Ok(())
+    }
+}
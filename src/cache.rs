@@ -0,0 +1,212 @@
+use crate::app::App;
+use crate::har::{Incoming, Outgoing};
+use crate::i18n::tr;
+use crate::page::{PageInfo, TextStats};
+use crate::types::{LinkData, Mode};
+use ratatui::text::Line;
+use std::collections::VecDeque;
+use std::error::Error;
+use std::time::{Duration, Instant, SystemTime};
+
+pub const CAPACITY: usize = 32;
+const MAX_AGE: Duration = Duration::from_secs(300);
+
+#[derive(Clone)]
+pub enum Body {
+    Text(String),
+    // Image data, shown as a preview
+    Bytes(Vec<u8>),
+    // Anything else that isn't text; offered as a download instead of being rendered
+    Binary(Vec<u8>),
+}
+
+// Control characters that show up in real text files
+fn is_text_control(b: u8) -> bool {
+    matches!(b, b'\t' | b'\n' | b'\r' | 0x0c | 0x1b)
+}
+
+// Sniffs the start of a body: a NUL byte, or more than one byte in ten being a control
+// character, means it is not text whatever the Content-Type claims
+pub fn looks_binary(bytes: &[u8]) -> bool {
+    let head = &bytes[..bytes.len().min(1024)];
+    let controls = head.iter().filter(|&&b| b < 0x20 && !is_text_control(b)).count();
+    head.contains(&0) || controls * 10 > head.len()
+}
+
+impl Body {
+    pub fn len(&self) -> usize {
+        match self {
+            Body::Text(t) => t.len(),
+            Body::Bytes(b) | Body::Binary(b) => b.len(),
+        }
+    }
+
+    // `content_type` is the full header value, so its charset parameter picks the decoding
+    pub fn from_bytes(content_type: &str, bytes: Vec<u8>) -> Self {
+        let mime = content_type.to_ascii_lowercase();
+        if mime.starts_with("image/") {
+            return Body::Bytes(bytes);
+        }
+        if !mime.contains("html") && !mime.contains("xml") && looks_binary(&bytes) {
+            return Body::Binary(bytes);
+        }
+        let encoding = mime.split(';')
+            .find_map(|p| p.trim().strip_prefix("charset="))
+            .and_then(|label| encoding_rs::Encoding::for_label(label.trim_matches('"').as_bytes()))
+            .unwrap_or(encoding_rs::UTF_8);
+        Body::Text(encoding.decode(&bytes).0.into_owned())
+    }
+
+    pub async fn read(res: reqwest::Response, incoming: &Incoming) -> reqwest::Result<Self> {
+        Ok(Body::from_bytes(&incoming.mime_type, res.bytes().await?.to_vec()))
+    }
+}
+
+pub struct CachedPage {
+    pub url: String,
+    pub response: Incoming,
+    pub body: Body,
+    stored: Instant,
+}
+
+// A prefetch finished in the background, waiting to be logged and cached
+pub struct Prefetched {
+    pub request: Outgoing,
+    pub response: Incoming,
+    pub body: Body,
+    pub ttfb: Duration,
+    pub receive: Duration,
+}
+
+#[derive(Default)]
+pub struct PageCache {
+    entries: VecDeque<CachedPage>,
+}
+
+impl PageCache {
+    pub fn insert(&mut self, url: String, response: Incoming, body: Body) {
+        self.entries.retain(|e| e.url != url);
+        if self.entries.len() >= CAPACITY {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(CachedPage { url, response, body, stored: Instant::now() });
+    }
+
+    pub fn contains(&self, url: &str) -> bool {
+        self.entries.iter().any(|e| e.url == url && e.stored.elapsed() < MAX_AGE)
+    }
+
+    // Entries are single-use, so a reload after the page was shown goes back to the network
+    pub fn take(&mut self, url: &str) -> Option<CachedPage> {
+        self.entries.retain(|e| e.stored.elapsed() < MAX_AGE);
+        let pos = self.entries.iter().position(|e| e.url == url)?;
+        self.entries.remove(pos)
+    }
+}
+
+// A page as it was laid out when it was left, so back / forward can show it again
+// without the network
+pub struct RenderedPage {
+    url: String,
+    content_lines: Vec<Line<'static>>,
+    links: Vec<LinkData>,
+    page_info: PageInfo,
+    raw_html: String,
+    fetched_source: Option<String>,
+    stats: TextStats,
+    tls_verified: bool,
+}
+
+// Least recently left first; how many are kept is the pagecache setting
+#[derive(Default)]
+pub struct RenderedCache {
+    entries: VecDeque<RenderedPage>,
+}
+
+impl RenderedCache {
+    fn insert(&mut self, page: RenderedPage, capacity: usize) {
+        self.remove(&page.url);
+        while !self.entries.is_empty() && self.entries.len() >= capacity {
+            self.entries.pop_front();
+        }
+        if capacity > 0 {
+            self.entries.push_back(page);
+        }
+    }
+
+    fn take(&mut self, url: &str) -> Option<RenderedPage> {
+        let pos = self.entries.iter().position(|e| e.url == url)?;
+        self.entries.remove(pos)
+    }
+
+    fn remove(&mut self, url: &str) {
+        self.entries.retain(|e| e.url != url);
+    }
+}
+
+impl App {
+    // Called on leaving a page. Only a whole page that is shown as fetched is kept: internal
+    // pages are made fresh each time, and partial, streamed, translated or extended ones
+    // would come back different.
+    pub fn keep_rendered(&mut self) {
+        let whole = self.partial.is_none() && self.stream.is_none() && self.held.is_none() && self.download.is_none()
+            && self.translation.is_none() && self.appended_pages.is_empty();
+        if !whole || self.shown.0 != self.current_url || self.current_url.starts_with("about:") {
+            return;
+        }
+        // The page stays on screen until the next one arrives, so it is copied, from under
+        // any alternate view
+        let (content_lines, links) = match self.saved_view {
+            Some(ref view) => (view.lines.clone(), view.links.clone()),
+            None => (self.content_lines.clone(), self.links.clone()),
+        };
+        let page = RenderedPage {
+            url: self.current_url.clone(),
+            content_lines,
+            links,
+            page_info: self.page_info.clone(),
+            raw_html: self.raw_html.clone(),
+            fetched_source: self.fetched_source.clone(),
+            stats: self.stats,
+            tls_verified: self.tls_verified,
+        };
+        self.rendered.insert(page, self.settings.page_cache);
+    }
+
+    // Shows the kept copy of the current URL, if there is one
+    pub fn restore_rendered(&mut self) -> bool {
+        let Some(page) = self.rendered.take(&self.current_url) else { return false };
+        self.content_lines = page.content_lines;
+        self.links = page.links;
+        self.page_info = page.page_info;
+        self.raw_html = page.raw_html;
+        self.fetched_source = page.fetched_source;
+        self.stats = page.stats;
+        self.tls_verified = page.tls_verified;
+        self.partial = None;
+        self.download = None;
+        self.held = None;
+        self.stream = None;
+        self.pending_refresh = None;
+        self.appended_pages.clear();
+        self.visited_at = SystemTime::now();
+        self.translation = None;
+        self.saved_view = None;
+        self.inspector = None;
+        self.search = None;
+        if self.hints.take().is_some() {
+            self.mode = Mode::Normal;
+        }
+        self.shown = (self.current_url.clone(), self.history.current);
+        self.status = tr!("Restored from memory: {} (:reload fetches it again)", self.current_url);
+        true
+    }
+
+    // `:reload`: from the network whatever is cached, keeping the reading position
+    pub async fn reload(&mut self) -> Result<(), Box<dyn Error>> {
+        self.rendered.remove(&self.current_url);
+        self.cache.take(&self.current_url);
+        self.restore_on_load = Some(self.position());
+        self.fetch_page().await
+    }
+}
@@ -0,0 +1,215 @@
+use crate::app::App;
+use crate::i18n::tr;
+use crate::types::*;
+use crossterm::event::KeyCode;
+use ratatui::{style::{Modifier, Style}, text::{Line, Span}};
+use std::error::Error;
+
+#[derive(Default, Clone, Copy)]
+pub struct Caret {
+    pub line: usize,
+    pub col: usize,
+    pub anchor: Option<(usize, usize)>,
+}
+
+fn line_chars(line: &Line) -> Vec<char> {
+    line.spans.iter().flat_map(|s| s.content.chars()).collect()
+}
+
+// Restyle the char range [start, end) of a line, splitting spans at the boundaries
+pub fn restyle_range(line: Line<'static>, start: usize, end: usize, style: Style) -> Line<'static> {
+    let mut spans = Vec::new();
+    let mut pos = 0;
+    for span in line.spans {
+        let chars: Vec<char> = span.content.chars().collect();
+        let (s, e) = (start.clamp(pos, pos + chars.len()) - pos, end.clamp(pos, pos + chars.len()) - pos);
+        for (range, patched) in [(0..s, false), (s..e, true), (e..chars.len(), false)] {
+            if range.is_empty() { continue; }
+            let text: String = chars[range].iter().collect();
+            spans.push(Span::styled(text, if patched { span.style.patch(style) } else { span.style }));
+        }
+        pos += chars.len();
+    }
+    if end > pos {
+        spans.push(Span::styled(" ".repeat(end - pos), style));
+    }
+    Line::from(spans)
+}
+
+impl App {
+    fn caret_line_len(&self, line: usize) -> usize {
+        self.content_lines.get(line).map(|l| line_chars(l).len()).unwrap_or(0)
+    }
+
+    pub fn enter_caret(&mut self) {
+        let line = (self.scroll as usize).min(self.content_lines.len().saturating_sub(1));
+        self.caret = Caret { line, col: 0, anchor: None };
+        self.mode = Mode::Caret;
+        self.status = tr!("-- CARET -- (v: select, y: yank, K: define, Esc: exit)");
+    }
+
+    fn clamp_caret(&mut self) {
+        self.caret.line = self.caret.line.min(self.content_lines.len().saturating_sub(1));
+        self.caret.col = self.caret.col.min(self.caret_line_len(self.caret.line).saturating_sub(1));
+        let viewport = self.viewport_height.max(1) as usize;
+        if self.caret.line < self.scroll as usize {
+            self.scroll = self.caret.line as u16;
+        } else if self.caret.line >= self.scroll as usize + viewport {
+            self.scroll = (self.caret.line + 1 - viewport) as u16;
+        }
+    }
+
+    fn caret_word_forward(&mut self) {
+        let (mut line, mut col) = (self.caret.line, self.caret.col);
+        let mut chars = self.content_lines.get(line).map(line_chars).unwrap_or_default();
+        while col < chars.len() && !chars[col].is_whitespace() { col += 1; }
+        loop {
+            while col < chars.len() && chars[col].is_whitespace() { col += 1; }
+            if col < chars.len() || line + 1 >= self.content_lines.len() { break; }
+            line += 1;
+            col = 0;
+            chars = line_chars(&self.content_lines[line]);
+        }
+        self.caret.line = line;
+        self.caret.col = col;
+    }
+
+    fn caret_word_back(&mut self) {
+        let (mut line, mut col) = (self.caret.line, self.caret.col);
+        let mut chars = self.content_lines.get(line).map(line_chars).unwrap_or_default();
+        loop {
+            while col > 0 && chars.get(col - 1).is_some_and(|c| c.is_whitespace()) { col -= 1; }
+            if col > 0 || line == 0 { break; }
+            line -= 1;
+            chars = line_chars(&self.content_lines[line]);
+            col = chars.len();
+        }
+        while col > 0 && !chars[col - 1].is_whitespace() { col -= 1; }
+        self.caret.line = line;
+        self.caret.col = col;
+    }
+
+    pub fn word_under_caret(&self) -> String {
+        let chars = self.content_lines.get(self.caret.line).map(line_chars).unwrap_or_default();
+        let is_word = |c: &char| c.is_alphanumeric() || *c == '-' || *c == '\'';
+        if !chars.get(self.caret.col).is_some_and(is_word) {
+            return String::new();
+        }
+        let start = (0..=self.caret.col).rev().take_while(|&i| is_word(&chars[i])).last().unwrap_or(self.caret.col);
+        let end = (self.caret.col..chars.len()).take_while(|&i| is_word(&chars[i])).last().unwrap_or(self.caret.col);
+        chars[start..=end].iter().collect()
+    }
+
+    // Ordered (start, end) of the selection, end exclusive
+    pub fn caret_selection(&self) -> Option<((usize, usize), (usize, usize))> {
+        let anchor = self.caret.anchor?;
+        let cursor = (self.caret.line, self.caret.col);
+        let (a, b) = if anchor <= cursor { (anchor, cursor) } else { (cursor, anchor) };
+        Some((a, (b.0, b.1 + 1)))
+    }
+
+    pub fn selected_text(&self) -> Option<String> {
+        let ((l1, c1), (l2, c2)) = self.caret_selection()?;
+        let mut out = Vec::new();
+        for line in l1..=l2 {
+            let chars = self.content_lines.get(line).map(line_chars).unwrap_or_default();
+            let start = if line == l1 { c1.min(chars.len()) } else { 0 };
+            let end = if line == l2 { c2.min(chars.len()) } else { chars.len() };
+            out.push(chars[start..end.max(start)].iter().collect::<String>());
+        }
+        Some(out.join("\n"))
+    }
+
+    pub fn link_at(&self, line: usize, col: usize) -> Option<usize> {
+        let mut link_idx = 0;
+        for (row, l) in self.content_lines.iter().enumerate() {
+            let mut pos = 0;
+            for span in &l.spans {
+                let len = span.content.chars().count();
+                let is_link = is_link_style(span.style);
+                if row == line && col >= pos && col < pos + len {
+                    return is_link.then_some(link_idx);
+                }
+                if is_link { link_idx += 1; }
+                pos += len;
+            }
+            if row == line { return None; }
+        }
+        None
+    }
+
+    pub fn caret_overlay(&self, row: usize, line: Line<'static>) -> Line<'static> {
+        if self.mode != Mode::Caret {
+            return line;
+        }
+        let mut line = line;
+        if let Some(((l1, c1), (l2, c2))) = self.caret_selection() && row >= l1 && row <= l2 {
+            let start = if row == l1 { c1 } else { 0 };
+            let end = if row == l2 { c2 } else { usize::MAX / 2 };
+            let end = end.min(line.width().max(start));
+            line = restyle_range(line, start, end, Style::default().add_modifier(Modifier::REVERSED));
+        }
+        if row == self.caret.line {
+            line = restyle_range(line, self.caret.col, self.caret.col + 1, Style::default().add_modifier(Modifier::REVERSED | Modifier::UNDERLINED));
+        }
+        line
+    }
+
+    pub async fn handle_caret_key(&mut self, code: KeyCode) -> Result<(), Box<dyn Error>> {
+        if self.pending_key.take() == Some('"') {
+            match code {
+                KeyCode::Char(c) => self.select_register(c),
+                _ => self.register = None,
+            }
+            return Ok(());
+        }
+        match code {
+            KeyCode::Char('"') => self.pending_key = Some('"'),
+            KeyCode::Esc => {
+                self.mode = Mode::Normal;
+                self.caret.anchor = None;
+                self.status = tr!("Caret mode off");
+                return Ok(());
+            }
+            KeyCode::Char('h') | KeyCode::Left => self.caret.col = self.caret.col.saturating_sub(1),
+            KeyCode::Char('l') | KeyCode::Right => self.caret.col += 1,
+            KeyCode::Char('j') | KeyCode::Down => self.caret.line += 1,
+            KeyCode::Char('k') | KeyCode::Up => self.caret.line = self.caret.line.saturating_sub(1),
+            KeyCode::Char('w') => self.caret_word_forward(),
+            KeyCode::Char('b') => self.caret_word_back(),
+            KeyCode::Char('0') | KeyCode::Home => self.caret.col = 0,
+            KeyCode::Char('$') | KeyCode::End => self.caret.col = usize::MAX,
+            KeyCode::Char('v') => {
+                self.caret.anchor = match self.caret.anchor {
+                    Some(_) => None,
+                    None => Some((self.caret.line, self.caret.col)),
+                };
+            }
+            KeyCode::Char('y') => {
+                let text = self.selected_text().unwrap_or_else(|| self.word_under_caret());
+                self.yank(text, "text");
+                self.caret.anchor = None;
+            }
+            KeyCode::Char('K') => {
+                let word = self.word_under_caret();
+                self.define(&word, false)?;
+            }
+            KeyCode::Char(':') => {
+                self.mode = Mode::Command;
+                self.command_buffer.clear();
+                return Ok(());
+            }
+            KeyCode::Enter => match self.link_at(self.caret.line, self.caret.col) {
+                Some(idx) => {
+                    self.mode = Mode::Normal;
+                    self.selected_link_idx = idx;
+                    return self.follow_link(idx).await;
+                }
+                None => self.status = tr!("No link under cursor"),
+            },
+            _ => {}
+        }
+        self.clamp_caret();
+        Ok(())
+    }
+}
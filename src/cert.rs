@@ -0,0 +1,230 @@
+use crate::app::App;
+use crate::har::iso8601;
+use crate::i18n::{self, tr};
+use crate::timing::HandshakeTiming;
+use ratatui::{style::{Color, Modifier, Style}, text::{Line, Span}};
+use rustls::client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier};
+use rustls::client::{Resumption, WebPkiServerVerifier};
+use rustls::pki_types::{CertificateDer, ServerName, UnixTime};
+use rustls::{DigitallySignedStruct, SignatureScheme};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+
+// Certificate chains as last presented by each host, leaf first
+#[derive(Clone, Default)]
+pub struct CertStore(Arc<Mutex<HashMap<String, Vec<Vec<u8>>>>>);
+
+impl CertStore {
+    pub fn chain(&self, host: &str) -> Option<Vec<Vec<u8>>> {
+        self.0.lock().ok()?.get(host).cloned()
+    }
+}
+
+// The standard webpki verifier, noting each chain it is shown. Verification is unchanged.
+#[derive(Debug)]
+struct RecordingVerifier {
+    inner: Arc<WebPkiServerVerifier>,
+    store: Arc<Mutex<HashMap<String, Vec<Vec<u8>>>>>,
+}
+
+impl ServerCertVerifier for RecordingVerifier {
+    fn verify_server_cert(&self, end_entity: &CertificateDer<'_>, intermediates: &[CertificateDer<'_>], server_name: &ServerName<'_>, ocsp: &[u8], now: UnixTime) -> Result<ServerCertVerified, rustls::Error> {
+        let chain = std::iter::once(end_entity).chain(intermediates).map(|c| c.to_vec()).collect();
+        if let Ok(mut store) = self.store.lock() {
+            store.insert(server_name.to_str().into_owned(), chain);
+        }
+        self.inner.verify_server_cert(end_entity, intermediates, server_name, ocsp, now)
+    }
+
+    fn verify_tls12_signature(&self, message: &[u8], cert: &CertificateDer<'_>, dss: &DigitallySignedStruct) -> Result<HandshakeSignatureValid, rustls::Error> {
+        self.inner.verify_tls12_signature(message, cert, dss)
+    }
+
+    fn verify_tls13_signature(&self, message: &[u8], cert: &CertificateDer<'_>, dss: &DigitallySignedStruct) -> Result<HandshakeSignatureValid, rustls::Error> {
+        self.inner.verify_tls13_signature(message, cert, dss)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.inner.supported_verify_schemes()
+    }
+}
+
+// The rustls configuration reqwest would build itself, with the recording verifier
+pub fn tls_config(store: &CertStore, alpn: &[&[u8]]) -> Result<rustls::ClientConfig, rustls::Error> {
+    let provider = Arc::new(rustls::crypto::ring::default_provider());
+    let roots = rustls::RootCertStore { roots: webpki_roots::TLS_SERVER_ROOTS.to_vec() };
+    let inner = WebPkiServerVerifier::builder_with_provider(Arc::new(roots), provider.clone())
+        .build()
+        .map_err(|e| rustls::Error::General(e.to_string()))?;
+    let mut config = rustls::ClientConfig::builder_with_provider(provider)
+        .with_safe_default_protocol_versions()?
+        .dangerous()
+        .with_custom_certificate_verifier(Arc::new(RecordingVerifier { inner, store: store.0.clone() }))
+        .with_no_client_auth();
+    config.alpn_protocols = alpn.iter().map(|p| p.to_vec()).collect();
+    config.resumption = Resumption::store(Arc::new(HandshakeTiming::default()));
+    Ok(config)
+}
+
+// Just enough DER to read the fields a certificate viewer shows
+struct Der<'a>(&'a [u8]);
+
+impl<'a> Der<'a> {
+    // Next (tag, contents), advancing past it
+    fn next(&mut self) -> Option<(u8, &'a [u8])> {
+        let (&tag, rest) = self.0.split_first()?;
+        let (&first, mut rest) = rest.split_first()?;
+        let len = if first < 0x80 {
+            first as usize
+        } else {
+            let n = (first & 0x7f) as usize;
+            if n == 0 || n > 4 || rest.len() < n {
+                return None;
+            }
+            let len = rest[..n].iter().fold(0usize, |acc, &b| (acc << 8) | b as usize);
+            rest = &rest[n..];
+            len
+        };
+        if rest.len() < len {
+            return None;
+        }
+        self.0 = &rest[len..];
+        Some((tag, &rest[..len]))
+    }
+
+    fn expect(&mut self, tag: u8) -> Option<&'a [u8]> {
+        self.next().filter(|(t, _)| *t == tag).map(|(_, c)| c)
+    }
+}
+
+const SEQUENCE: u8 = 0x30;
+const SET: u8 = 0x31;
+const OID: u8 = 0x06;
+
+pub struct CertSummary {
+    pub subject: String,
+    pub issuer: String,
+    pub key: String,
+    // "YYYY-MM-DD HH:MM:SS", UTC
+    pub not_before: String,
+    pub not_after: String,
+}
+
+fn name(der: &[u8]) -> String {
+    let mut parts = Vec::new();
+    let mut rdns = Der(der);
+    while let Some(set) = rdns.expect(SET) {
+        let mut attrs = Der(set);
+        while let Some(attr) = attrs.expect(SEQUENCE) {
+            let mut attr = Der(attr);
+            let (Some(oid), Some((_, value))) = (attr.expect(OID), attr.next()) else { continue };
+            let key = match oid {
+                [0x55, 0x04, 0x03] => "CN",
+                [0x55, 0x04, 0x0a] => "O",
+                [0x55, 0x04, 0x0b] => "OU",
+                [0x55, 0x04, 0x06] => "C",
+                [0x55, 0x04, 0x07] => "L",
+                [0x55, 0x04, 0x08] => "ST",
+                _ => continue,
+            };
+            parts.push(format!("{}={}", key, String::from_utf8_lossy(value)));
+        }
+    }
+    parts.join(", ")
+}
+
+fn time(tag: u8, value: &[u8]) -> String {
+    let s = String::from_utf8_lossy(value);
+    // The chain is recorded before it is verified, so this may be anything; only ASCII is sliced
+    if !value.is_ascii() {
+        return s.into_owned();
+    }
+    // UTCTime has a two-digit year: 50-99 are 19xx
+    let full = match tag {
+        0x17 if s.len() >= 12 => format!("{}{}", if s[..2] >= *"50" { "19" } else { "20" }, s),
+        _ => s.into_owned(),
+    };
+    if full.len() < 14 {
+        return full;
+    }
+    format!("{}-{}-{} {}:{}:{}", &full[0..4], &full[4..6], &full[6..8], &full[8..10], &full[10..12], &full[12..14])
+}
+
+fn key_type(spki: &[u8]) -> String {
+    let mut spki = Der(spki);
+    let (Some(alg), Some(bits)) = (spki.expect(SEQUENCE), spki.expect(0x03)) else { return "unknown".to_string() };
+    let mut alg = Der(alg);
+    match (alg.expect(OID), alg.next()) {
+        (Some([0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x01, 0x01]), _) => {
+            let modulus = bits.get(1..).and_then(|b| Der(b).expect(SEQUENCE)).and_then(|k| Der(k).expect(0x02));
+            match modulus {
+                Some(m) => format!("RSA {} bits", m.iter().skip_while(|&&b| b == 0).count() * 8),
+                None => "RSA".to_string(),
+            }
+        }
+        (Some([0x2a, 0x86, 0x48, 0xce, 0x3d, 0x02, 0x01]), Some((_, curve))) => match curve {
+            [0x2a, 0x86, 0x48, 0xce, 0x3d, 0x03, 0x01, 0x07] => "ECDSA P-256".to_string(),
+            [0x2b, 0x81, 0x04, 0x00, 0x22] => "ECDSA P-384".to_string(),
+            [0x2b, 0x81, 0x04, 0x00, 0x23] => "ECDSA P-521".to_string(),
+            _ => "ECDSA".to_string(),
+        },
+        (Some([0x2b, 0x65, 0x70]), _) => "Ed25519".to_string(),
+        (Some([0x2b, 0x65, 0x71]), _) => "Ed448".to_string(),
+        _ => "unknown".to_string(),
+    }
+}
+
+pub fn summarize(der: &[u8]) -> Option<CertSummary> {
+    let cert = Der(der).expect(SEQUENCE)?;
+    let mut tbs = Der(Der(cert).expect(SEQUENCE)?);
+    if tbs.0.first() == Some(&0xa0) {
+        tbs.next()?;
+    }
+    tbs.next()?; // serial number
+    tbs.expect(SEQUENCE)?; // signature algorithm
+    let issuer = name(tbs.expect(SEQUENCE)?);
+    let mut validity = Der(tbs.expect(SEQUENCE)?);
+    let (before_tag, before) = validity.next()?;
+    let (after_tag, after) = validity.next()?;
+    let subject = name(tbs.expect(SEQUENCE)?);
+    let key = key_type(tbs.expect(SEQUENCE)?);
+    Some(CertSummary { subject, issuer, key, not_before: time(before_tag, before), not_after: time(after_tag, after) })
+}
+
+impl App {
+    pub fn show_cert_chain(&mut self) {
+        let host = url::Url::parse(&self.current_url).ok().and_then(|u| u.host_str().map(str::to_string));
+        let Some(chain) = host.as_deref().and_then(|h| self.certs.chain(h)) else {
+            self.status = tr!("cert: no certificate seen for this host in this session");
+            return;
+        };
+        let now = iso8601(SystemTime::now()).replace('T', " ");
+        let bold = Style::default().add_modifier(Modifier::BOLD);
+        let field = |k: &str, v: String, style: Style| Line::from(vec![Span::styled(format!("  {}", i18n::pad(i18n::lookup(k), 10)), bold), Span::styled(v, style)]);
+        let mut lines = Vec::new();
+        for (i, der) in chain.iter().enumerate() {
+            let role = match i {
+                0 => "server certificate",
+                _ if i + 1 == chain.len() => "last certificate sent",
+                _ => "intermediate",
+            };
+            lines.push(Line::from(Span::styled(format!("#{} {}", i, i18n::lookup(role)), bold.fg(Color::Cyan))));
+            match summarize(der) {
+                Some(c) => {
+                    let expired = c.not_after.as_str() < &now[..19] || c.not_before.as_str() > &now[..19];
+                    let validity_style = if expired { Style::default().fg(Color::Red) } else { Style::default() };
+                    lines.push(field("Subject", c.subject, Style::default()));
+                    lines.push(field("Issuer", c.issuer, Style::default()));
+                    lines.push(field("Key", c.key, Style::default()));
+                    let valid = if expired { tr!("{} to {} UTC  (not valid now)", c.not_before, c.not_after) } else { tr!("{} to {} UTC", c.not_before, c.not_after) };
+                    lines.push(field("Valid", valid, validity_style));
+                }
+                None => lines.push(field("", tr!("could not parse ({} bytes)", der.len()), Style::default().fg(Color::Red))),
+            }
+            lines.push(Line::from(""));
+        }
+        self.open_popup(&tr!("Certificate chain: {}", host.unwrap_or_default()), lines);
+        self.status = tr!("{} certificate(s) — j / k to scroll", chain.len());
+    }
+}
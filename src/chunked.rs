@@ -0,0 +1,101 @@
+use crate::app::App;
+use crate::cache::Body;
+use crate::i18n::tr;
+use crate::types::{LinkData, LinkType, LINK_COLOR_WEB};
+use crate::ui::format_size;
+use ratatui::{style::{Modifier, Style}, text::{Line, Span}};
+use std::error::Error;
+use url::Url;
+
+// The marker link at the end of a partially loaded page
+pub const LOAD_MORE_URL: &str = "about:load-more";
+
+// A response too large to render at once: what has been read so far, and the
+// still-open response the rest is read from when asked for
+pub struct PartialBody {
+    pub url: String,
+    pub mime_type: String,
+    pub received: Vec<u8>,
+    // From Content-Length; 0 when the server didn't say
+    pub total: u64,
+    response: Option<reqwest::Response>,
+}
+
+impl PartialBody {
+    // `received` is what was already read from `response`
+    pub fn new(url: &str, mime_type: &str, received: Vec<u8>, response: reqwest::Response) -> Self {
+        let total = response.content_length().unwrap_or(0);
+        Self { url: url.to_string(), mime_type: mime_type.to_string(), received, total, response: Some(response) }
+    }
+
+    pub fn complete(&self) -> bool {
+        self.response.is_none()
+    }
+
+    // Reads about `limit` more bytes, stopping early at the end of the body; usize::MAX reads
+    // to the end
+    pub async fn read_more(&mut self, limit: usize) -> reqwest::Result<()> {
+        let target = self.received.len().saturating_add(limit);
+        while self.received.len() < target {
+            let Some(res) = self.response.as_mut() else { break };
+            match res.chunk().await? {
+                Some(chunk) => self.received.extend_from_slice(&chunk),
+                None => self.response = None,
+            }
+        }
+        Ok(())
+    }
+
+    // Decodes what has arrived, leaving off a character cut in half by the chunk boundary
+    pub fn text(&self) -> String {
+        let valid = match std::str::from_utf8(&self.received) {
+            Ok(_) => self.received.len(),
+            Err(e) if e.error_len().is_none() => e.valid_up_to(),
+            Err(_) => self.received.len(),
+        };
+        String::from_utf8_lossy(&self.received[..valid]).into_owned()
+    }
+}
+
+impl App {
+    pub fn chunk_limit(&self) -> usize {
+        self.settings.chunk_kb.saturating_mul(1024)
+    }
+
+    // Renders the received part of `partial` with a [Load more] marker while more remains
+    pub fn render_partial(&mut self, partial: PartialBody, base_url: &Url) -> Result<(), Box<dyn Error>> {
+        if partial.complete() {
+            return self.render_body(&partial.mime_type, &Body::from_bytes(&partial.mime_type, partial.received), base_url);
+        }
+        self.render_body(&partial.mime_type, &Body::Text(partial.text()), base_url)?;
+        let (shown, total) = (format_size(partial.received.len()), format_size(partial.total as usize));
+        let label = match partial.total {
+            0 => format!("[Load more: {} shown]", shown),
+            _ => format!("[Load more: {} of {} shown]", shown, total),
+        };
+        self.content_lines.push(Line::from(""));
+        self.content_lines.push(Line::from(Span::styled(label, Style::default().fg(LINK_COLOR_WEB).add_modifier(Modifier::UNDERLINED))));
+        self.links.push(LinkData { url: LOAD_MORE_URL.to_string(), link_type: LinkType::Web });
+        self.status = match partial.total {
+            0 => tr!("Showing the first {} (:more or the [Load more] link continues)", shown),
+            _ => tr!("Showing the first {} of {} (:more or the [Load more] link continues)", shown, total),
+        };
+        self.partial = Some(partial);
+        Ok(())
+    }
+
+    pub async fn load_more(&mut self) -> Result<(), Box<dyn Error>> {
+        let Some(mut partial) = self.partial.take_if(|p| p.url == self.current_url) else {
+            self.status = tr!("more: this page is already fully loaded");
+            return Ok(());
+        };
+        let limit = self.chunk_limit().max(1024);
+        partial.read_more(limit).await?;
+        let base_url = Url::parse(&self.current_url)?;
+        let (scroll, selected) = (self.scroll, self.selected_link_idx);
+        self.render_partial(partial, &base_url)?;
+        self.scroll = scroll.min(self.max_scroll());
+        self.selected_link_idx = selected.min(self.links.len().saturating_sub(1));
+        Ok(())
+    }
+}
@@ -0,0 +1,36 @@
+use base64::Engine;
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+const HELPERS: &[(&str, &[&str])] = &[
+    ("pbcopy", &[]),
+    ("wl-copy", &[]),
+    ("xclip", &["-selection", "clipboard"]),
+    ("xsel", &["--clipboard", "--input"]),
+];
+
+fn copy_with_helper(text: &str) -> Option<&'static str> {
+    for (cmd, args) in HELPERS {
+        let Ok(mut child) = Command::new(cmd).args(*args).stdin(Stdio::piped()).stdout(Stdio::null()).stderr(Stdio::null()).spawn() else { continue };
+        if let Some(mut stdin) = child.stdin.take() {
+            let _ = stdin.write_all(text.as_bytes());
+        }
+        if child.wait().is_ok_and(|s| s.success()) {
+            return Some(cmd);
+        }
+    }
+    None
+}
+
+// Returns a short description of the mechanism used, for the status bar
+pub fn copy(text: &str) -> String {
+    if let Some(cmd) = copy_with_helper(text) {
+        return cmd.to_string();
+    }
+    // OSC 52 works over SSH in most modern terminals when no local helper exists
+    let encoded = base64::engine::general_purpose::STANDARD.encode(text);
+    let mut out = std::io::stdout();
+    let _ = write!(out, "\x1b]52;c;{}\x07", encoded);
+    let _ = out.flush();
+    "OSC 52".to_string()
+}
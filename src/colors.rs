@@ -0,0 +1,144 @@
+use ratatui::style::Color;
+use std::path::PathBuf;
+
+#[derive(Clone, Copy, PartialEq, PartialOrd)]
+pub enum ColorDepth {
+    // NO_COLOR or a terminal without colors: emphasis only
+    Mono,
+    Ansi16,
+    Ansi256,
+    TrueColor,
+}
+
+impl ColorDepth {
+    pub fn parse(value: &str) -> Result<Option<Self>, String> {
+        match value {
+            "auto" => Ok(None),
+            "none" | "0" => Ok(Some(ColorDepth::Mono)),
+            "16" => Ok(Some(ColorDepth::Ansi16)),
+            "256" => Ok(Some(ColorDepth::Ansi256)),
+            "truecolor" | "24bit" => Ok(Some(ColorDepth::TrueColor)),
+            _ => Err(format!("expected auto, truecolor, 256, 16 or none, got {:?}", value)),
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            ColorDepth::Mono => "none",
+            ColorDepth::Ansi16 => "16",
+            ColorDepth::Ansi256 => "256",
+            ColorDepth::TrueColor => "truecolor",
+        }
+    }
+}
+
+// The `colors` number from the compiled terminfo entry for $TERM
+fn terminfo_colors(term: &str) -> Option<i32> {
+    let home = std::env::var_os("HOME").map(PathBuf::from);
+    let mut dirs: Vec<PathBuf> = Vec::new();
+    dirs.extend(std::env::var_os("TERMINFO").map(PathBuf::from));
+    dirs.extend(home.map(|h| h.join(".terminfo")));
+    if let Some(list) = std::env::var_os("TERMINFO_DIRS") {
+        dirs.extend(std::env::split_paths(&list));
+    }
+    dirs.extend(["/etc/terminfo", "/lib/terminfo", "/usr/share/terminfo", "/usr/lib/terminfo"].map(PathBuf::from));
+    let first = term.chars().next()?;
+    let data = dirs.iter()
+        .flat_map(|d| [d.join(first.to_string()).join(term), d.join(format!("{:x}", first as u32)).join(term)])
+        .find_map(|p| std::fs::read(p).ok())?;
+    let word = |i: usize| data.get(i * 2..i * 2 + 2).map(|b| i16::from_le_bytes([b[0], b[1]]));
+    // Legacy entries store numbers as 16 bits, the extended format (ncurses 6.1) as 32
+    let wide = match word(0)? {
+        0o432 => false,
+        0o1036 => true,
+        _ => return None,
+    };
+    let (names, bools, nums) = (word(1)? as usize, word(2)? as usize, word(3)? as usize);
+    const COLORS: usize = 13;
+    if nums <= COLORS {
+        return None;
+    }
+    let mut offset = 12 + names + bools;
+    offset += offset % 2;
+    if wide {
+        let b = data.get(offset + COLORS * 4..offset + COLORS * 4 + 4)?;
+        Some(i32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+    } else {
+        let b = data.get(offset + COLORS * 2..offset + COLORS * 2 + 2)?;
+        Some(i16::from_le_bytes([b[0], b[1]]) as i32)
+    }
+}
+
+// COLORTERM announces truecolor; otherwise terminfo's color count, then the name of $TERM
+pub fn detect() -> ColorDepth {
+    if std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty()) {
+        return ColorDepth::Mono;
+    }
+    let colorterm = std::env::var("COLORTERM").unwrap_or_default();
+    if colorterm == "truecolor" || colorterm == "24bit" {
+        return ColorDepth::TrueColor;
+    }
+    let term = std::env::var("TERM").unwrap_or_default();
+    if term.is_empty() || term == "dumb" {
+        return ColorDepth::Mono;
+    }
+    match terminfo_colors(&term) {
+        Some(n) if n >= 1 << 24 => ColorDepth::TrueColor,
+        Some(n) if n >= 256 => ColorDepth::Ansi256,
+        Some(n) if n >= 8 => ColorDepth::Ansi16,
+        Some(_) => ColorDepth::Mono,
+        None if term.contains("direct") => ColorDepth::TrueColor,
+        None if term.contains("256") => ColorDepth::Ansi256,
+        None => ColorDepth::Ansi16,
+    }
+}
+
+// xterm's defaults for the 16 ANSI colors, in the order of their indices
+const ANSI: [(Color, (u8, u8, u8)); 16] = [
+    (Color::Black, (0, 0, 0)), (Color::Red, (205, 0, 0)), (Color::Green, (0, 205, 0)), (Color::Yellow, (205, 205, 0)),
+    (Color::Blue, (0, 0, 238)), (Color::Magenta, (205, 0, 205)), (Color::Cyan, (0, 205, 205)), (Color::Gray, (229, 229, 229)),
+    (Color::DarkGray, (127, 127, 127)), (Color::LightRed, (255, 0, 0)), (Color::LightGreen, (0, 255, 0)), (Color::LightYellow, (255, 255, 0)),
+    (Color::LightBlue, (92, 92, 255)), (Color::LightMagenta, (255, 0, 255)), (Color::LightCyan, (0, 255, 255)), (Color::White, (255, 255, 255)),
+];
+const CUBE: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+fn distance(a: (u8, u8, u8), b: (u8, u8, u8)) -> u32 {
+    let d = |x: u8, y: u8| (x as i32 - y as i32).pow(2) as u32;
+    d(a.0, b.0) + d(a.1, b.1) + d(a.2, b.2)
+}
+
+fn indexed_rgb(n: u8) -> (u8, u8, u8) {
+    match n {
+        0..=15 => ANSI[n as usize].1,
+        16..=231 => {
+            let n = n - 16;
+            (CUBE[(n / 36) as usize], CUBE[(n / 6 % 6) as usize], CUBE[(n % 6) as usize])
+        }
+        _ => {
+            let v = 8 + (n - 232) * 10;
+            (v, v, v)
+        }
+    }
+}
+
+fn to_256(rgb: (u8, u8, u8)) -> u8 {
+    let level = |v: u8| CUBE.iter().enumerate().min_by_key(|(_, c)| (v as i32 - **c as i32).abs()).map_or(0, |(i, _)| i as u8);
+    let cube = 16 + 36 * level(rgb.0) + 6 * level(rgb.1) + level(rgb.2);
+    let gray = 232 + ((rgb.0 as u32 + rgb.1 as u32 + rgb.2 as u32) / 3 + 5).saturating_sub(8).min(230) as u8 / 10;
+    if distance(indexed_rgb(gray), rgb) < distance(indexed_rgb(cube), rgb) { gray } else { cube }
+}
+
+fn to_16(rgb: (u8, u8, u8)) -> Color {
+    ANSI.iter().min_by_key(|(_, c)| distance(*c, rgb)).map_or(Color::Reset, |(c, _)| *c)
+}
+
+// The closest color the terminal can show
+pub fn fit(color: Color, depth: ColorDepth) -> Color {
+    match (color, depth) {
+        (_, ColorDepth::Mono) => Color::Reset,
+        (Color::Rgb(r, g, b), ColorDepth::Ansi256) => Color::Indexed(to_256((r, g, b))),
+        (Color::Rgb(r, g, b), ColorDepth::Ansi16) => to_16((r, g, b)),
+        (Color::Indexed(n), ColorDepth::Ansi16) => to_16(indexed_rgb(n)),
+        (c, _) => c,
+    }
+}
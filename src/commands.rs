@@ -0,0 +1,567 @@
+use crate::app::App;
+use crate::dialog::{DialogKind, SideEffect};
+use crate::i18n::{self, tr};
+use crate::jobs::Job;
+use crate::selector::Selector;
+use crate::xpath::{XItem, XPath};
+use crate::types::{Dictionary, PopupAction, SetEffect, ToastKind, Translation, Translator, ViewKind};
+use crate::{about, clipboard, dictionary, dom, har, markdown, sitemap, table, translate};
+use ratatui::{style::{Color, Modifier, Style}, text::{Line, Span}};
+use std::error::Error;
+use std::time::SystemTime;
+use url::Url;
+
+fn encode_query(value: &str) -> String {
+    url::form_urlencoded::byte_serialize(value.as_bytes()).collect()
+}
+
+fn time_ago(t: SystemTime) -> String {
+    match t.elapsed().map(|d| d.as_secs()).unwrap_or(0) {
+        s if s < 60 => format!("{}s ago", s),
+        s if s < 3600 => format!("{}m ago", s / 60),
+        s if s < 86_400 => format!("{}h ago", s / 3600),
+        s => format!("{}d ago", s / 86_400),
+    }
+}
+
+impl App {
+    // Returns Ok(true) when the command asks the browser to quit.
+    pub async fn execute_command(&mut self, cmd: &str) -> Result<bool, Box<dyn Error>> {
+        let cmd = self.config.expand_alias(cmd);
+        let (name, arg) = match cmd.split_once(' ') {
+            Some((n, a)) => (n, a.trim()),
+            None => (cmd.as_str(), ""),
+        };
+        // A trailing `!` forces the command past its confirmation, e.g. `:save! path`
+        let (name, force) = match name.strip_suffix('!') {
+            Some(n) => (n, true),
+            None => (name, false),
+        };
+        match name {
+            "q" => return Ok(true),
+            "url" if force => self.go_to(arg.to_string()).await?,
+            "url" => self.navigate(arg.to_string()).await?,
+            "grep" => self.navigate(format!("about:grep?q={}", encode_query(arg))).await?,
+            "meta" => self.navigate(format!("about:meta?q={}", encode_query(arg))).await?,
+            "frames" => self.navigate("about:frames".to_string()).await?,
+            "sitemap" | "robots" => {
+                let current = Url::parse(&self.current_url).ok();
+                match current.as_ref().and_then(sitemap::origin) {
+                    Some(site) => self.navigate(format!("about:{}?url={}", name, encode_query(site.as_str()))).await?,
+                    None => self.status = tr!("{}: not on a web page", name),
+                }
+            }
+            "help" => self.navigate("about:help".to_string()).await?,
+            "stats" => self.navigate("about:stats".to_string()).await?,
+            "reading-list" => self.navigate("about:reading-list".to_string()).await?,
+            "read-later" => {
+                let title = self.page_info.title.clone().unwrap_or_default();
+                self.reading_list.add(&self.current_url, &title, &self.raw_html)?;
+                self.notify(ToastKind::Info, tr!("Saved to reading list: {}", self.current_url));
+            }
+            "read-watch" => match self.reading_list.toggle_watch(&self.current_url)? {
+                Some(true) if self.settings.monitor_mins == 0 => self.status = tr!("Watching for changes (checks are off until :set monitor=<minutes>)"),
+                Some(true) => self.status = tr!("Watching for changes every {} min", self.settings.monitor_mins),
+                Some(false) => self.status = tr!("No longer watching this page"),
+                None => self.status = tr!("read-watch: save the page with :read-later first"),
+            },
+            "read-done" => match self.reading_list.remove(&self.current_url)? {
+                true => self.status = tr!("Removed from reading list: {}", self.current_url),
+                false => self.status = tr!("read-done: this page isn't on the reading list"),
+            },
+            "bookmark" => self.add_bookmark(arg)?,
+            "unbookmark" => match self.bookmarks.remove(&self.current_url)? {
+                true => self.status = tr!("Bookmark removed: {}", self.current_url),
+                false => self.status = tr!("unbookmark: this page isn't bookmarked"),
+            },
+            "bm" => {
+                let (sub, rest) = arg.split_once(' ').unwrap_or((arg, ""));
+                match sub {
+                    "add" => self.add_bookmark(rest)?,
+                    "list" | "" => self.navigate("about:bookmarks".to_string()).await?,
+                    "del" => self.delete_bookmark(rest).await?,
+                    "watch" => self.toggle_bookmark_watch()?,
+                    _ => self.status = tr!("bm: expected add, list, del <n> or watch"),
+                }
+            }
+            "bookmarks" => {
+                // `#tag` narrows to a tag, anything else to a folder
+                let mut query = String::new();
+                for word in arg.split_whitespace() {
+                    let (key, value) = match word.strip_prefix('#') {
+                        Some(tag) => ("tag", tag),
+                        None => ("folder", word),
+                    };
+                    query.push(if query.is_empty() { '?' } else { '&' });
+                    query.push_str(&format!("{}={}", key, encode_query(value)));
+                }
+                self.navigate(format!("about:bookmarks{}", query)).await?;
+            }
+            "frame" => match arg.parse::<usize>().ok().and_then(|n| self.page_info.frames.get(n).cloned()) {
+                Some(src) => self.navigate(src).await?,
+                None => self.status = tr!("frame: no frame {} ({} on page)", format!("{:?}", arg), self.page_info.frames.len()),
+            },
+            "set" => {
+                let (key, value) = arg.split_once('=').unwrap_or((arg, "on"));
+                match self.settings.set(key.trim(), value.trim()) {
+                    Ok(effect) => {
+                        match effect {
+                            SetEffect::Rerender => self.rerender(),
+                            SetEffect::RebuildClient => self.rebuild_client(),
+                            SetEffect::Restyle => self.load_theme(),
+                            SetEffect::None => {}
+                        }
+                        self.status = format!("{} = {}", key.trim(), value.trim());
+                    }
+                    Err(e) => self.status = tr!("set: {}", e),
+                }
+            }
+            "proxy" => self.set_proxy(arg),
+            "info" => self.show_info(),
+            "cert" => self.show_cert_chain(),
+            "timing" => match self.session_log.iter().rev().find(|e| e.request.url == self.current_url) {
+                Some(e) => {
+                    let mut report = e.timing.report();
+                    if e.from_cache { report.insert(1, "served from the prefetch cache".to_string()); }
+                    self.open_popup("Timing", report.into_iter().map(Line::from).collect());
+                }
+                None => self.status = tr!("timing: no fetch recorded for this page"),
+            },
+            "source" => self.toggle_source(),
+            "reader" => self.toggle_reader(),
+            "reload" => self.reload().await?,
+            "reload-config" => {
+                self.load_config();
+                let (keys, aliases, headers) = self.config.counts();
+                self.status = tr!("Loaded voyager.toml: {} key binding(s), {} alias(es), {} header(s)", keys, aliases, headers);
+            }
+            "diff" => self.toggle_diff(),
+            "noscript" => self.show_noscript()?,
+            "frontend" => match self.frontend_url() {
+                Some(url) => self.navigate(url).await?,
+                None => self.status = tr!("frontend: none configured for this site (:set frontend=<site>=<host>)"),
+            },
+            "wayback" => {
+                let url = self.wayback_url();
+                self.navigate(url).await?;
+            }
+            "fill" => self.fill_form(arg)?,
+            "watch" => self.start_watch(arg)?,
+            "inspect" => self.open_inspector(),
+            "resources" => self.show_resources().await?,
+            "cookies" => self.cookies_command(arg).await?,
+            "submit" => self.submit_command(arg).await?,
+            "select" => match Selector::parse(arg) {
+                Ok(selector) => {
+                    let document = dom::parse(&self.raw_html);
+                    let matched = selector.select(&document.document);
+                    self.show_extraction(&format!("select {}", arg), matched.iter().map(dom::serialize_node).collect())?;
+                }
+                Err(e) => self.status = tr!("select: {}", e),
+            },
+            "xpath" => match XPath::parse(arg) {
+                Ok(xpath) => {
+                    let document = dom::parse(&self.raw_html);
+                    let fragments = xpath.evaluate(&document.document).iter().map(|item| match item {
+                        XItem::Node(node) => dom::serialize_node(node),
+                        XItem::Value(v) => format!("<p>{}</p>", about::escape_html(v)),
+                    }).collect();
+                    self.show_extraction(&format!("xpath {}", arg), fragments)?;
+                }
+                Err(e) => self.status = tr!("xpath: {}", e),
+            },
+            "request" => self.open_request_composer(arg),
+            "head" => self.show_headers(reqwest::Method::HEAD, arg)?,
+            "options" => self.show_headers(reqwest::Method::OPTIONS, arg)?,
+            "table-export" => self.table_export(arg, force)?,
+            "save" => self.save_page(arg, force)?,
+            "download" => self.save_download(arg, force)?,
+            "continue" => self.continue_held().await?,
+            "stream-to" => self.stream_held(arg, force)?,
+            "abort" => match self.held.take() {
+                Some(_) => self.status = tr!("Download cancelled"),
+                None => self.status = tr!("abort: no response is waiting"),
+            },
+            "network" => self.toggle_network_pane(),
+            "registers" => self.show_registers(),
+            "restore-session" => self.restore_session().await?,
+            "har-export" => {
+                if arg.is_empty() {
+                    self.prompt_path("HAR Export", "har-export");
+                } else if self.may_proceed(SideEffect::Write(arg.to_string()), force, format!("har-export! {}", arg)) {
+                    std::fs::write(arg, serde_json::to_string_pretty(&har::to_har(&self.session_log))?)?;
+                    self.notify(ToastKind::Info, tr!("Wrote {} HAR entries to {}", self.session_log.len(), arg));
+                }
+            }
+            "outline" => self.show_outline(),
+            "translate" => self.translate(arg, force)?,
+            "define" => self.define(arg, force)?,
+            "yank-md" => {
+                let base = Url::parse(&self.current_url)?;
+                // A caret-mode selection is already rendered text, so it is copied verbatim
+                let md = match self.selected_text() {
+                    Some(text) => text,
+                    None => markdown::from_html(&self.raw_html, &base),
+                };
+                let via = clipboard::copy(&md);
+                self.status = tr!("Copied {} lines of Markdown ({})", md.lines().count(), via);
+            }
+            "yank-links" => self.yank_links(arg, force)?,
+            "open-all" => self.open_all(arg, force).await?,
+            "download-all" => self.download_all(arg, force)?,
+            "play" => self.play_media(arg, force)?,
+            "save-url" | "save-media" => self.save_url(arg, force)?,
+            "mirror" => self.start_mirror(arg, force)?,
+            "search" => {
+                let url = self.search_url(arg);
+                self.navigate(url).await?;
+            }
+            "search-site" => self.search_site(arg).await?,
+            "more" => self.load_more().await?,
+            "next" => self.follow_pagination(true).await?,
+            "prev" => self.follow_pagination(false).await?,
+            "tabnew" | "tabe" => self.new_tab(arg).await?,
+            "tab" => match arg.parse::<usize>().ok().filter(|n| (1..=self.tabs.len()).contains(n)) {
+                Some(n) => self.switch_tab(n - 1),
+                None => self.status = tr!("tab: expected a tab number 1..{}", self.tabs.len()),
+            },
+            "tabnext" => self.cycle_tab(true),
+            "tabprev" => self.cycle_tab(false),
+            "tabclose" => self.close_tab(),
+            "back" | "b" => self.go_back().await?,
+            "forward" | "f" => self.go_forward().await?,
+            "history" if arg.is_empty() => self.navigate("about:history".to_string()).await?,
+            "history" => self.navigate(format!("about:history?q={}", encode_query(arg))).await?,
+            "jumps" => self.show_history(false),
+            "tree" => self.show_history(true),
+            "messages" => {
+                let lines: Vec<Line> = self.messages.iter().map(|(t, msg)| Line::from(vec![
+                    Span::styled(format!("{:>8} ", time_ago(*t)), Style::default().fg(Color::DarkGray)),
+                    Span::raw(msg.clone()),
+                ])).collect();
+                let bottom = lines.len().saturating_sub(self.viewport_height as usize / 2) as u16;
+                self.open_popup("Messages", lines);
+                if let Some(popup) = self.popup.as_mut() { popup.scroll = bottom; }
+            }
+            _ => self.status = tr!("Unknown command: {}", name),
+        }
+        Ok(false)
+    }
+
+    pub fn toggle_network_pane(&mut self) {
+        self.network_pane = !self.network_pane;
+        self.status = if self.network_pane { tr!("Network log shown") } else { tr!("Network log hidden") };
+    }
+
+    // rel=next/prev from the document, falling back to rendered links labelled like pagers
+    pub async fn follow_pagination(&mut self, forward: bool) -> Result<(), Box<dyn Error>> {
+        let declared = if forward { self.page_info.next.clone() } else { self.page_info.prev.clone() };
+        let (words, arrows): (&[&str], &[&str]) = if forward {
+            (&["next", "next page", "older", "older posts"], &["›", "»", "→", ">", ">>"])
+        } else {
+            (&["prev", "previous", "previous page", "newer", "newer posts"], &["‹", "«", "←", "<", "<<"])
+        };
+        let guessed = || {
+            self.link_texts().into_iter().zip(&self.links).find_map(|(text, link)| {
+                let text = text.trim().to_lowercase();
+                let word = text.trim_matches(|c: char| !c.is_alphanumeric() && !c.is_whitespace()).trim();
+                (words.contains(&word) || arrows.contains(&text.as_str())).then(|| link.url.clone())
+            })
+        };
+        match declared.or_else(guessed) {
+            Some(url) => self.navigate(url).await?,
+            None => self.status = if forward { tr!("No next page found") } else { tr!("No previous page found") },
+        }
+        Ok(())
+    }
+
+    fn history_line(&self, idx: usize, indent: usize) -> Line<'static> {
+        let entry = &self.history.node(idx).entry;
+        let marker = if idx == self.history.current { "● " } else { "○ " };
+        Line::from(vec![
+            Span::styled(format!("{:>8} ", time_ago(entry.visited_at)), Style::default().fg(Color::DarkGray)),
+            Span::raw(format!("{}{}", "│ ".repeat(indent), marker)),
+            Span::raw(entry.title.clone().unwrap_or_else(|| entry.url.clone())),
+            Span::styled(format!("  {}", entry.url), Style::default().fg(Color::DarkGray)),
+        ])
+    }
+
+    // `tree` shows every branch; otherwise only the back/forward line through the current page
+    fn show_history(&mut self, tree: bool) {
+        self.history.save(self.current_entry());
+        let layout = if tree { self.history.layout() } else { self.history.path().into_iter().map(|i| (i, 0)).collect() };
+        let selected = layout.iter().position(|&(i, _)| i == self.history.current).unwrap_or(0);
+        let entries = layout.into_iter().map(|(i, indent)| (self.history_line(i, indent), PopupAction::JumpHistory(i))).collect();
+        self.open_menu(if tree { "History Tree" } else { "History" }, entries);
+        if let Some(popup) = self.popup.as_mut() { popup.selected = selected; }
+    }
+
+    fn show_info(&mut self) {
+        let info = self.page_info.clone();
+        let field = |k: &str, v: Option<&String>| Line::from(vec![
+            Span::styled(i18n::pad(i18n::lookup(k), 14), Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw(v.cloned().unwrap_or_else(|| "-".to_string())),
+        ]);
+        let mut lines = vec![
+            field("URL", Some(&self.current_url)),
+            field("Title", info.title.as_ref()),
+            field("Description", info.description.as_ref()),
+            field("Canonical", info.canonical.as_ref()),
+            field("Length", Some(&self.stats.summary())),
+            field("HTTP", self.session_log.iter().rev().find(|e| e.request.url == self.current_url).map(|e| &e.response.http_version)),
+            field("Security", Some(&i18n::lookup(self.security().describe()).to_string())),
+        ];
+        if !info.social.is_empty() {
+            lines.push(Line::from(""));
+            lines.extend(info.social.iter().map(|(k, v)| field(k, Some(v))));
+        }
+        self.open_popup("Page Info", lines);
+    }
+
+    fn translate(&mut self, lang: &str, force: bool) -> Result<(), Box<dyn Error>> {
+        let selection = self.selected_text();
+        if lang.is_empty() && selection.is_none() && let Some(t) = self.translation.as_mut() {
+            std::mem::swap(&mut self.content_lines, &mut t.other_lines);
+            std::mem::swap(&mut self.links, &mut t.other_links);
+            t.showing_translated = !t.showing_translated;
+            self.selected_link_idx = 0;
+            self.status = if t.showing_translated { tr!("Showing translation ({})", t.lang) } else { tr!("Showing original") };
+            return Ok(());
+        }
+        let lang = if lang.is_empty() { self.settings.translate_lang.clone() } else { lang.to_string() };
+        if let Translator::Command(ref cmd) = self.settings.translator
+            && !self.may_proceed(SideEffect::Exec(cmd.replace("{lang}", &lang)), force, format!("translate! {}", lang))
+        {
+            return Ok(());
+        }
+        // A caret-mode selection is translated on its own, into a popup over the page
+        if let Some(t) = self.translation.take_if(|t| t.showing_translated && selection.is_none()) {
+            self.content_lines = t.other_lines;
+            self.links = t.other_links;
+        }
+        self.status = tr!("Translating to {}...", lang);
+        let text = selection.clone().unwrap_or_else(|| self.page_text());
+        let (net, backend, page) = (self.network()?.clone(), self.settings.translator.clone(), self.current_url.clone());
+        self.spawn_job(async move {
+            let result = translate::translate(&net, &backend, &text, &lang).await.map_err(|e| e.to_string());
+            Job::Translation { page, lang, selection: selection.is_some(), result }
+        });
+        Ok(())
+    }
+
+    pub fn show_translation(&mut self, page: &str, lang: &str, selection: bool, translated: String) {
+        if selection {
+            self.open_popup(&tr!("Translation ({})", lang), translated.lines().map(|l| Line::from(l.to_string())).collect());
+            self.status = tr!("Translated the selection to {}", lang);
+            return;
+        }
+        if page != self.current_url || self.loading.is_some() {
+            self.status = tr!("Translation of {} dropped: the page has changed", page);
+            return;
+        }
+        if let Some(t) = self.translation.take() && t.showing_translated {
+            self.content_lines = t.other_lines;
+            self.links = t.other_links;
+        }
+        let lines = translated.lines().map(|l| Line::from(l.to_string())).collect();
+        self.translation = Some(Translation {
+            lang: lang.to_string(),
+            showing_translated: true,
+            other_lines: std::mem::replace(&mut self.content_lines, lines),
+            other_links: std::mem::take(&mut self.links),
+        });
+        self.selected_link_idx = 0;
+        self.status = tr!("Showing translation ({}) — :translate to toggle", lang);
+    }
+
+    pub fn define(&mut self, word: &str, force: bool) -> Result<(), Box<dyn Error>> {
+        let word = word.trim_matches(|c: char| !c.is_alphanumeric());
+        if word.is_empty() {
+            self.status = tr!("define: no word given");
+            return Ok(());
+        }
+        if let Dictionary::Command(ref cmd) = self.settings.dictionary
+            && !self.may_proceed(SideEffect::Exec(format!("{} '{}'", cmd, word)), force, format!("define! {}", word))
+        {
+            return Ok(());
+        }
+        // DICT is a plain TCP connection, which would go around the proxy
+        if matches!(self.settings.dictionary, Dictionary::Dict(_)) && self.proxied() {
+            self.status = tr!("define: dict lookups can't go through the proxy; :set dictionary=web:<url> instead");
+            return Ok(());
+        }
+        self.status = tr!("Looking up {}...", word);
+        let (net, backend, word) = (self.network()?.clone(), self.settings.dictionary.clone(), word.to_string());
+        self.spawn_job(async move {
+            let result = dictionary::lookup(&net, &backend, &word).await.map_err(|e| e.to_string());
+            Job::Definition { word, result }
+        });
+        Ok(())
+    }
+
+    pub fn show_definition(&mut self, word: &str, text: &str) {
+        if text.trim().is_empty() {
+            self.status = tr!("No definition found for {}", word);
+        } else {
+            self.open_popup(&tr!("Define: {}", word), text.lines().map(|l| Line::from(l.to_string())).collect());
+            self.status = tr!("Defined {}", word);
+        }
+    }
+
+    // Renders extracted HTML fragments in place of the page until Esc restores it
+    fn show_extraction(&mut self, label: &str, fragments: Vec<String>) -> Result<(), Box<dyn Error>> {
+        if fragments.is_empty() {
+            self.status = tr!("{}: no matches", label);
+            return Ok(());
+        }
+        let base = Url::parse(&self.current_url)?;
+        let html = fragments.iter().map(|f| format!("<div>{}</div>", f)).collect::<Vec<_>>().join("<hr>");
+        let (lines, links) = self.layout_html(&html, &base, 0);
+        self.show_view(ViewKind::Extract, lines, links);
+        self.status = tr!("{}: {} match(es) — Esc to return to the page", label, fragments.len());
+        Ok(())
+    }
+
+    // `[html|md|text] <path>`: the page as fetched, as Markdown, or as the content pane shows it.
+    // Without a format the extension picks one, and other extensions get the text.
+    fn save_page(&mut self, arg: &str, force: bool) -> Result<(), Box<dyn Error>> {
+        let (format, path) = match arg.split_once(' ').unwrap_or((arg, "")) {
+            (format @ ("html" | "md" | "text"), path) => (format, path.trim()),
+            _ => {
+                let ext = std::path::Path::new(arg).extension().and_then(|e| e.to_str()).unwrap_or_default().to_ascii_lowercase();
+                match ext.as_str() {
+                    "html" | "htm" | "xhtml" => ("html", arg),
+                    "md" | "markdown" => ("md", arg),
+                    _ => ("text", arg),
+                }
+            }
+        };
+        if path.is_empty() {
+            self.prompt_path("Save", format!("save {}", format).trim_end());
+            return Ok(());
+        }
+        if !self.may_proceed(SideEffect::Write(path.to_string()), force, format!("save! {}", arg)) {
+            return Ok(());
+        }
+        match format {
+            "html" => {
+                let html = self.fetched_source.as_deref().unwrap_or(&self.raw_html);
+                std::fs::write(path, html)?;
+                self.notify(ToastKind::Info, tr!("Saved the page's HTML to {}", path));
+            }
+            "md" => {
+                let md = markdown::from_html(&self.raw_html, &Url::parse(&self.current_url)?);
+                std::fs::write(path, &md)?;
+                self.notify(ToastKind::Info, tr!("Saved {} lines of Markdown to {}", md.lines().count(), path));
+            }
+            _ => {
+                // Whatever the content pane shows, so an active extraction is saved on its own
+                let text = self.page_text();
+                std::fs::write(path, &text)?;
+                self.notify(ToastKind::Info, tr!("Saved {} lines to {}", text.lines().count(), path));
+            }
+        }
+        Ok(())
+    }
+
+    fn table_export(&mut self, arg: &str, force: bool) -> Result<(), Box<dyn Error>> {
+        let (n, path) = arg.split_once(' ').map(|(n, p)| (n, p.trim())).unwrap_or((arg, ""));
+        let document = dom::parse(&self.raw_html);
+        let tables = table::tables(&document.document);
+        if tables.is_empty() {
+            self.status = tr!("table-export: no tables on this page");
+            return Ok(());
+        }
+        // Without a table number, `arg` is at most the path and the table is picked from a list
+        let Ok(n) = n.parse::<usize>() else {
+            let options = tables.iter().enumerate().map(|(i, t)| {
+                let grid = table::flatten(t);
+                let head = grid.first().map(|row| row.join(" | ")).unwrap_or_default();
+                let label = tr!("{} rows x {} columns: {}", grid.len(), grid.first().map_or(0, Vec::len), head.chars().take(60).collect::<String>());
+                (label, format!("table-export {} {}", i + 1, arg).trim_end().to_string())
+            }).collect();
+            self.open_dialog("Export Table", tr!("Which table?"), DialogKind::Choice { options, selected: 0 });
+            return Ok(());
+        };
+        let Some(t) = n.checked_sub(1).and_then(|i| tables.get(i)) else {
+            self.status = tr!("table-export: expected a table number 1..{}", tables.len());
+            return Ok(());
+        };
+        if path.is_empty() {
+            self.prompt_path("Export Table", &format!("table-export {}", n));
+            return Ok(());
+        }
+        if !self.may_proceed(SideEffect::Write(path.to_string()), force, format!("table-export! {}", arg)) {
+            return Ok(());
+        }
+        let grid = table::flatten(t);
+        std::fs::write(path, table::to_delimited(&grid, path.ends_with(".tsv")))?;
+        self.notify(ToastKind::Info, tr!("Wrote {} rows x {} columns to {}", grid.len(), grid.first().map_or(0, Vec::len), path));
+        Ok(())
+    }
+
+    // `[pattern] [> path]`: copies the page's link URLs, or writes them to `path`
+    fn yank_links(&mut self, arg: &str, force: bool) -> Result<(), Box<dyn Error>> {
+        // Only a > with space (or nothing) on either side starts the path, so a regex can hold one
+        let redirect = arg.char_indices().rev()
+            .filter(|&(_, c)| c == '>')
+            .map(|(i, _)| i)
+            .find(|&i| arg[..i].chars().next_back().is_none_or(char::is_whitespace) && arg[i + 1..].chars().next().is_none_or(char::is_whitespace));
+        let (pattern, path) = match redirect {
+            Some(i) => (arg[..i].trim(), Some(arg[i + 1..].trim())),
+            None => (arg, None),
+        };
+        let urls = match self.matching_links(pattern) {
+            Ok(urls) => urls,
+            Err(e) => {
+                self.status = tr!("yank-links: {}", e);
+                return Ok(());
+            }
+        };
+        if urls.is_empty() {
+            self.status = tr!("yank-links: no matching links");
+            return Ok(());
+        }
+        let list = urls.join("\n") + "\n";
+        let count = urls.len();
+        match path {
+            Some("") => self.prompt_path("Yank Links", &format!("yank-links {} >", arg.trim_end_matches('>').trim())),
+            Some(path) => {
+                if self.may_proceed(SideEffect::Write(path.to_string()), force, format!("yank-links! {}", arg)) {
+                    std::fs::write(path, list)?;
+                    self.notify(ToastKind::Info, tr!("Wrote {} links to {}", count, path));
+                }
+            }
+            None => {
+                let via = clipboard::copy(&list);
+                self.status = tr!("Copied {} links ({})", count, via);
+            }
+        }
+        Ok(())
+    }
+
+    fn show_outline(&mut self) {
+        let texts: Vec<String> = self.content_lines.iter()
+            .map(|l| l.spans.iter().map(|s| s.content.as_ref()).collect())
+            .collect();
+        let mut next_line = 0;
+        let mut entries = Vec::new();
+        for (level, text) in &self.page_info.headings {
+            let prefix = format!("{} ", "#".repeat(*level));
+            let found = texts.iter().skip(next_line).position(|t| t.trim_start().starts_with(&prefix)).map(|p| p + next_line);
+            let Some(line) = found else { continue };
+            next_line = line + 1;
+            let label = Line::from(format!("{}{}", "  ".repeat(level - 1), text));
+            entries.push((label, PopupAction::JumpToLine(line as u16)));
+        }
+        if entries.is_empty() {
+            self.status = tr!("outline: no headings on this page");
+        } else {
+            self.open_menu("Outline", entries);
+        }
+    }
+
+    pub fn search_url(&self, query: &str) -> String {
+        self.settings.search_engine.replace("{}", &encode_query(query))
+    }
+}
@@ -0,0 +1,282 @@
+use crate::app::App;
+use crate::headers::HeaderRules;
+use crate::help::PREFIX_KEYS;
+use crate::i18n::tr;
+use crate::storage;
+use crate::types::ToastKind;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+// Normal-mode actions that voyager.toml can bind, each with the key it is built in as. Those
+// reached through a prefix key (see help::PREFIX_KEYS) are written as both keys.
+pub const ACTIONS: &[(&str, &str)] = &[
+    ("command", ":"),
+    ("search", "/"),
+    ("palette", "Ctrl-p"),
+    ("link-finder", "L"),
+    ("omnibar", "o"),
+    ("edit-url", "O"),
+    ("help", "?"),
+    ("define", "K"),
+    ("scroll-down", "j"),
+    ("scroll-up", "k"),
+    ("half-page-down", "Ctrl-d"),
+    ("half-page-up", "Ctrl-u"),
+    ("page-down", "Ctrl-f"),
+    ("page-up", "Ctrl-b"),
+    ("top", "Home"),
+    ("bottom", "G"),
+    ("next-link", "l"),
+    ("prev-link", "h"),
+    ("follow", "Enter"),
+    ("hint", "f"),
+    ("back", "B"),
+    ("forward", "F"),
+    ("zoom-in", "+"),
+    ("zoom-out", "-"),
+    ("titles", "t"),
+    ("caret", "v"),
+    ("source", "\\"),
+    ("next-match", "n"),
+    ("prev-match", "N"),
+    ("network-pane", "Ctrl-n"),
+    ("next-tab", "gt"),
+    ("prev-tab", "gT"),
+    ("next-page", "]p"),
+    ("prev-page", "[p"),
+    ("source-view", "gs"),
+    ("register", "\""),
+    ("yank-link", "y"),
+    ("yank-url", "Y"),
+    ("paste", "p"),
+    ("follow-refresh", "R"),
+    ("cancel-refresh", "X"),
+    ("escape", "Esc"),
+];
+
+#[derive(Clone, PartialEq)]
+enum Binding {
+    // Stands in for the built-in key of an action
+    Key(KeyEvent),
+    // Stands in for a prefix key and the key after it
+    Prefixed(char, KeyEvent),
+    // Run as if typed after `:`
+    Command(String),
+    // "none": the key does nothing
+    Unbound,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct ConfigFile {
+    proxy: Option<String>,
+    user_agent: Option<String>,
+    keys: HashMap<String, String>,
+    aliases: HashMap<String, String>,
+    headers: HashMap<String, String>,
+    site_headers: HashMap<String, HashMap<String, String>>,
+}
+
+// voyager.toml in the config directory, read at startup and by :reload-config:
+//
+//   proxy = "socks5h://127.0.0.1:9050"   # the proxy setting, unless VOYAGER_PROXY is set
+//   user_agent = "Mozilla/5.0"           # the useragent setting, unless VOYAGER_USER_AGENT is set
+//
+//   [keys]
+//   J = "scroll-down"        # an action from ACTIONS
+//   T = "next-tab"           # the same for one behind a prefix key, here gt
+//   "Ctrl-r" = ":reload"     # a command
+//   h = "none"               # nothing at all
+//
+//   [aliases]
+//   gh = "url https://github.com"
+//
+// and [headers] / [site_headers] tables, described in headers.rs. A key that isn't listed keeps its built-in meaning.
+#[derive(Default)]
+pub struct Config {
+    pub proxy: Option<String>,
+    pub user_agent: Option<String>,
+    pub headers: Arc<HeaderRules>,
+    // With the key as written in the file, for the help page
+    keys: HashMap<(KeyCode, KeyModifiers), (String, Binding)>,
+    aliases: HashMap<String, String>,
+}
+
+pub enum Resolved {
+    Key(KeyEvent),
+    Prefixed(char, KeyEvent),
+    Command(String),
+}
+
+// Characters carry their case, so Shift only counts for the other keys
+fn normalize(code: KeyCode, mut modifiers: KeyModifiers) -> (KeyCode, KeyModifiers) {
+    if let KeyCode::Char(_) = code {
+        modifiers.remove(KeyModifiers::SHIFT);
+    }
+    (code, modifiers)
+}
+
+// "j", "J", "Ctrl-p", "Alt-Left", "Enter", "Space", "F5", ...
+fn parse_key(spec: &str) -> Option<(KeyCode, KeyModifiers)> {
+    let mut modifiers = KeyModifiers::NONE;
+    let mut rest = spec;
+    while let Some((prefix, key)) = rest.split_once('-').filter(|(_, k)| !k.is_empty()) {
+        match prefix.to_ascii_lowercase().as_str() {
+            "ctrl" => modifiers.insert(KeyModifiers::CONTROL),
+            "alt" => modifiers.insert(KeyModifiers::ALT),
+            "shift" => modifiers.insert(KeyModifiers::SHIFT),
+            _ => break,
+        }
+        rest = key;
+    }
+    let code = match rest.to_ascii_lowercase().as_str() {
+        "enter" | "return" => KeyCode::Enter,
+        "esc" | "escape" => KeyCode::Esc,
+        "tab" => KeyCode::Tab,
+        "backtab" => KeyCode::BackTab,
+        "backspace" => KeyCode::Backspace,
+        "space" => KeyCode::Char(' '),
+        "up" => KeyCode::Up,
+        "down" => KeyCode::Down,
+        "left" => KeyCode::Left,
+        "right" => KeyCode::Right,
+        "home" => KeyCode::Home,
+        "end" => KeyCode::End,
+        "pageup" => KeyCode::PageUp,
+        "pagedown" => KeyCode::PageDown,
+        "delete" => KeyCode::Delete,
+        "insert" => KeyCode::Insert,
+        f if f.len() > 1 && f.starts_with('f') => KeyCode::F(f[1..].parse().ok()?),
+        _ => {
+            let mut chars = rest.chars();
+            let c = chars.next()?;
+            if chars.next().is_some() {
+                return None;
+            }
+            // Terminals send Ctrl-P as Ctrl-p
+            KeyCode::Char(if modifiers.contains(KeyModifiers::CONTROL) { c.to_ascii_lowercase() } else { c })
+        }
+    };
+    Some(normalize(code, modifiers))
+}
+
+// An action's built-in key: a single key, or a prefix key and the one after it
+fn builtin(spec: &str) -> Option<Binding> {
+    if let Some((code, modifiers)) = parse_key(spec) {
+        return Some(Binding::Key(KeyEvent::new(code, modifiers)));
+    }
+    let mut chars = spec.chars();
+    let prefix = chars.next().filter(|c| PREFIX_KEYS.iter().any(|(p, _, _)| p == c))?;
+    let (code, modifiers) = parse_key(chars.as_str())?;
+    Some(Binding::Prefixed(prefix, KeyEvent::new(code, modifiers)))
+}
+
+impl Config {
+    // Bad entries are skipped and described in the returned list, so one typo doesn't lose the rest
+    pub fn load() -> (Self, Vec<String>) {
+        let path = storage::config_file("voyager.toml");
+        let text = match std::fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return (Self::default(), Vec::new()),
+            Err(e) => return (Self::default(), vec![format!("{}: {}", path.display(), e)]),
+        };
+        let file: ConfigFile = match toml::from_str(&text) {
+            Ok(file) => file,
+            Err(e) => return (Self::default(), vec![format!("{}: {}", path.display(), e)]),
+        };
+        let mut problems = Vec::new();
+        let headers = Arc::new(HeaderRules::parse(file.headers, file.site_headers, &mut problems));
+        let mut config = Config { proxy: file.proxy, user_agent: file.user_agent, headers, keys: HashMap::new(), aliases: file.aliases };
+        for (key, action) in file.keys {
+            let Some(id) = parse_key(&key) else {
+                problems.push(format!("unknown key {:?}", key));
+                continue;
+            };
+            let binding = match action.strip_prefix(':') {
+                Some(cmd) => Binding::Command(cmd.trim().to_string()),
+                None if action == "none" => Binding::Unbound,
+                None => match ACTIONS.iter().find(|(name, _)| *name == action).and_then(|(_, default)| builtin(default)) {
+                    Some(binding) => binding,
+                    None => {
+                        problems.push(format!("unknown action {:?} for {}", action, key));
+                        continue;
+                    }
+                },
+            };
+            config.keys.insert(id, (key, binding));
+        }
+        (config, problems)
+    }
+
+    // Normal-mode keys go through here before the built-in bindings see them
+    pub fn resolve(&self, key: KeyEvent) -> Resolved {
+        match self.keys.get(&normalize(key.code, key.modifiers)).map(|(_, b)| b) {
+            Some(Binding::Key(builtin)) => Resolved::Key(*builtin),
+            Some(Binding::Prefixed(prefix, builtin)) => Resolved::Prefixed(*prefix, *builtin),
+            Some(Binding::Command(cmd)) => Resolved::Command(cmd.clone()),
+            Some(Binding::Unbound) => Resolved::Key(KeyEvent::new(KeyCode::Null, KeyModifiers::NONE)),
+            None => Resolved::Key(key),
+        }
+    }
+
+    // The keys that do an action now, given its built-in key: that one unless it (or its prefix)
+    // was rebound, and any bound to the action. Empty when no key does it.
+    pub fn keys_for(&self, default: &str) -> Vec<String> {
+        let Some(builtin) = builtin(default) else { return Vec::new() };
+        let mut keys: Vec<String> = self.keys.values()
+            .filter(|(_, b)| *b == builtin)
+            .map(|(spec, _)| spec.clone())
+            .collect();
+        keys.sort();
+        let first = match builtin {
+            Binding::Prefixed(prefix, _) => (KeyCode::Char(prefix), KeyModifiers::NONE),
+            Binding::Key(key) => (key.code, key.modifiers),
+            _ => return keys,
+        };
+        if !self.keys.contains_key(&first) {
+            keys.insert(0, default.to_string());
+        }
+        keys
+    }
+
+    // An alias is replaced by its expansion, with any arguments after it; only one level deep,
+    // so an alias can't loop
+    pub fn expand_alias(&self, cmd: &str) -> String {
+        let (name, arg) = cmd.split_once(' ').map(|(n, a)| (n, a.trim())).unwrap_or((cmd, ""));
+        match self.aliases.get(name) {
+            Some(expansion) if arg.is_empty() => expansion.clone(),
+            Some(expansion) => format!("{} {}", expansion, arg),
+            None => cmd.to_string(),
+        }
+    }
+
+    pub fn counts(&self) -> (usize, usize, usize) {
+        (self.keys.len(), self.aliases.len(), self.headers.count())
+    }
+}
+
+impl App {
+    pub fn load_config(&mut self) {
+        let (config, problems) = Config::load();
+        self.config = config;
+        self.redirect_log.set_sites(self.config.headers.site_domains());
+        self.net.set_headers(self.config.headers.clone());
+        for problem in problems {
+            self.notify(ToastKind::Error, tr!("voyager.toml: {}", problem));
+        }
+        // Settings the file can hold; the environment variable wins where both are set
+        let settings = [
+            ("proxy", "VOYAGER_PROXY", self.config.proxy.clone()),
+            ("useragent", "VOYAGER_USER_AGENT", self.config.user_agent.clone()),
+        ];
+        for (key, env, value) in settings {
+            let Some(value) = value.filter(|_| std::env::var_os(env).is_none()) else { continue };
+            match self.settings.set(key, &value) {
+                Ok(_) => self.rebuild_client(),
+                Err(e) => self.notify(ToastKind::Error, tr!("voyager.toml: {}: {}", key, e)),
+            }
+        }
+    }
+}
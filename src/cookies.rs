@@ -0,0 +1,289 @@
+use crate::about::escape_html;
+use crate::app::App;
+use crate::har::iso8601;
+use crate::i18n::tr;
+use reqwest::header::HeaderValue;
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use url::Url;
+
+const MONTHS: [&str; 12] = ["jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec"];
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Cookie {
+    pub name: String,
+    pub value: String,
+    // Lowercase, without a leading dot
+    pub domain: String,
+    pub path: String,
+    // Set without a Domain attribute: sent to exactly `domain`, not its subdomains
+    #[serde(default)]
+    pub host_only: bool,
+    #[serde(default)]
+    pub secure: bool,
+    // Unix time; None for a session cookie, which is never written to disk
+    pub expires: Option<u64>,
+}
+
+fn now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0)
+}
+
+// Howard Hinnant's days_from_civil, the inverse of the one in har.rs
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = y.div_euclid(400);
+    let yoe = y.rem_euclid(400);
+    let mp = (month + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+// "Wed, 21 Oct 2026 07:28:00 GMT", and the older "Wednesday, 21-Oct-26 07:28:00 GMT"
+fn parse_http_date(date: &str) -> Option<u64> {
+    let (mut day, mut month, mut year, mut time) = (None, None, None, None);
+    for token in date.split([' ', '-', ',']).filter(|t| !t.is_empty()) {
+        let lower = token.to_ascii_lowercase();
+        if token.contains(':') {
+            let parts: Vec<i64> = token.split(':').filter_map(|p| p.parse().ok()).collect();
+            if let [h, m, s] = parts[..] {
+                time = Some(h * 3600 + m * 60 + s);
+            }
+        } else if let Some(m) = MONTHS.iter().position(|m| lower.starts_with(m)) {
+            month = Some(m as i64 + 1);
+        } else if let Ok(n) = token.parse::<i64>() {
+            match token.len() {
+                1 | 2 if day.is_none() => day = Some(n),
+                2 => year = Some(if n < 70 { 2000 + n } else { 1900 + n }),
+                4 => year = Some(n),
+                _ => {}
+            }
+        }
+    }
+    let secs = days_from_civil(year?, month?, day?) * 86_400 + time.unwrap_or(0);
+    Some(secs.max(0) as u64)
+}
+
+fn domain_matches(host: &str, domain: &str) -> bool {
+    host == domain || host.ends_with(&format!(".{}", domain))
+}
+
+// The directory part of the request path, as the default cookie path
+fn default_path(url: &Url) -> String {
+    match url.path().rfind('/') {
+        Some(0) | None => "/".to_string(),
+        Some(i) => url.path()[..i].to_string(),
+    }
+}
+
+fn path_matches(request: &str, cookie: &str) -> bool {
+    request == cookie
+        || (request.starts_with(cookie) && (cookie.ends_with('/') || request[cookie.len()..].starts_with('/')))
+}
+
+// One Set-Cookie header; None when it is malformed or names a domain the URL can't set
+fn parse_set_cookie(header: &str, url: &Url) -> Option<Cookie> {
+    let host = url.host_str()?.to_ascii_lowercase();
+    let mut parts = header.split(';');
+    let (name, value) = parts.next()?.split_once('=')?;
+    let name = name.trim();
+    if name.is_empty() {
+        return None;
+    }
+    let mut cookie = Cookie {
+        name: name.to_string(),
+        value: value.trim().trim_matches('"').to_string(),
+        domain: host.clone(),
+        path: default_path(url),
+        host_only: true,
+        secure: false,
+        expires: None,
+    };
+    let mut max_age = None;
+    for attr in parts {
+        let (key, val) = attr.split_once('=').unwrap_or((attr, ""));
+        let val = val.trim();
+        match key.trim().to_ascii_lowercase().as_str() {
+            "domain" if !val.is_empty() => {
+                let domain = val.trim_start_matches('.').to_ascii_lowercase();
+                if !domain_matches(&host, &domain) {
+                    return None;
+                }
+                // A public suffix (com, co.uk, github.io) would reach every site under it. One
+                // that is the host itself only gets a host-only cookie, as RFC 6265 says.
+                if psl::suffix_str(&domain) == Some(domain.as_str()) {
+                    match domain == host {
+                        true => continue,
+                        false => return None,
+                    }
+                }
+                cookie.domain = domain;
+                cookie.host_only = false;
+            }
+            "path" if val.starts_with('/') => cookie.path = val.to_string(),
+            "secure" => cookie.secure = true,
+            "max-age" => max_age = val.parse::<i64>().ok(),
+            "expires" => cookie.expires = cookie.expires.or(parse_http_date(val)),
+            _ => {}
+        }
+    }
+    // Max-Age wins over Expires; zero or less deletes the cookie, as does a date in the past
+    if let Some(age) = max_age {
+        cookie.expires = Some((now() as i64).saturating_add(age).max(0) as u64);
+    }
+    Some(cookie)
+}
+
+// The client's cookie store, shared with the App so the cookies can be listed and cleared.
+// Persistent cookies are kept in one JSON array in the data directory.
+pub struct CookieJar {
+    path: PathBuf,
+    cookies: Mutex<Vec<Cookie>>,
+}
+
+impl CookieJar {
+    pub fn load(path: PathBuf) -> Self {
+        let mut cookies: Vec<Cookie> = fs::read_to_string(&path).ok().and_then(|data| serde_json::from_str(&data).ok()).unwrap_or_default();
+        let now = now();
+        cookies.retain(|c| c.expires.is_some_and(|e| e > now));
+        Self { path, cookies: Mutex::new(cookies) }
+    }
+
+    fn save(&self, cookies: &[Cookie]) {
+        let persistent: Vec<&Cookie> = cookies.iter().filter(|c| c.expires.is_some()).collect();
+        let Ok(data) = serde_json::to_string_pretty(&persistent) else { return };
+        if let Some(dir) = self.path.parent() {
+            let _ = fs::create_dir_all(dir);
+        }
+        let _ = fs::write(&self.path, data);
+        // They can be as good as a password, so only the user may read them
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let _ = fs::set_permissions(&self.path, fs::Permissions::from_mode(0o600));
+        }
+    }
+
+    // Cookies grouped by domain, domains in alphabetical order
+    pub fn by_domain(&self) -> Vec<(String, Vec<Cookie>)> {
+        let now = now();
+        let mut groups: Vec<(String, Vec<Cookie>)> = Vec::new();
+        for cookie in self.cookies.lock().unwrap().iter().filter(|c| c.expires.is_none_or(|e| e > now)) {
+            match groups.iter_mut().find(|(d, _)| *d == cookie.domain) {
+                Some((_, list)) => list.push(cookie.clone()),
+                None => groups.push((cookie.domain.clone(), vec![cookie.clone()])),
+            }
+        }
+        groups.sort_by(|a, b| a.0.cmp(&b.0));
+        groups
+    }
+
+    // Removes the cookies of `domain` and its subdomains, or every cookie for ""; returns how many
+    pub fn clear(&self, domain: &str) -> usize {
+        let domain = domain.trim_start_matches('.').to_ascii_lowercase();
+        let mut cookies = self.cookies.lock().unwrap();
+        let before = cookies.len();
+        cookies.retain(|c| !domain.is_empty() && !domain_matches(&c.domain, &domain));
+        let removed = before - cookies.len();
+        if removed > 0 {
+            self.save(&cookies);
+        }
+        removed
+    }
+}
+
+impl reqwest::cookie::CookieStore for CookieJar {
+    fn set_cookies(&self, cookie_headers: &mut dyn Iterator<Item = &HeaderValue>, url: &Url) {
+        let mut cookies = self.cookies.lock().unwrap();
+        let mut persistent_changed = false;
+        let now = now();
+        for cookie in cookie_headers.filter_map(|h| h.to_str().ok()).filter_map(|h| parse_set_cookie(h, url)) {
+            if let Some(old) = cookies.iter().position(|c| c.name == cookie.name && c.domain == cookie.domain && c.path == cookie.path) {
+                persistent_changed |= cookies.remove(old).expires.is_some();
+            }
+            if cookie.expires.is_none_or(|e| e > now) {
+                persistent_changed |= cookie.expires.is_some();
+                cookies.push(cookie);
+            }
+        }
+        if persistent_changed {
+            self.save(&cookies);
+        }
+    }
+
+    fn cookies(&self, url: &Url) -> Option<HeaderValue> {
+        let host = url.host_str()?.to_ascii_lowercase();
+        let now = now();
+        let cookies = self.cookies.lock().unwrap();
+        let mut matching: Vec<&Cookie> = cookies.iter()
+            .filter(|c| if c.host_only { host == c.domain } else { domain_matches(&host, &c.domain) })
+            .filter(|c| path_matches(url.path(), &c.path))
+            .filter(|c| !c.secure || url.scheme() == "https")
+            .filter(|c| c.expires.is_none_or(|e| e > now))
+            .collect();
+        if matching.is_empty() {
+            return None;
+        }
+        // More specific paths first, as RFC 6265 asks
+        matching.sort_by_key(|c| std::cmp::Reverse(c.path.len()));
+        let header = matching.iter().map(|c| format!("{}={}", c.name, c.value)).collect::<Vec<_>>().join("; ");
+        HeaderValue::from_str(&header).ok()
+    }
+}
+
+fn describe_expiry(expires: Option<u64>) -> String {
+    match expires {
+        Some(secs) => iso8601(UNIX_EPOCH + Duration::from_secs(secs))[..10].to_string(),
+        None => "session".to_string(),
+    }
+}
+
+// Every domain holding cookies, and the cookies themselves for one domain or all
+pub fn cookies_page(jar: &CookieJar, domain: &str) -> String {
+    let groups = jar.by_domain();
+    let total: usize = groups.iter().map(|(_, c)| c.len()).sum();
+    let mut html = format!("<title>Cookies</title><h1>Cookies</h1><p>{} cookie(s) from {} site(s). \
+        <code>:cookies clear &lt;domain&gt;</code> removes a site's cookies, <code>:cookies clear all</code> every one.</p>",
+        total, groups.len());
+    if !domain.is_empty() {
+        html.push_str("<p><a href=\"about:cookies\">Show all sites</a></p>");
+    }
+    for (name, cookies) in groups.iter().filter(|(d, _)| domain.is_empty() || domain_matches(d, domain)) {
+        let link = format!("<a href=\"about:cookies?domain={0}\">{0}</a>", escape_html(name));
+        html.push_str(&format!("<h2>{} ({})</h2><ul>", link, cookies.len()));
+        for cookie in cookies {
+            let value: String = cookie.value.chars().take(40).collect();
+            let ellipsis = if cookie.value.chars().count() > 40 { "…" } else { "" };
+            let flags = if cookie.secure { ", secure" } else { "" };
+            html.push_str(&format!("<li><b>{}</b> = {}{}<br>path {}, expires {}{}</li>",
+                escape_html(&cookie.name), escape_html(&value), ellipsis, escape_html(&cookie.path), describe_expiry(cookie.expires), flags));
+        }
+        html.push_str("</ul>");
+    }
+    html
+}
+
+impl App {
+    // `:cookies [domain]` lists them; `:cookies clear <domain|all>` removes them
+    pub async fn cookies_command(&mut self, arg: &str) -> Result<(), Box<dyn Error>> {
+        let (first, rest) = arg.split_once(' ').map(|(a, b)| (a, b.trim())).unwrap_or((arg, ""));
+        match (first, rest) {
+            ("", _) => self.navigate("about:cookies".to_string()).await?,
+            ("clear", "") => self.status = tr!("cookies clear: expected a domain, or all"),
+            ("clear", target) => {
+                let removed = self.cookies.clear(if target == "all" { "" } else { target });
+                if self.current_url.starts_with("about:cookies") {
+                    self.fetch_page().await?;
+                }
+                self.status = tr!("Removed {} cookie(s)", removed);
+            }
+            (domain, _) => self.navigate(format!("about:cookies?domain={}", domain.trim_start_matches('.'))).await?,
+        }
+        Ok(())
+    }
+}
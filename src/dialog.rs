@@ -0,0 +1,131 @@
+use crate::app::App;
+use crate::i18n::{self, tr};
+use crate::types::Mode;
+use crossterm::event::KeyCode;
+use std::error::Error;
+
+// Dialogs resolve to a command line that is run once answered, so any `:` command can
+// sit behind a confirmation, a text prompt or a choice without its own callback plumbing.
+pub enum DialogKind {
+    Confirm { on_yes: String },
+    // `{}` in `on_submit` is replaced by the entered text
+    Prompt { input: String, secret: bool, on_submit: String },
+    // (label, command) pairs
+    Choice { options: Vec<(String, String)>, selected: usize },
+}
+
+pub enum SideEffect {
+    Write(String),
+    // The shell command line, as it will be passed to `sh -c`
+    Exec(String),
+}
+
+pub struct Dialog {
+    pub title: String,
+    pub message: String,
+    pub kind: DialogKind,
+}
+
+impl App {
+    pub fn open_dialog(&mut self, title: &str, message: String, kind: DialogKind) {
+        self.dialog = Some(Dialog { title: format!(" {} ", i18n::lookup(title)), message, kind });
+        self.mode = Mode::Dialog;
+    }
+
+    pub fn confirm(&mut self, title: &str, message: String, on_yes: String) {
+        self.open_dialog(title, message, DialogKind::Confirm { on_yes });
+    }
+
+    // Asks for a file name and runs `command` with it appended
+    pub fn prompt_path(&mut self, title: &str, command: &str) {
+        let kind = DialogKind::Prompt { input: String::new(), secret: false, on_submit: format!("{} {{}}", command) };
+        self.open_dialog(title, tr!("Save to file:"), kind);
+    }
+
+    // Gate for file writes and external commands. True when the action may go ahead now;
+    // otherwise a dialog shows exactly what would happen and re-runs `retry` forced if accepted.
+    pub fn may_proceed(&mut self, effect: SideEffect, force: bool, retry: String) -> bool {
+        if force {
+            return true;
+        }
+        match effect {
+            SideEffect::Write(path) => {
+                let exists = std::path::Path::new(&path).exists();
+                if self.settings.allow_write && !exists {
+                    return true;
+                }
+                let shown = std::path::absolute(&path).map(|p| p.display().to_string()).unwrap_or(path.clone());
+                if self.settings.allow_write {
+                    self.confirm("Overwrite?", tr!("{} already exists. Replace it?", shown), retry);
+                    return false;
+                }
+                let message = if exists { tr!("Write {} (replacing the existing file)", shown) } else { tr!("Write {}", shown) };
+                self.ask_permission("Write File", message, "Always allow file writes", "allow-write", retry);
+            }
+            SideEffect::Exec(command) => {
+                if self.settings.allow_exec {
+                    return true;
+                }
+                self.ask_permission("Run Command", tr!("Run: sh -c {}", command), "Always allow external commands", "allow-exec", retry);
+            }
+        }
+        false
+    }
+
+    fn ask_permission(&mut self, title: &str, message: String, always: &str, setting: &str, retry: String) {
+        let options = vec![
+            (tr!("Allow once"), retry.clone()),
+            (format!("{} (:set {}=on)", i18n::lookup(always), setting), format!("set {}=on\n{}", setting, retry)),
+            (tr!("Cancel"), String::new()),
+        ];
+        self.open_dialog(title, message, DialogKind::Choice { options, selected: 0 });
+    }
+
+    // Runs an answered dialog's command lines in order; Ok(true) when one of them quits
+    pub async fn run_answer(&mut self, commands: &str) -> Result<bool, Box<dyn Error>> {
+        let commands: Vec<&str> = commands.lines().filter(|c| !c.trim().is_empty()).collect();
+        if commands.is_empty() {
+            self.status = tr!("Cancelled");
+        }
+        for cmd in commands {
+            if self.execute_command(cmd).await? {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    // Returns the command to run once the dialog has been answered
+    pub fn handle_dialog_key(&mut self, code: KeyCode) -> Option<String> {
+        let dialog = self.dialog.as_mut()?;
+        let answer = match (&mut dialog.kind, code) {
+            (_, KeyCode::Esc) => Some(None),
+            (DialogKind::Confirm { on_yes }, KeyCode::Char('y')) => Some(Some(std::mem::take(on_yes))),
+            (DialogKind::Confirm { .. }, KeyCode::Char('n') | KeyCode::Enter) => Some(None),
+            (DialogKind::Prompt { input, on_submit, .. }, KeyCode::Enter) => Some(Some(on_submit.replace("{}", input))),
+            (DialogKind::Prompt { input, .. }, KeyCode::Backspace) => { input.pop(); None }
+            (DialogKind::Prompt { input, .. }, KeyCode::Char(c)) => { input.push(c); None }
+            (DialogKind::Choice { options, selected }, KeyCode::Char('j') | KeyCode::Down) => {
+                *selected = (*selected + 1).min(options.len().saturating_sub(1));
+                None
+            }
+            (DialogKind::Choice { selected, .. }, KeyCode::Char('k') | KeyCode::Up) => {
+                *selected = selected.saturating_sub(1);
+                None
+            }
+            (DialogKind::Choice { options, selected }, KeyCode::Enter) => Some(options.get(*selected).map(|(_, cmd)| cmd.clone())),
+            (DialogKind::Choice { options, .. }, KeyCode::Char(c)) if c.is_ascii_digit() => {
+                let n = c.to_digit(10).unwrap_or(0) as usize;
+                options.get(n.wrapping_sub(1)).map(|(_, cmd)| Some(cmd.clone()))
+            }
+            _ => None,
+        };
+        let command = answer?;
+        self.dialog = None;
+        self.mode = Mode::Normal;
+        if command.is_none() {
+            self.status = tr!("Cancelled");
+        }
+        command
+    }
+}
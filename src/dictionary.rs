@@ -0,0 +1,73 @@
+use crate::net::Net;
+use crate::types::Dictionary;
+use std::error::Error;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::TcpStream;
+
+// RFC 2229: send DEFINE, collect the text blocks of each 151 response until the final status line
+async fn lookup_dict(host: &str, word: &str) -> Result<String, Box<dyn Error>> {
+    let addr = if host.contains(':') { host.to_string() } else { format!("{}:2628", host) };
+    let stream = TcpStream::connect(addr).await?;
+    let (read, mut write) = stream.into_split();
+    let mut reader = BufReader::new(read).lines();
+    write.write_all(format!("DEFINE * \"{}\"\r\nQUIT\r\n", word.replace('"', "")).as_bytes()).await?;
+    let mut out = String::new();
+    let mut in_text = false;
+    while let Some(line) = reader.next_line().await? {
+        if in_text {
+            if line == "." {
+                in_text = false;
+                out.push('\n');
+            } else {
+                out.push_str(line.strip_prefix("..").map(|l| format!(".{}", l)).as_deref().unwrap_or(&line));
+                out.push('\n');
+            }
+            continue;
+        }
+        match line.get(..3) {
+            Some("151") => {
+                in_text = true;
+                let source = line.splitn(4, ' ').nth(3).unwrap_or_default().trim_matches('"').to_string();
+                out.push_str(&format!("== {} ==\n", source));
+            }
+            Some("552") => return Ok(String::new()),
+            Some("250") | Some("221") => break,
+            Some(code) if code.starts_with('5') => return Err(format!("dict: {}", line).into()),
+            _ => {}
+        }
+    }
+    Ok(out)
+}
+
+async fn lookup_web(net: &Net, template: &str, word: &str) -> Result<String, Box<dyn Error>> {
+    let encoded: String = url::form_urlencoded::byte_serialize(word.as_bytes()).collect();
+    let body = net.send(net.get(template.replace("{}", &encoded))).await?.text().await?;
+    // dictionaryapi.dev style JSON; anything else is shown as plain text
+    let Ok(value) = serde_json::from_str::<serde_json::Value>(&body) else { return Ok(body) };
+    let mut out = String::new();
+    for entry in value.as_array().into_iter().flatten() {
+        for meaning in entry["meanings"].as_array().into_iter().flatten() {
+            out.push_str(&format!("== {} ==\n", meaning["partOfSpeech"].as_str().unwrap_or("?")));
+            for (i, def) in meaning["definitions"].as_array().into_iter().flatten().enumerate() {
+                out.push_str(&format!("{}. {}\n", i + 1, def["definition"].as_str().unwrap_or_default()));
+            }
+            out.push('\n');
+        }
+    }
+    Ok(out)
+}
+
+async fn lookup_command(cmd: &str, word: &str) -> Result<String, Box<dyn Error>> {
+    let out = tokio::process::Command::new("sh")
+        .arg("-c").arg(format!("{} \"$1\"", cmd)).arg("sh").arg(word)
+        .output().await?;
+    Ok(String::from_utf8_lossy(&out.stdout).into_owned())
+}
+
+pub async fn lookup(net: &Net, backend: &Dictionary, word: &str) -> Result<String, Box<dyn Error>> {
+    match backend {
+        Dictionary::Dict(host) => lookup_dict(host, word).await,
+        Dictionary::Web(template) => lookup_web(net, template, word).await,
+        Dictionary::Command(cmd) => lookup_command(cmd, word).await,
+    }
+}
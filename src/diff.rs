@@ -0,0 +1,110 @@
+use crate::app::App;
+use crate::i18n::tr;
+use crate::index::MAX_TEXT_CHARS;
+use crate::types::ViewKind;
+use ratatui::{style::{Color, Style}, text::{Line, Span}};
+
+const CONTEXT_LINES: usize = 3;
+
+#[derive(Clone, Copy, PartialEq)]
+enum Op {
+    Same,
+    Removed,
+    Added,
+}
+
+// Line diff by longest common subsequence, after trimming the common prefix and suffix
+fn diff_lines<'a>(old: &[&'a str], new: &[&'a str]) -> Vec<(Op, &'a str)> {
+    let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..].iter().rev().zip(new[prefix..].iter().rev()).take_while(|(a, b)| a == b).count();
+    let (a, b) = (&old[prefix..old.len() - suffix], &new[prefix..new.len() - suffix]);
+    // lcs[i][j]: length of the LCS of a[i..] and b[j..]
+    let mut lcs = vec![vec![0u32; b.len() + 1]; a.len() + 1];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            lcs[i][j] = if a[i] == b[j] { lcs[i + 1][j + 1] + 1 } else { lcs[i + 1][j].max(lcs[i][j + 1]) };
+        }
+    }
+    let mut ops: Vec<(Op, &str)> = old[..prefix].iter().map(|l| (Op::Same, *l)).collect();
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() || j < b.len() {
+        if i < a.len() && j < b.len() && a[i] == b[j] {
+            ops.push((Op::Same, a[i]));
+            i += 1;
+            j += 1;
+        } else if j < b.len() && (i == a.len() || lcs[i][j + 1] >= lcs[i + 1][j]) {
+            ops.push((Op::Added, b[j]));
+            j += 1;
+        } else {
+            ops.push((Op::Removed, a[i]));
+            i += 1;
+        }
+    }
+    ops.extend(old[old.len() - suffix..].iter().map(|l| (Op::Same, *l)));
+    ops
+}
+
+// Unified diff hunks with a few lines of context, colored like `git diff`
+fn unified(ops: &[(Op, &str)]) -> Vec<Line<'static>> {
+    let changed: Vec<usize> = ops.iter().enumerate().filter(|(_, (op, _))| *op != Op::Same).map(|(i, _)| i).collect();
+    let mut out = Vec::new();
+    let mut k = 0;
+    while k < changed.len() {
+        let start = changed[k].saturating_sub(CONTEXT_LINES);
+        let mut end = changed[k];
+        while k < changed.len() && changed[k] <= end + 2 * CONTEXT_LINES + 1 {
+            end = changed[k];
+            k += 1;
+        }
+        let end = (end + CONTEXT_LINES + 1).min(ops.len());
+        let line_no = |upto: usize, skip: Op| ops[..upto].iter().filter(|(op, _)| *op != skip).count();
+        let count = |skip: Op| ops[start..end].iter().filter(|(op, _)| *op != skip).count();
+        let header = format!("@@ -{},{} +{},{} @@", line_no(start, Op::Added) + 1, count(Op::Added), line_no(start, Op::Removed) + 1, count(Op::Removed));
+        out.push(Line::from(Span::styled(header, Style::default().fg(Color::Cyan))));
+        for &(op, text) in &ops[start..end] {
+            let (sign, style) = match op {
+                Op::Same => (' ', Style::default()),
+                Op::Removed => ('-', Style::default().fg(Color::Red)),
+                Op::Added => ('+', Style::default().fg(Color::Green)),
+            };
+            out.push(Line::from(Span::styled(format!("{}{}", sign, text), style)));
+        }
+    }
+    out
+}
+
+impl App {
+    pub fn toggle_diff(&mut self) {
+        if self.saved_view.as_ref().is_some_and(|v| v.kind == ViewKind::Diff) {
+            self.restore_view();
+            self.status = tr!("Rendered view: {}", self.current_url);
+            return;
+        }
+        self.restore_view();
+        let Some(previous) = self.previous_text.clone() else {
+            self.status = tr!("diff: no earlier copy of this page");
+            return;
+        };
+        // The stored copy is capped, so only the same leading part of the page is compared
+        let current: String = self.page_text().chars().take(MAX_TEXT_CHARS).collect();
+        let old: Vec<&str> = previous.lines().collect();
+        let new: Vec<&str> = current.lines().collect();
+        let ops = diff_lines(&old, &new);
+        let (removed, added) = ops.iter().fold((0, 0), |(r, a), (op, _)| match op {
+            Op::Removed => (r + 1, a),
+            Op::Added => (r, a + 1),
+            Op::Same => (r, a),
+        });
+        if removed + added == 0 {
+            self.status = tr!("diff: unchanged since the last visit");
+            return;
+        }
+        let mut lines = vec![
+            Line::from(Span::styled(tr!("--- {} (last visit)", self.current_url), Style::default().fg(Color::Red))),
+            Line::from(Span::styled(tr!("+++ {} (now)", self.current_url), Style::default().fg(Color::Green))),
+        ];
+        lines.extend(unified(&ops));
+        self.show_view(ViewKind::Diff, lines, Vec::new());
+        self.status = tr!("diff: {} line(s) removed, {} added — Esc to return to the page", removed, added);
+    }
+}
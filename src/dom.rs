@@ -0,0 +1,130 @@
+use html5ever::tendril::TendrilSink;
+use html5ever::serialize::{SerializeOpts, TraversalScope};
+use html5ever::{namespace_url, ns, Attribute, QualName};
+use markup5ever_rcdom::{Handle, Node, NodeData, RcDom, SerializableHandle};
+use std::cell::RefCell;
+
+pub fn parse(html: &str) -> RcDom {
+    html5ever::parse_document(RcDom::default(), Default::default()).one(html)
+}
+
+pub fn serialize(dom: &RcDom) -> String {
+    let mut out = Vec::new();
+    let handle: SerializableHandle = dom.document.clone().into();
+    let _ = html5ever::serialize(&mut out, &handle, Default::default());
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+pub fn serialize_node(handle: &Handle) -> String {
+    let mut out = Vec::new();
+    let node: SerializableHandle = handle.clone().into();
+    let opts = SerializeOpts { traversal_scope: TraversalScope::IncludeNode, ..Default::default() };
+    let _ = html5ever::serialize(&mut out, &node, opts);
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+pub fn parent(handle: &Handle) -> Option<Handle> {
+    let weak = handle.parent.take();
+    let parent = weak.as_ref().and_then(|w| w.upgrade());
+    handle.parent.set(weak);
+    parent
+}
+
+pub fn is_ancestor(ancestor: &Handle, node: &Handle) -> bool {
+    let mut current = parent(node);
+    while let Some(p) = current {
+        if std::rc::Rc::ptr_eq(&p, ancestor) {
+            return true;
+        }
+        current = parent(&p);
+    }
+    false
+}
+
+pub fn child_elements(handle: &Handle) -> Vec<Handle> {
+    handle.children.borrow().iter().filter(|c| matches!(c.data, NodeData::Element { .. })).cloned().collect()
+}
+
+pub fn tag_name(handle: &Handle) -> Option<String> {
+    match handle.data {
+        NodeData::Element { ref name, .. } => Some(name.local.to_string()),
+        _ => None,
+    }
+}
+
+pub fn attr(handle: &Handle, key: &str) -> Option<String> {
+    match handle.data {
+        NodeData::Element { ref attrs, .. } => attrs.borrow().iter()
+            .find(|a| a.name.local.as_ref() == key)
+            .map(|a| a.value.to_string()),
+        _ => None,
+    }
+}
+
+pub fn text_content(handle: &Handle) -> String {
+    let mut out = String::new();
+    collect_text(handle, &mut out);
+    out
+}
+
+fn collect_text(handle: &Handle, out: &mut String) {
+    match handle.data {
+        NodeData::Text { ref contents } => out.push_str(&contents.borrow()),
+        _ => for child in handle.children.borrow().iter() {
+            collect_text(child, out);
+        },
+    }
+}
+
+// Pre-order list of every element below (and excluding) `root`
+pub fn elements(root: &Handle) -> Vec<Handle> {
+    let mut out = Vec::new();
+    let mut stack: Vec<Handle> = root.children.borrow().iter().rev().cloned().collect();
+    while let Some(node) = stack.pop() {
+        if let NodeData::Element { .. } = node.data {
+            out.push(node.clone());
+        }
+        stack.extend(node.children.borrow().iter().rev().cloned());
+    }
+    out
+}
+
+pub fn new_element(tag: &str, attrs: &[(&str, &str)], text: &str) -> Handle {
+    let node = Node::new(NodeData::Element {
+        name: QualName::new(None, ns!(html), tag.into()),
+        attrs: RefCell::new(
+            attrs.iter()
+                .map(|(k, v)| Attribute { name: QualName::new(None, ns!(), (*k).into()), value: (*v).into() })
+                .collect(),
+        ),
+        template_contents: RefCell::new(None),
+        mathml_annotation_xml_integration_point: false,
+    });
+    if !text.is_empty() {
+        append(&node, new_text(text));
+    }
+    node
+}
+
+pub fn new_text(text: &str) -> Handle {
+    Node::new(NodeData::Text { contents: RefCell::new(text.into()) })
+}
+
+pub fn append(parent: &Handle, child: Handle) {
+    child.parent.set(Some(std::rc::Rc::downgrade(parent)));
+    parent.children.borrow_mut().push(child);
+}
+
+pub fn replace(old: &Handle, new: Handle) {
+    let Some(parent) = old.parent.take().and_then(|w| w.upgrade()) else { return };
+    new.parent.set(Some(std::rc::Rc::downgrade(&parent)));
+    let mut children = parent.children.borrow_mut();
+    if let Some(pos) = children.iter().position(|c| std::rc::Rc::ptr_eq(c, old)) {
+        children[pos] = new;
+    }
+}
+
+pub fn remove(node: &Handle) {
+    let Some(parent) = node.parent.take().and_then(|w| w.upgrade()) else { return };
+    parent.children.borrow_mut().retain(|c| !std::rc::Rc::ptr_eq(c, node));
+}
@@ -0,0 +1,182 @@
+use crate::app::App;
+use crate::dialog::{DialogKind, SideEffect};
+use crate::har::Outgoing;
+use crate::i18n::tr;
+use crate::net::Net;
+use crate::types::ToastKind;
+use crate::ui::format_size;
+use std::error::Error;
+use std::time::Duration;
+use tokio::io::AsyncWriteExt;
+use tokio::sync::mpsc::UnboundedSender;
+use url::Url;
+
+// A body that was not rendered, held until it is saved or the page is left
+pub struct Download {
+    pub url: String,
+    pub mime_type: String,
+    pub bytes: Vec<u8>,
+}
+
+// A response whose announced size exceeded the limit, waiting on the user before its body is read
+pub struct HeldResponse {
+    pub response: reqwest::Response,
+    pub outgoing: Outgoing,
+    pub ttfb: Duration,
+    pub base_url: Url,
+}
+
+pub struct DownloadDone {
+    pub path: String,
+    pub result: Result<u64, String>,
+}
+
+async fn stream_to_file(mut res: reqwest::Response, path: &str) -> Result<u64, Box<dyn Error>> {
+    let mut file = tokio::fs::File::create(path).await?;
+    let mut written = 0;
+    while let Some(chunk) = res.chunk().await? {
+        file.write_all(&chunk).await?;
+        written += chunk.len() as u64;
+    }
+    file.flush().await?;
+    Ok(written)
+}
+
+fn spawn_stream(res: reqwest::Response, path: String, tx: UnboundedSender<DownloadDone>) {
+    tokio::spawn(async move {
+        let result = stream_to_file(res, &path).await.map_err(|e| e.to_string());
+        let _ = tx.send(DownloadDone { path, result });
+    });
+}
+
+// Fetches `url` and streams it to `path`, reporting through `tx` like any other download
+pub fn spawn_download(net: Net, url: String, path: String, tx: UnboundedSender<DownloadDone>) {
+    tokio::spawn(async move {
+        let result = match net.send(net.get(&url)).await.and_then(|r| r.error_for_status()) {
+            Ok(res) => stream_to_file(res, &path).await.map_err(|e| e.to_string()),
+            Err(e) => Err(e.to_string()),
+        };
+        let _ = tx.send(DownloadDone { path, result });
+    });
+}
+
+// The last path segment of the URL, or a generic name when it has none
+pub fn suggested_name(url: &str) -> String {
+    Url::parse(url).ok()
+        .and_then(|u| u.path_segments().and_then(|mut s| s.next_back().map(str::to_string)))
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| "download".to_string())
+}
+
+impl App {
+    pub fn offer_download(&mut self, mime_type: &str, bytes: Vec<u8>, base_url: &Url) {
+        let size = format_size(bytes.len());
+        let shown_type = if mime_type.is_empty() { "unknown type" } else { mime_type };
+        let html = format!(
+            "<title>Download</title><h1>Binary content</h1><p>{} ({}, {}) is not text and was not displayed.</p><p>:download &lt;path&gt; saves it.</p>",
+            crate::about::escape_html(&self.current_url), crate::about::escape_html(shown_type), size
+        );
+        self.render_html(&html, base_url);
+        self.download = Some(Download { url: self.current_url.clone(), mime_type: shown_type.to_string(), bytes });
+        self.status = tr!("Binary content ({}), not rendered", size);
+        self.prompt_download();
+    }
+
+    // The sizelimit setting in bytes; 0 never holds a response back
+    pub fn size_limit(&self) -> u64 {
+        self.settings.size_limit_mb.saturating_mul(1024 * 1024)
+    }
+
+    pub fn hold_response(&mut self, held: HeldResponse) {
+        let size = format_size(held.response.content_length().unwrap_or(0) as usize);
+        let url = self.current_url.clone();
+        let html = format!(
+            "<title>Large response</title><h1>Large response</h1><p>{} is {}; nothing has been downloaded yet.</p><p>:continue loads it, :stream-to &lt;path&gt; saves it to disk, :abort drops it.</p>",
+            crate::about::escape_html(&url), size
+        );
+        let base_url = held.base_url.clone();
+        self.render_html(&html, &base_url);
+        self.held = Some(held);
+        self.status = tr!("{} is {}, waiting for confirmation", url, size);
+        let options = vec![
+            (tr!("Continue and load {}", size), "continue".to_string()),
+            (tr!("Save to disk instead"), "stream-to".to_string()),
+            (tr!("Cancel"), "abort".to_string()),
+        ];
+        self.open_dialog("Large Response", tr!("{} is {}.", url, size), DialogKind::Choice { options, selected: 0 });
+    }
+
+    pub async fn continue_held(&mut self) -> Result<(), Box<dyn Error>> {
+        let Some(held) = self.held.take() else {
+            self.status = tr!("continue: no response is waiting");
+            return Ok(());
+        };
+        self.status = tr!("Downloading {}...", self.current_url);
+        self.receive_response(held.response, &held.base_url, held.outgoing, held.ttfb).await
+    }
+
+    pub fn stream_held(&mut self, path: &str, force: bool) -> Result<(), Box<dyn Error>> {
+        let Some(ref held) = self.held else {
+            self.status = tr!("stream-to: no response is waiting");
+            return Ok(());
+        };
+        if path.is_empty() {
+            let kind = DialogKind::Prompt { input: suggested_name(held.response.url().as_str()), secret: false, on_submit: "stream-to {}".to_string() };
+            self.open_dialog("Save to Disk", tr!("Stream the response to:"), kind);
+            return Ok(());
+        }
+        if !self.may_proceed(SideEffect::Write(path.to_string()), force, format!("stream-to! {}", path)) {
+            return Ok(());
+        }
+        let Some(held) = self.held.take() else { return Ok(()) };
+        spawn_stream(held.response, path.to_string(), self.download_tx.clone());
+        self.status = tr!("Downloading to {} in the background", path);
+        Ok(())
+    }
+
+    fn prompt_download(&mut self) {
+        let Some(ref download) = self.download else { return };
+        let message = tr!("Save {} ({}, {}) to:", download.url, download.mime_type, format_size(download.bytes.len()));
+        let kind = DialogKind::Prompt { input: suggested_name(&download.url), secret: false, on_submit: "download {}".to_string() };
+        self.open_dialog("Download", message, kind);
+    }
+
+    pub fn save_download(&mut self, path: &str, force: bool) -> Result<(), Box<dyn Error>> {
+        if self.download.is_none() {
+            self.status = tr!("download: nothing to save on this page");
+            return Ok(());
+        }
+        if path.is_empty() {
+            self.prompt_download();
+            return Ok(());
+        }
+        if !self.may_proceed(SideEffect::Write(path.to_string()), force, format!("download! {}", path)) {
+            return Ok(());
+        }
+        let Some(download) = self.download.take() else { return Ok(()) };
+        std::fs::write(path, &download.bytes)?;
+        self.notify(ToastKind::Info, tr!("Saved {} to {}", format_size(download.bytes.len()), path));
+        Ok(())
+    }
+
+    // `<url> [path]`: downloads a URL in the background, asking for a file name without one
+    pub fn save_url(&mut self, arg: &str, force: bool) -> Result<(), Box<dyn Error>> {
+        let (url, path) = arg.split_once(' ').map(|(u, p)| (u, p.trim())).unwrap_or((arg, ""));
+        if url.is_empty() {
+            self.status = tr!("save-url: expected a URL");
+            return Ok(());
+        }
+        if path.is_empty() {
+            let kind = DialogKind::Prompt { input: suggested_name(url), secret: false, on_submit: format!("save-url {} {{}}", url) };
+            self.open_dialog("Save to Disk", tr!("Save {} to:", url), kind);
+            return Ok(());
+        }
+        let net = self.network()?.clone();
+        if !self.may_proceed(SideEffect::Write(path.to_string()), force, format!("save-url! {}", arg)) {
+            return Ok(());
+        }
+        spawn_download(net, url.to_string(), path.to_string(), self.download_tx.clone());
+        self.status = tr!("Downloading to {} in the background", path);
+        Ok(())
+    }
+}
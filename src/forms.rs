@@ -0,0 +1,331 @@
+use crate::app::App;
+use crate::dom;
+use crate::i18n::tr;
+use crate::types::{LinkType, Mode, PopupAction};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use markup5ever_rcdom::Handle;
+use ratatui::text::Line;
+use std::error::Error;
+use url::Url;
+
+// Form controls are rendered as links to `voyager-field:<form>/<field>`, so they are
+// numbered and selected like any other link
+pub const FIELD_PREFIX: &str = "voyager-field:";
+// Columns a text box takes on the page, whatever its value's length
+const BOX_WIDTH: usize = 20;
+
+#[derive(Clone, Default)]
+pub struct Form {
+    // Absolute; the page's own URL when the form has no action
+    pub action: String,
+    pub post: bool,
+    pub fields: Vec<Field>,
+    // The query box of a site search form: a type=search input, or the first text box of a
+    // form with role=search (or inside one)
+    pub search: Option<usize>,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum FieldKind {
+    Text,
+    Password,
+    TextArea,
+    Hidden,
+    Checkbox,
+    Radio,
+    Select,
+    Submit,
+}
+
+#[derive(Clone)]
+pub struct Field {
+    pub name: String,
+    pub kind: FieldKind,
+    pub value: String,
+    pub checked: bool,
+    // Placeholder of a text box, or the caption of a <button>
+    pub caption: String,
+    // (value, label) of each <option>
+    pub options: Vec<(String, String)>,
+    // What autofill matches on besides the name and placeholder: the autocomplete token first
+    // (empty without one), then id, aria-label and the <label> text
+    pub hints: Vec<String>,
+}
+
+impl Field {
+    // None for controls that can't be filled in or submitted without scripts
+    pub fn from_node(node: &Handle) -> Option<Self> {
+        let attr = |key: &str| dom::attr(node, key).unwrap_or_default();
+        let kind = attr("type").to_ascii_lowercase();
+        let mut field = Field {
+            name: attr("name"),
+            kind: FieldKind::Text,
+            value: attr("value"),
+            checked: dom::attr(node, "checked").is_some(),
+            caption: attr("placeholder"),
+            options: Vec::new(),
+            hints: vec![attr("autocomplete"), attr("id"), attr("aria-label")],
+        };
+        match dom::tag_name(node).as_deref()? {
+            "textarea" => {
+                field.kind = FieldKind::TextArea;
+                field.value = dom::text_content(node);
+            }
+            "select" => {
+                field.kind = FieldKind::Select;
+                let options: Vec<Handle> = dom::elements(node).into_iter().filter(|n| dom::tag_name(n).as_deref() == Some("option")).collect();
+                for option in &options {
+                    let label = dom::text_content(option).split_whitespace().collect::<Vec<_>>().join(" ");
+                    field.options.push((dom::attr(option, "value").unwrap_or_else(|| label.clone()), label));
+                }
+                let selected = options.iter().position(|o| dom::attr(o, "selected").is_some()).unwrap_or(0);
+                field.value = field.options.get(selected).map(|(v, _)| v.clone()).unwrap_or_default();
+            }
+            "button" if matches!(kind.as_str(), "" | "submit") => {
+                field.kind = FieldKind::Submit;
+                field.caption = dom::text_content(node).split_whitespace().collect::<Vec<_>>().join(" ");
+            }
+            "input" => field.kind = match kind.as_str() {
+                "hidden" => FieldKind::Hidden,
+                "password" => FieldKind::Password,
+                "checkbox" => FieldKind::Checkbox,
+                "radio" => FieldKind::Radio,
+                "submit" | "image" => FieldKind::Submit,
+                "reset" | "button" | "file" => return None,
+                _ => FieldKind::Text,
+            },
+            _ => return None,
+        }
+        Some(field)
+    }
+
+    // What stands in for the control on the page; None for hidden inputs
+    pub fn label(&self) -> Option<String> {
+        let text_box = |shown: &str| {
+            let shown: String = shown.chars().map(|c| if c == '\n' { ' ' } else { c }).collect();
+            let chars: Vec<char> = shown.chars().collect();
+            // The end of a long value, where typing happens, stays in view
+            let tail: String = chars[chars.len().saturating_sub(BOX_WIDTH)..].iter().collect();
+            format!("[{:_<width$}]", tail, width = BOX_WIDTH)
+        };
+        Some(match self.kind {
+            FieldKind::Hidden => return None,
+            FieldKind::Checkbox => if self.checked { "[x]" } else { "[ ]" }.to_string(),
+            FieldKind::Radio => if self.checked { "(*)" } else { "( )" }.to_string(),
+            FieldKind::Select => format!("<{}>", self.options.iter().find(|(v, _)| *v == self.value).map_or("", |(_, l)| l.as_str())),
+            FieldKind::Submit => {
+                let caption = [&self.caption, &self.value].into_iter().find(|c| !c.is_empty()).cloned().unwrap_or_else(|| "Submit".to_string());
+                format!("[ {} ]", caption)
+            }
+            FieldKind::Password => text_box(&"*".repeat(self.value.chars().count())),
+            FieldKind::Text | FieldKind::TextArea if self.value.is_empty() => text_box(&self.caption),
+            FieldKind::Text | FieldKind::TextArea => text_box(&self.value),
+        })
+    }
+}
+
+impl Form {
+    // The name/value pairs a browser would send, in document order
+    fn pairs(&self, submitter: Option<usize>) -> Vec<(String, String)> {
+        self.fields.iter().enumerate()
+            .filter(|(_, f)| !f.name.is_empty())
+            .filter(|(i, f)| match f.kind {
+                FieldKind::Checkbox | FieldKind::Radio => f.checked,
+                FieldKind::Submit => submitter == Some(*i),
+                _ => true,
+            })
+            .map(|(_, f)| {
+                let value = match f.kind {
+                    FieldKind::Checkbox | FieldKind::Radio if f.value.is_empty() => "on".to_string(),
+                    FieldKind::TextArea => f.value.replace('\n', "\r\n"),
+                    _ => f.value.clone(),
+                };
+                (f.name.clone(), value)
+            })
+            .collect()
+    }
+}
+
+fn parse_field_url(url: &str) -> Option<(usize, usize)> {
+    let (form, field) = url.strip_prefix(FIELD_PREFIX)?.split_once('/')?;
+    Some((form.parse().ok()?, field.parse().ok()?))
+}
+
+pub fn field_url(form: usize, field: usize) -> String {
+    format!("{}{}/{}", FIELD_PREFIX, form, field)
+}
+
+impl App {
+    fn field(&self, form: usize, field: usize) -> Option<&Field> {
+        self.page_info.forms.get(form)?.fields.get(field)
+    }
+
+    fn field_mut(&mut self, form: usize, field: usize) -> Option<&mut Field> {
+        self.page_info.forms.get_mut(form)?.fields.get_mut(field)
+    }
+
+    // Enter on a form control: text boxes go to insert mode, buttons submit, the rest toggle or offer choices
+    pub async fn activate_field(&mut self, url: &str) -> Result<(), Box<dyn Error>> {
+        let Some((form, idx)) = parse_field_url(url) else { return Ok(()) };
+        let Some(field) = self.field(form, idx).cloned() else { return Ok(()) };
+        match field.kind {
+            FieldKind::Text | FieldKind::Password | FieldKind::TextArea => {
+                self.editing_field = Some((form, idx));
+                self.command_buffer = field.value;
+                self.mode = Mode::Insert;
+                self.status = tr!("-- INSERT -- (Enter: submit, Tab: next field, Esc: done)");
+            }
+            FieldKind::Checkbox => {
+                if let Some(f) = self.field_mut(form, idx) {
+                    f.checked = !f.checked;
+                }
+                self.rerender();
+            }
+            FieldKind::Radio => {
+                for (i, f) in self.page_info.forms[form].fields.iter_mut().enumerate() {
+                    if f.kind == FieldKind::Radio && f.name == field.name {
+                        f.checked = i == idx;
+                    }
+                }
+                self.rerender();
+            }
+            FieldKind::Select => {
+                let entries = field.options.iter().enumerate()
+                    .map(|(n, (value, label))| {
+                        let marker = if *value == field.value { "● " } else { "○ " };
+                        (Line::from(format!("{}{}", marker, label)), PopupAction::FormOption(form, idx, n))
+                    })
+                    .collect();
+                self.open_menu(&tr!("Choose {}", field.name), entries);
+                if let (Some(popup), Some(n)) = (self.popup.as_mut(), field.options.iter().position(|(v, _)| *v == field.value)) {
+                    popup.selected = n;
+                }
+            }
+            FieldKind::Submit => self.submit_form(form, Some(idx)).await?,
+            FieldKind::Hidden => {}
+        }
+        Ok(())
+    }
+
+    pub fn choose_option(&mut self, form: usize, idx: usize, option: usize) {
+        let Some(field) = self.field_mut(form, idx) else { return };
+        if let Some((value, _)) = field.options.get(option) {
+            field.value = value.clone();
+            self.rerender();
+        }
+    }
+
+    // The text being typed goes into the field on leaving insert mode, however it is left
+    fn commit_field(&mut self) {
+        self.mode = Mode::Normal;
+        let value = std::mem::take(&mut self.command_buffer);
+        let Some((form, idx)) = self.editing_field.take() else { return };
+        if let Some(field) = self.field_mut(form, idx) {
+            field.value = value;
+        }
+        self.rerender();
+        self.status.clear();
+    }
+
+    pub async fn handle_insert_key(&mut self, key: KeyEvent) -> Result<(), Box<dyn Error>> {
+        let textarea = self.editing_field.and_then(|(f, i)| self.field(f, i)).is_some_and(|f| f.kind == FieldKind::TextArea);
+        match key.code {
+            KeyCode::Esc => self.commit_field(),
+            KeyCode::Enter if textarea => self.command_buffer.push('\n'),
+            // Like a browser, Enter in a text box submits its form with the first button
+            KeyCode::Enter => {
+                let form = self.editing_field.map(|(f, _)| f);
+                self.commit_field();
+                if let Some(form) = form {
+                    let submitter = self.page_info.forms[form].fields.iter().position(|f| f.kind == FieldKind::Submit);
+                    self.submit_form(form, submitter).await?;
+                }
+            }
+            KeyCode::Tab => {
+                self.commit_field();
+                self.select_next_field();
+            }
+            KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => self.command_buffer.clear(),
+            KeyCode::Backspace => { self.command_buffer.pop(); }
+            KeyCode::Char(c) => self.command_buffer.push(c),
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn select_next_field(&mut self) {
+        let n = self.links.len();
+        let next = (1..=n).map(|step| (self.selected_link_idx + step) % n).find(|&i| self.links[i].link_type == LinkType::Field);
+        if let Some(idx) = next {
+            self.selected_link_idx = idx;
+        }
+    }
+
+    // Status line while typing into a field
+    pub fn insert_prompt(&self) -> String {
+        let Some(field) = self.editing_field.and_then(|(f, i)| self.field(f, i)) else { return String::new() };
+        let shown = match field.kind {
+            FieldKind::Password => "*".repeat(self.command_buffer.chars().count()),
+            _ => self.command_buffer.replace('\n', "⏎"),
+        };
+        tr!("-- INSERT -- {}: {}", field.name, shown)
+    }
+
+    // `:submit [n]`: form n (from 1), or the one the selected link belongs to, or the first
+    pub async fn submit_command(&mut self, arg: &str) -> Result<(), Box<dyn Error>> {
+        if self.page_info.forms.is_empty() {
+            self.status = tr!("submit: no forms on this page");
+            return Ok(());
+        }
+        let selected = self.links.get(self.selected_link_idx).and_then(|l| parse_field_url(&l.url)).map(|(f, _)| f);
+        let count = self.page_info.forms.len();
+        let form = match arg.parse::<usize>() {
+            _ if arg.is_empty() => selected.unwrap_or(0),
+            Ok(n) if (1..=count).contains(&n) => n - 1,
+            _ => {
+                self.status = tr!("submit: expected a form number 1..{}", count);
+                return Ok(());
+            }
+        };
+        let submitter = self.page_info.forms[form].fields.iter().position(|f| f.kind == FieldKind::Submit);
+        self.submit_form(form, submitter).await
+    }
+
+    // `:search-site <query>`: through the page's own search form when it has one, otherwise the
+    // search engine with a site: operator
+    pub async fn search_site(&mut self, query: &str) -> Result<(), Box<dyn Error>> {
+        let found = self.page_info.forms.iter().enumerate().find_map(|(i, f)| Some((i, f.search?)));
+        if let Some((form, idx)) = found {
+            if let Some(field) = self.field_mut(form, idx) {
+                field.value = query.to_string();
+            }
+            let submitter = self.page_info.forms[form].fields.iter().position(|f| f.kind == FieldKind::Submit);
+            return self.submit_form(form, submitter).await;
+        }
+        let Some(host) = Url::parse(&self.current_url).ok().and_then(|u| u.host_str().map(str::to_string)) else {
+            self.status = tr!("search-site: current page has no domain");
+            return Ok(());
+        };
+        let url = self.search_url(&format!("site:{} {}", host, query));
+        self.navigate(url).await
+    }
+
+    // GET forms navigate to the action with the fields as its query; POST sends them urlencoded
+    pub async fn submit_form(&mut self, form: usize, submitter: Option<usize>) -> Result<(), Box<dyn Error>> {
+        let Some(form) = self.page_info.forms.get(form).cloned() else { return Ok(()) };
+        let pairs = form.pairs(submitter);
+        let mut target = Url::parse(&form.action)?;
+        if !matches!(target.scheme(), "http" | "https") {
+            self.status = tr!("submit: can't send a form to {}", form.action);
+            return Ok(());
+        }
+        if !form.post {
+            target.set_fragment(None);
+            target.query_pairs_mut().clear().extend_pairs(&pairs);
+            return self.navigate(target.to_string()).await;
+        }
+        self.status = tr!("Submitting {} field(s) to {}...", pairs.len(), target);
+        let builder = self.net.post(target.clone()).form(&pairs);
+        self.load_request(target.as_str(), builder)
+    }
+}
@@ -0,0 +1,184 @@
+use crate::app::App;
+use base64::Engine;
+use image::{DynamicImage, GenericImageView, RgbImage};
+use ratatui::layout::Rect;
+use std::collections::BTreeMap;
+use std::io::Cursor;
+
+// Deletes every image kitty has placed; sixel pixels go when the cells are redrawn
+pub const KITTY_DELETE_ALL: &str = "\x1b_Ga=d\x1b\\";
+// Kitty takes the image data in chunks of at most this many base64 bytes
+const KITTY_CHUNK: usize = 4096;
+// Cell size in pixels when the terminal doesn't report its own
+const DEFAULT_CELL: (u32, u32) = (8, 16);
+
+// How image previews are drawn
+#[derive(Clone, Copy, PartialEq)]
+pub enum Graphics {
+    // ASCII art, which works everywhere
+    Text,
+    Kitty,
+    Sixel,
+}
+
+impl Graphics {
+    pub fn parse(value: &str) -> Result<Option<Self>, String> {
+        match value {
+            "auto" => Ok(None),
+            "none" | "text" => Ok(Some(Graphics::Text)),
+            "kitty" => Ok(Some(Graphics::Kitty)),
+            "sixel" => Ok(Some(Graphics::Sixel)),
+            _ => Err(format!("expected auto, kitty, sixel or none, got {:?}", value)),
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Graphics::Text => "none",
+            Graphics::Kitty => "kitty",
+            Graphics::Sixel => "sixel",
+        }
+    }
+}
+
+// From the environment only: the replies to the usual queries (kitty's a=q, the DA1 sixel bit)
+// are swallowed by crossterm's event parser. Inside tmux or screen the escapes would need
+// passthrough, so those get ASCII art unless the graphics setting says otherwise.
+pub fn detect() -> Graphics {
+    let env = |k: &str| std::env::var(k).unwrap_or_default();
+    let term = env("TERM");
+    if std::env::var_os("TMUX").is_some() || term.starts_with("screen") || term.starts_with("tmux") {
+        return Graphics::Text;
+    }
+    if std::env::var_os("KITTY_WINDOW_ID").is_some() || term == "xterm-kitty" || matches!(env("TERM_PROGRAM").as_str(), "WezTerm" | "ghostty") {
+        return Graphics::Kitty;
+    }
+    if term.starts_with("foot") || term.contains("sixel") || term.starts_with("mlterm") || term == "contour"
+        || env("TERM_PROGRAM") == "iTerm.app"
+        || std::env::var_os("WT_SESSION").is_some()
+        || std::env::var_os("KONSOLE_VERSION").is_some()
+    {
+        return Graphics::Sixel;
+    }
+    Graphics::Text
+}
+
+// An image preview: the decoded image for a graphics protocol, and ASCII art for the rest
+pub struct ImagePreview {
+    pub aa: Vec<String>,
+    image: DynamicImage,
+    // Where the image was last sent; it stays on screen until the popup closes or moves
+    sent_to: Option<Rect>,
+}
+
+impl ImagePreview {
+    pub fn new(aa: Vec<String>, image: DynamicImage) -> Self {
+        Self { aa, image, sent_to: None }
+    }
+}
+
+fn cell_size() -> (u32, u32) {
+    match crossterm::terminal::window_size() {
+        Ok(size) if size.width > 0 && size.height > 0 && size.columns > 0 && size.rows > 0 => {
+            (size.width as u32 / size.columns as u32, size.height as u32 / size.rows as u32)
+        }
+        _ => DEFAULT_CELL,
+    }
+}
+
+// The largest size in cells with the image's aspect ratio that fits in `area`
+fn fit(image: &DynamicImage, area: Rect, cell: (u32, u32)) -> (u32, u32) {
+    let (w, h) = image.dimensions();
+    let (area_w, area_h) = (area.width as u32 * cell.0, area.height as u32 * cell.1);
+    let scale = (area_w as f64 / w as f64).min(area_h as f64 / h as f64);
+    let (px_w, px_h) = ((w as f64 * scale) as u32, (h as f64 * scale) as u32);
+    ((px_w / cell.0).max(1), (px_h / cell.1).max(1))
+}
+
+fn kitty(image: &DynamicImage, cols: u32, rows: u32) -> Option<String> {
+    let mut png = Vec::new();
+    image.write_to(&mut Cursor::new(&mut png), image::ImageOutputFormat::Png).ok()?;
+    let data = base64::engine::general_purpose::STANDARD.encode(png);
+    let chunks: Vec<&[u8]> = data.as_bytes().chunks(KITTY_CHUNK).collect();
+    let mut out = String::new();
+    for (i, chunk) in chunks.iter().enumerate() {
+        let more = if i + 1 < chunks.len() { 1 } else { 0 };
+        // Kitty scales the PNG to c x r cells itself; C=1 leaves the cursor where it was
+        let keys = match i {
+            0 => format!("a=T,f=100,c={},r={},C=1,q=2,m={}", cols, rows, more),
+            _ => format!("m={}", more),
+        };
+        out.push_str(&format!("\x1b_G{};{}\x1b\\", keys, std::str::from_utf8(chunk).ok()?));
+    }
+    Some(out)
+}
+
+// Colors are mapped onto a 6x6x6 cube, which needs no palette search and is plenty for a preview
+fn sixel(image: &RgbImage) -> String {
+    let (w, h) = image.dimensions();
+    let level = |v: u8| (v as usize * 5 + 127) / 255;
+    let mut out = format!("\x1bPq\"1;1;{};{}", w, h);
+    for i in 0..216 {
+        out.push_str(&format!("#{};2;{};{};{}", i, i / 36 * 20, i / 6 % 6 * 20, i % 6 * 20));
+    }
+    for top in (0..h).step_by(6) {
+        // For each color in this band of six rows, which of the six pixels of each column have it
+        let mut bands: BTreeMap<usize, Vec<u8>> = BTreeMap::new();
+        for x in 0..w {
+            for dy in 0..6.min(h - top) {
+                let p = image.get_pixel(x, top + dy);
+                let color = level(p[0]) * 36 + level(p[1]) * 6 + level(p[2]);
+                bands.entry(color).or_insert_with(|| vec![0; w as usize])[x as usize] |= 1 << dy;
+            }
+        }
+        for (n, (color, bits)) in bands.iter().enumerate() {
+            // `$` goes back to the start of the band for the next color
+            if n > 0 {
+                out.push('$');
+            }
+            out.push_str(&format!("#{}", color));
+            let mut x = 0;
+            while x < bits.len() {
+                let run = bits[x..].iter().take_while(|&&b| b == bits[x]).count();
+                let c = (63 + bits[x]) as char;
+                match run {
+                    1..=3 => (0..run).for_each(|_| out.push(c)),
+                    _ => out.push_str(&format!("!{}{}", run, c)),
+                }
+                x += run;
+            }
+        }
+        out.push('-');
+    }
+    out.push_str("\x1b\\");
+    out
+}
+
+impl App {
+    pub fn graphics(&self) -> Graphics {
+        if self.settings.ascii {
+            return Graphics::Text;
+        }
+        self.settings.graphics.unwrap_or(self.terminal_graphics)
+    }
+
+    // The escape that draws the preview image at the top left of `area`, when it isn't there
+    // already. Sent by the event loop after each draw, the way OSC 8 links are.
+    pub fn image_escape(&mut self, area: Rect) -> Option<String> {
+        let graphics = self.graphics();
+        let preview = self.image_preview.as_mut()?;
+        if graphics == Graphics::Text || preview.sent_to == Some(area) || area.width == 0 || area.height == 0 {
+            return None;
+        }
+        preview.sent_to = Some(area);
+        let cell = cell_size();
+        let (cols, rows) = fit(&preview.image, area, cell);
+        // Never more pixels than the cells show, which also keeps big photos quick to send
+        let resized = preview.image.resize(cols * cell.0, rows * cell.1, image::imageops::FilterType::Triangle);
+        match graphics {
+            Graphics::Kitty => kitty(&resized, cols, rows),
+            Graphics::Sixel => Some(sixel(&resized.to_rgb8())),
+            Graphics::Text => None,
+        }
+    }
+}
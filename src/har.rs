@@ -0,0 +1,159 @@
+use crate::timing::{FetchTiming, Probe};
+use serde_json::{json, Value};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+// The request half of an exchange, captured before the client sends it
+pub struct Outgoing {
+    pub method: String,
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+    pub body_size: usize,
+    pub started: Instant,
+    pub started_at: SystemTime,
+    // Collects the connection phases while the request is sent
+    pub probe: Probe,
+}
+
+impl Outgoing {
+    pub fn capture(req: &reqwest::Request) -> Self {
+        Self {
+            method: req.method().to_string(),
+            url: req.url().to_string(),
+            headers: header_pairs(req.headers()),
+            body_size: req.body().and_then(|b| b.as_bytes()).map_or(0, |b| b.len()),
+            started: Instant::now(),
+            started_at: SystemTime::now(),
+            probe: Probe::default(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct Incoming {
+    pub status: u16,
+    pub status_text: String,
+    pub http_version: String,
+    pub headers: Vec<(String, String)>,
+    pub mime_type: String,
+    // Arrived over TLS (and so with a certificate that passed verification)
+    pub tls: bool,
+}
+
+impl Incoming {
+    pub fn capture(res: &reqwest::Response) -> Self {
+        Self {
+            status: res.status().as_u16(),
+            status_text: res.status().canonical_reason().unwrap_or("").to_string(),
+            http_version: format!("{:?}", res.version()),
+            headers: header_pairs(res.headers()),
+            mime_type: res.headers().get(reqwest::header::CONTENT_TYPE).and_then(|v| v.to_str().ok()).unwrap_or("").to_string(),
+            tls: res.extensions().get::<reqwest::tls::TlsInfo>().is_some(),
+        }
+    }
+}
+
+pub struct Exchange {
+    pub request: Outgoing,
+    pub response: Incoming,
+    pub timing: FetchTiming,
+    pub from_cache: bool,
+}
+
+impl Exchange {
+    // `durations` is (ttfb, body download, render)
+    pub fn new(request: Outgoing, response: Incoming, durations: (Duration, Duration, Duration), bytes: usize, from_cache: bool) -> Self {
+        let phases = request.probe.take();
+        let (ttfb, body, render) = durations;
+        let timing = FetchTiming { url: request.url.clone(), phases, ttfb, body, render, bytes };
+        Self { request, response, timing, from_cache }
+    }
+}
+
+fn header_pairs(headers: &reqwest::header::HeaderMap) -> Vec<(String, String)> {
+    headers.iter().map(|(k, v)| (k.to_string(), String::from_utf8_lossy(v.as_bytes()).into_owned())).collect()
+}
+
+fn name_values(pairs: &[(String, String)]) -> Value {
+    pairs.iter().map(|(name, value)| json!({ "name": name, "value": value })).collect()
+}
+
+// RFC 3339 UTC timestamp with millisecond precision, as HAR's startedDateTime expects
+pub fn iso8601(t: SystemTime) -> String {
+    let d = t.duration_since(UNIX_EPOCH).unwrap_or_default();
+    let secs = d.as_secs();
+    let days = (secs / 86_400) as i64;
+    let rem = secs % 86_400;
+    // Howard Hinnant's civil_from_days
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!("{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}Z", year, month, day, rem / 3600, rem % 3600 / 60, rem % 60, d.subsec_millis())
+}
+
+fn entry(e: &Exchange) -> Value {
+    let ms = |d: Duration| d.as_secs_f64() * 1000.0;
+    let t = &e.timing;
+    let dns = t.phases.dns.map_or(-1.0, ms);
+    let connect = t.phases.connect.map_or(-1.0, |c| ms(c.saturating_sub(t.phases.dns.unwrap_or_default())));
+    let query: Vec<(String, String)> = url::Url::parse(&e.request.url)
+        .map(|u| u.query_pairs().map(|(k, v)| (k.into_owned(), v.into_owned())).collect())
+        .unwrap_or_default();
+    let location = e.response.headers.iter().find(|(k, _)| k == "location").map(|(_, v)| v.as_str()).unwrap_or("");
+    // TTFB covers connection setup too, so the connect phases are carved out of `wait`
+    let wait = (ms(t.ttfb) - dns.max(0.0) - connect.max(0.0)).max(0.0);
+    json!({
+        "startedDateTime": iso8601(e.request.started_at),
+        "time": ms(t.ttfb + t.body),
+        "request": {
+            "method": e.request.method,
+            "url": e.request.url,
+            "httpVersion": e.response.http_version,
+            "cookies": [],
+            "headers": name_values(&e.request.headers),
+            "queryString": name_values(&query),
+            "headersSize": -1,
+            "bodySize": e.request.body_size,
+        },
+        "response": {
+            "status": e.response.status,
+            "statusText": e.response.status_text,
+            "httpVersion": e.response.http_version,
+            "cookies": [],
+            "headers": name_values(&e.response.headers),
+            "content": { "size": t.bytes, "mimeType": e.response.mime_type },
+            "redirectURL": location,
+            "headersSize": -1,
+            "bodySize": t.bytes,
+        },
+        "cache": {},
+        "timings": {
+            "blocked": -1,
+            "dns": dns,
+            "connect": connect,
+            "ssl": t.phases.tls.map_or(-1.0, ms),
+            "send": 0,
+            "wait": wait,
+            "receive": ms(t.body),
+        },
+    })
+}
+
+// Background fetches are logged as their headers come in and page loads once rendered, so the
+// session log isn't in start order; HAR viewers expect it to be
+pub fn to_har(exchanges: &[Exchange]) -> Value {
+    let mut exchanges: Vec<&Exchange> = exchanges.iter().collect();
+    exchanges.sort_by_key(|e| e.request.started);
+    json!({
+        "log": {
+            "version": "1.2",
+            "creator": { "name": "Voyager", "version": env!("CARGO_PKG_VERSION") },
+            "entries": exchanges.into_iter().map(entry).collect::<Vec<_>>(),
+        }
+    })
+}
@@ -0,0 +1,68 @@
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use std::collections::HashMap;
+use url::Url;
+
+// Extra request headers from voyager.toml:
+//
+//   [headers]                    # sent to every site
+//   "Accept-Language" = "en"
+//
+//   [site_headers."example.jp"]  # example.jp and its subdomains, over the ones above
+//   "Accept-Language" = "ja"
+//   "User-Agent" = "Mozilla/5.0"
+//
+// Headers a request already has, like those typed into the request composer, are left alone.
+#[derive(Default)]
+pub struct HeaderRules {
+    global: HeaderMap,
+    // Longest domain first, so the most specific rule for a host is applied first
+    sites: Vec<(String, HeaderMap)>,
+}
+
+fn parse_map(entries: HashMap<String, String>, section: &str, problems: &mut Vec<String>) -> HeaderMap {
+    let mut map = HeaderMap::new();
+    for (name, value) in entries {
+        match (HeaderName::from_bytes(name.as_bytes()), HeaderValue::from_str(&value)) {
+            (Ok(name), Ok(value)) => { map.insert(name, value); }
+            (Err(_), _) => problems.push(format!("[{}]: bad header name {:?}", section, name)),
+            (_, Err(_)) => problems.push(format!("[{}]: bad value for {}", section, name)),
+        }
+    }
+    map
+}
+
+pub fn matches(host: &str, domain: &str) -> bool {
+    host == domain || host.strip_suffix(domain).is_some_and(|rest| rest.ends_with('.'))
+}
+
+impl HeaderRules {
+    // Bad entries are skipped and described in `problems`
+    pub fn parse(global: HashMap<String, String>, sites: HashMap<String, HashMap<String, String>>, problems: &mut Vec<String>) -> Self {
+        let global = parse_map(global, "headers", problems);
+        let mut sites: Vec<(String, HeaderMap)> = sites.into_iter().map(|(domain, entries)| {
+            let map = parse_map(entries, &format!("site_headers.{:?}", domain), problems);
+            (domain.trim_start_matches("www.").to_ascii_lowercase(), map)
+        }).collect();
+        sites.sort_by_key(|(domain, _)| std::cmp::Reverse(domain.len()));
+        Self { global, sites }
+    }
+
+    // The domains with [site_headers] of their own
+    pub fn site_domains(&self) -> Vec<String> {
+        self.sites.iter().map(|(domain, _)| domain.clone()).collect()
+    }
+
+    pub fn count(&self) -> usize {
+        self.global.len() + self.sites.iter().map(|(_, map)| map.len()).sum::<usize>()
+    }
+
+    pub fn apply(&self, url: &Url, headers: &mut HeaderMap) {
+        let host = url.host_str().unwrap_or_default();
+        let site = self.sites.iter().filter(|(domain, _)| matches(host, domain)).map(|(_, map)| map);
+        for map in site.chain([&self.global]) {
+            for (name, value) in map {
+                headers.entry(name).or_insert_with(|| value.clone());
+            }
+        }
+    }
+}
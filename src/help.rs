@@ -0,0 +1,245 @@
+use crate::about::escape_html;
+use crate::config::{Config, ACTIONS};
+use crate::i18n::lookup;
+use crate::proxy;
+use crate::types::{Dictionary, HttpVersion, Settings, Translator};
+
+const KEY_BINDINGS_NOTE: &str = "Normal-mode keys can be rebound in voyager.toml in the config directory: under [keys], a key = an action below, a :command, or none. Under [aliases], name = a command to run for :name.";
+
+const FORCE_NOTE: &str = "A command that would ask first, to overwrite a file or run an external program, runs without asking when it ends in !, e.g. :save! to overwrite.";
+
+pub const KEYS: &[(&str, &str, &str)] = &[
+    ("Normal", ":", "Enter a command"),
+    ("Normal", "?", "Show this help"),
+    ("Normal", "Ctrl-P", "Open the command palette"),
+    ("Normal", "L", "Fuzzy-find a link on the page and follow it"),
+    ("Normal", "o / O", "Type an address or a search, or edit the current address; Tab completes from bookmarks and history"),
+    ("Normal", "j / k, wheel", "Scroll down / up (see the scroll, wheel and smooth settings)"),
+    ("Normal", "Ctrl-D / Ctrl-U", "Scroll down / up half a screen"),
+    ("Normal", "Ctrl-F / Ctrl-B, PageDown / PageUp", "Scroll down / up a screen, keeping two lines in view"),
+    ("Normal", "gg / G, Home / End", "Go to the top / bottom of the page"),
+    ("Normal", "l / Tab, h", "Select the next / previous link"),
+    ("Normal", "Enter", "Follow the selected link (images open a preview, audio and video offer the player)"),
+    ("Normal", "Enter on a field", "Type into a text box, toggle a checkbox or radio button, choose an option, or submit"),
+    ("Normal", "gt / gT", "Switch to the next / previous tab"),
+    ("Normal", "]p / [p", "Go to the next / previous page of a paginated document"),
+    ("Normal", "+ / -", "Zoom in / out: a narrower, centered or wider text column, remembered per site"),
+    ("Normal", "y / Y", "Yank the selected link's URL / the page URL"),
+    ("Normal", "p", "Open the URL (or search the text) held in a register"),
+    ("Normal", "\"x", "Use register x for the next y, Y or p, e.g. \"ay"),
+    ("Normal", "f", "Label the links on screen; type a label to follow it"),
+    ("Normal", "B / F", "Go back / forward in history (Backspace also goes back)"),
+    ("Normal", "t", "Show the selected link's title, or what the abbreviations on screen stand for"),
+    ("Normal", "v", "Enter caret mode"),
+    ("Normal", "K", "Look up a word (prefills :define)"),
+    ("Normal", "\\", "Toggle the page source view"),
+    ("Normal", "/", "Search the page; matches are highlighted (Esc clears them)"),
+    ("Normal", "n / N", "Next / previous search match"),
+    ("Normal", "Ctrl-N", "Toggle the network log pane"),
+    ("Normal", "R / X", "Follow / cancel a pending meta refresh"),
+    ("Normal", "Esc / Ctrl-C", "Stop a page load in progress"),
+    ("Normal", "Esc", "Return from an alternate view to the page"),
+    ("Caret", "h j k l, w b, 0 $", "Move the caret"),
+    ("Caret", "v", "Start or clear a selection"),
+    ("Caret", "y", "Yank the selection (\"xy into register x)"),
+    ("Caret", "K", "Define the word under the caret"),
+    ("Caret", "Enter", "Follow the link under the caret"),
+    ("Caret", "Esc", "Leave caret mode"),
+    ("Insert", "Enter", "Submit the form (a new line in a text area)"),
+    ("Insert", "Tab", "Keep the text and move to the next field"),
+    ("Insert", "Esc", "Keep the text and leave insert mode"),
+    ("Insert", "Ctrl-U", "Clear the field"),
+    ("Hint", "a-z, Backspace, Esc", "Type a label, take back a letter, cancel"),
+    ("URL", "Tab / Shift-Tab", "Complete from bookmarks and history, next / previous"),
+    ("URL", "Ctrl-A / Ctrl-E, Ctrl-B / Ctrl-F, Alt-B / Alt-F", "Move to the start / end, by a character, by a word"),
+    ("URL", "Ctrl-W, Ctrl-U / Ctrl-K, Ctrl-Y", "Cut the word before the cursor, cut to the start / end, paste the cut"),
+    ("URL", "Ctrl-R", "Search history by frecency, starting from the typed text"),
+    ("URL", "Enter, Esc", "Open the address (or search the text), cancel"),
+    ("Inspector", "j / k, l / h", "Move, expand / collapse nodes"),
+    ("Inspector", "Enter", "Jump to the node's text in the page"),
+    ("Request", "Ctrl-S", "Send the composed request"),
+    ("Popup", "j / k, Enter, Esc", "Scroll or select, run the entry, close"),
+    ("Picker", "type, Up / Down, Enter, Esc", "Filter, move, choose, cancel"),
+    ("Dialog", "y / n, Enter, Esc", "Answer, submit or choose, cancel"),
+];
+
+// (prefix, key, description): what can follow a prefix key, shown while it is pending
+pub const PREFIX_KEYS: &[(char, &str, &str)] = &[
+    (']', "p", "Next page of a paginated document"),
+    ('[', "p", "Previous page of a paginated document"),
+    ('g', "t", "Next tab"),
+    ('g', "T", "Previous tab"),
+    ('g', "g", "Top of the page"),
+    ('g', "s", "Toggle the page source view"),
+    ('"', "a-z 0-9 + \"", "Use that register for the next y, Y or p (+ is the clipboard)"),
+];
+
+// (name, arguments, description), one entry per command so the palette can run each directly
+pub const COMMANDS: &[(&str, &str, &str)] = &[
+    ("url", "<address>", "Open an address or a local path (:url! skips the short link and lookalike domain checks)"),
+    ("open-all", "<regex>", "Open every matching link in a background tab"),
+    ("tabnew", "[address]", "Open an address (default: this page) in a new tab (also :tabe)"),
+    ("tab", "<n>", "Switch to tab n"),
+    ("tabnext", "", "Switch to the next tab"),
+    ("tabprev", "", "Switch to the previous tab"),
+    ("tabclose", "", "Close the current tab"),
+    ("back", "", "Go back in history (also :b)"),
+    ("forward", "", "Go forward in history (also :f)"),
+    ("history", "[words]", "Show every page visited, across sessions, optionally only those matching (about:history)"),
+    ("jumps", "", "Pick an entry from the back/forward history"),
+    ("tree", "", "Pick an entry from the full history tree"),
+    ("restore-session", "", "Reopen the tabs and history left by a run that didn't exit cleanly (offered at startup)"),
+    ("more", "", "Load the next chunk of a large page"),
+    ("next", "", "Follow the rel=next page"),
+    ("prev", "", "Follow the rel=prev page"),
+    ("search", "<query>", "Search with the configured engine"),
+    ("search-site", "<query>", "Search within the current site, through its own search form when the page has one"),
+    ("meta", "<query>", "Query several search engines at once"),
+    ("grep", "<query>", "Search pages visited so far"),
+    ("bookmark", "[-t tag,tag] [folder/sub]", "Bookmark the page with tags, in a folder; again to move it or add tags"),
+    ("unbookmark", "", "Remove the page's bookmark"),
+    ("bm", "add [args] | list | del <n> | watch", "Bookmark the page (as :bookmark), list the bookmarks, delete bookmark n from the list, or toggle checking this bookmark for changes"),
+    ("bookmarks", "[#tag] [folder]", "Show the bookmarks by folder, optionally only one tag or folder (about:bookmarks)"),
+    ("read-later", "", "Save the page to the reading list"),
+    ("read-done", "", "Remove the page from the reading list"),
+    ("read-watch", "", "Toggle checking the saved page for changes (see the monitor setting)"),
+    ("reading-list", "", "Show the reading list (readable offline and in --sandbox)"),
+    ("sitemap", "", "Show the site's sitemap (found through robots.txt) as links"),
+    ("robots", "", "Show the site's robots.txt rules grouped by user agent"),
+    ("frames", "", "List the page's frames"),
+    ("frame", "<n>", "Open frame n"),
+    ("info", "", "Show page metadata and the negotiated HTTP version"),
+    ("cert", "", "Show the certificate chain the site presented"),
+    ("outline", "", "Jump to a heading"),
+    ("source", "", "Toggle the page source view"),
+    ("reader", "", "Toggle reader mode: just the article text, without menus, sidebars and footers (:set reader)"),
+    ("reload", "", "Fetch the page again, bypassing the caches, and keep the reading position"),
+    ("reload-config", "", "Read voyager.toml again: key bindings and command aliases"),
+    ("noscript", "", "Show the page's <noscript> content"),
+    ("frontend", "", "Open the page on its configured alternative front end"),
+    ("wayback", "", "Open the Wayback Machine snapshot of the page"),
+    ("diff", "", "Show what changed since the last visit to this page"),
+    ("watch", "<secs>|off", "Re-fetch the page on an interval and flag changes"),
+    ("inspect", "", "Open the DOM inspector"),
+    ("cookies", "[domain] | clear <domain|all>", "Show the stored cookies by site (about:cookies), or remove a site's or all of them"),
+    ("submit", "[n]", "Submit form n, or the form of the selected field"),
+    ("resources", "", "List the stylesheets, scripts, images and preloads the page declares, with sizes, to open, inspect or save"),
+    ("select", "<css>", "Show only elements matching a CSS selector"),
+    ("xpath", "<expr>", "Show the results of an XPath expression"),
+    ("table-export", "<n> <path>", "Write a table as CSV or TSV"),
+    ("save", "[html|md|text] <path>", "Write the page to a file: its HTML, Markdown, or the displayed text (by extension unless given)"),
+    ("download", "<path>", "Save a binary response that was not displayed"),
+    ("mirror", "<depth> [html] [dir]", "Save the site's pages up to depth links away as text (or HTML) with local links, into dir (default ./mirror)"),
+    ("play", "<url>", "Play an audio or video URL with the configured player"),
+    ("save-url", "<url> [path]", "Download a URL in the background, e.g. a media or resource link (also :save-media)"),
+    ("download-all", "<regex>", "Download every link whose text or URL matches into the working directory"),
+    ("continue", "", "Load a response held back by the size limit"),
+    ("stream-to", "<path>", "Stream a held-back response straight to a file"),
+    ("abort", "", "Drop a held-back response without downloading it"),
+    ("yank-md", "", "Copy the page (or caret selection) as Markdown"),
+    ("yank-links", "[regex] [> path]", "Copy the page's link URLs, one per line, or write them to a file (the > needs a space before and after); the regex filters on link text or URL"),
+    ("translate", "[lang]", "Translate the page or the caret selection, or toggle back to the original"),
+    ("define", "<word>", "Look up a word"),
+    ("fill", "<profile>", "Fill the page's form fields from an autofill profile (autofill.json in the config directory)"),
+    ("request", "[METHOD URL]", "Compose and send a custom request"),
+    ("head", "[url]", "Show the status and headers of a HEAD request (default: this page)"),
+    ("options", "[url]", "Show the status, headers and allowed methods from an OPTIONS request"),
+    ("timing", "", "Show the timing breakdown of the current page's fetch"),
+    ("network", "", "Toggle the network log pane"),
+    ("har-export", "<path>", "Write the session's requests as a HAR file"),
+    ("messages", "", "Review past status messages"),
+    ("registers", "", "Show the contents of the yank registers"),
+    ("stats", "", "Show browsing statistics: visits, top sites, pages per day, reading time (about:stats)"),
+    ("set", "<key>=<value>", "Change a setting (see below)"),
+    ("proxy", "[auto|none|tor|<url>]", "Fetch through an HTTP or SOCKS5 proxy, e.g. socks5h://127.0.0.1:9050; without an argument, show the one in use"),
+    ("help", "", "Show this help"),
+    ("q", "", "Quit"),
+];
+
+fn setting_values(s: &Settings) -> Vec<(&'static str, String, &'static str)> {
+    let on = |b: bool| if b { "on" } else { "off" }.to_string();
+    let translator = match s.translator {
+        Translator::LibreTranslate { ref url, .. } => format!("libretranslate:{}", url),
+        Translator::DeepL { .. } => "deepl".to_string(),
+        Translator::Command(ref cmd) => format!("cmd:{}", cmd),
+    };
+    let dictionary = match s.dictionary {
+        Dictionary::Dict(ref host) => format!("dict:{}", host),
+        Dictionary::Web(ref url) => format!("web:{}", url),
+        Dictionary::Command(ref cmd) => format!("cmd:{}", cmd),
+    };
+    let http = match s.http_version {
+        HttpVersion::Auto => "auto",
+        HttpVersion::Http1 => "1.1",
+        HttpVersion::Http2 => "2",
+        HttpVersion::Http3 => "3",
+    };
+    let frontends = s.frontends.iter().map(|(site, alt)| format!("{}={}", site, alt)).collect::<Vec<_>>().join(", ");
+    vec![
+        ("locale", s.locale.name().to_string(), "Language of messages and help: en or ja (VOYAGER_LOCALE, else LC_ALL, LC_MESSAGES or LANG)"),
+        ("theme", s.theme.clone(), "default, or <name> for themes/<name>.yaml in the config directory: a base16 scheme or role: color lines (VOYAGER_THEME)"),
+        ("colors", s.color_depth.map_or("auto", |d| d.name()).to_string(), "Colors the terminal shows: auto (COLORTERM, terminfo), truecolor, 256, 16 or none; theme colors are mapped to fit (VOYAGER_COLORS)"),
+        ("background", s.background.map_or("auto", |b| b.name()).to_string(), "Terminal background the colors suit: auto (asked at startup), dark or light (VOYAGER_BACKGROUND)"),
+        ("linknums", on(s.link_numbers), "Show [N] labels before links"),
+        ("linkstyle", if s.footnotes { "footnote" } else { "inline" }.to_string(), "inline: [N] before each link; footnote: a superscript number after it and the URLs listed at the end"),
+        ("showurls", on(s.show_urls), "Show each link's URL, dimmed, after its text"),
+        ("images", on(s.images), "Show image placeholders"),
+        ("graphics", s.graphics.map_or("auto", |g| g.name()).to_string(), "How image previews are drawn: auto (from TERM and the terminal's own variables), kitty, sixel or none for ASCII art (VOYAGER_GRAPHICS)"),
+        ("reader", on(s.reader), "Reader mode: lay out only the main article of each page, without menus and footers (VOYAGER_READER)"),
+        ("ascii", on(s.ascii), "Low-bandwidth mode: ASCII borders and glyphs, no images or OSC 8 hyperlinks, a short status line (--ascii, VOYAGER_ASCII)"),
+        ("hyperlinks", on(s.hyperlinks), "Emit OSC 8 terminal hyperlinks"),
+        ("scroll", s.scroll_step.to_string(), "Lines j / k scroll per press (VOYAGER_SCROLL)"),
+        ("wheel", s.wheel_step.to_string(), "Lines the mouse wheel scrolls per notch (VOYAGER_WHEEL)"),
+        ("smooth", on(s.smooth_scroll), "Animate scrolling over a few frames instead of jumping (VOYAGER_SMOOTH)"),
+        ("smartcase", on(s.smartcase), "Make / searches case-sensitive when the pattern has a capital letter"),
+        ("autopage", on(s.auto_paginate), "Append the next page on reaching the end"),
+        ("pagecache", s.page_cache.to_string(), "Pages kept in memory so back / forward show them without fetching, 0 for none"),
+        ("chunk", s.chunk_kb.to_string(), "Load bodies larger than this many KB in chunks, 0 for never"),
+        ("sizelimit", s.size_limit_mb.to_string(), "Ask before downloading bodies larger than this many MB, 0 for never"),
+        ("redirects", s.redirects.to_string(), "Redirects to follow per load, 0 for none; a redirect past this is shown as a page with a link on (VOYAGER_REDIRECTS)"),
+        ("proxy", proxy::shown(&s.proxy), "auto (from HTTPS_PROXY / ALL_PROXY), none, tor, or a proxy URL: http://, socks5://, or socks5h:// to resolve names through the proxy (VOYAGER_PROXY)"),
+        ("useragent", s.user_agent.clone(), "User-Agent sent to every site, or default; voyager.toml can set it and other headers per site (VOYAGER_USER_AGENT)"),
+        ("monitor", s.monitor_mins.to_string(), "Minutes between checks of watched reading list pages and bookmarks, 0 for off"),
+        ("expand", on(s.expand_short), "Show where short links (bit.ly, t.co, ...) lead before following them"),
+        ("deamp", on(s.deamp), "Rewrite AMP pages to the publisher's canonical URL"),
+        ("deamp-skip", s.deamp_skip.join(","), "Comma-separated sites whose AMP pages are kept"),
+        ("frontend", frontends, "<site>=<host>: alternative front end offered for JavaScript-only pages"),
+        ("margin", format!("{},{}", s.margin_left, s.margin_right), "Blank columns left and right of the text: <n> or <left>,<right> (VOYAGER_MARGIN)"),
+        ("spacing", s.paragraph_spacing.map_or("auto".to_string(), |n| n.to_string()), "Blank lines between paragraphs, or auto to keep the page's (VOYAGER_SPACING)"),
+        ("indent", s.indent.to_string(), "Indent the first line of each paragraph by this many columns (VOYAGER_INDENT)"),
+        ("engine", s.search_engine.clone(), "Search URL template, {} is the query"),
+        ("lang", s.translate_lang.clone(), "Target language for :translate"),
+        ("translator", translator, "libretranslate:<url>, deepl:<key> or cmd:<command>"),
+        ("dictionary", dictionary, "dict:<host>, web:<url template> or cmd:<command>"),
+        ("player", s.player.clone(), "Command that plays audio and video links, given the URL, e.g. mpv (VOYAGER_PLAYER)"),
+        ("http", http.to_string(), "HTTP version: auto, 1.1, 2 or 3"),
+        ("allow-write", on(s.allow_write), "Write files without asking first"),
+        ("allow-exec", on(s.allow_exec), "Run external translator/dictionary commands without asking"),
+    ]
+}
+
+// Keys as they are now, with voyager.toml's [keys] applied
+pub fn help_page(settings: &Settings, config: &Config) -> String {
+    let mut html = format!("<title>{}</title><h1>{}</h1><h2>{}</h2><table>", lookup("Help"), lookup("Voyager help"), lookup("Keys"));
+    for (mode, key, desc) in KEYS {
+        html.push_str(&format!("<tr><td>{}</td><td><b>{}</b></td><td>{}</td></tr>", lookup(mode), escape_html(key), escape_html(lookup(desc))));
+    }
+    html.push_str(&format!("</table><h2>{}</h2><p>{}</p><table>", lookup("Key bindings"), escape_html(lookup(KEY_BINDINGS_NOTE))));
+    for (action, default) in ACTIONS {
+        let keys = match config.keys_for(default) {
+            keys if keys.is_empty() => lookup("none").to_string(),
+            keys => keys.join(", "),
+        };
+        html.push_str(&format!("<tr><td>{}</td><td><b>{}</b></td></tr>", action, escape_html(&keys)));
+    }
+    html.push_str(&format!("</table><h2>{}</h2><p>{}</p><table>", lookup("Commands"), escape_html(lookup(FORCE_NOTE))));
+    for (name, args, desc) in COMMANDS {
+        html.push_str(&format!("<tr><td><b>:{} {}</b></td><td>{}</td></tr>", name, escape_html(args), escape_html(lookup(desc))));
+    }
+    html.push_str(&format!("</table><h2>{}</h2><table>", lookup("Settings")));
+    for (key, value, desc) in setting_values(settings) {
+        html.push_str(&format!("<tr><td><b>{}</b></td><td>{}</td><td>{}</td></tr>", key, escape_html(&value), escape_html(lookup(desc))));
+    }
+    html.push_str("</table>");
+    html
+}
@@ -0,0 +1,115 @@
+use ratatui::{style::{Color, Modifier, Style}, text::{Line, Span}};
+
+#[derive(Clone, Copy, PartialEq)]
+enum State {
+    Text,
+    TagName,
+    InTag,
+    AttrValue(char),
+    Comment,
+}
+
+fn style_for(state: State) -> Style {
+    match state {
+        State::Text => Style::default(),
+        State::TagName => Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD),
+        State::InTag => Style::default().fg(Color::Yellow),
+        State::AttrValue(_) => Style::default().fg(Color::Green),
+        State::Comment => Style::default().fg(Color::DarkGray).add_modifier(Modifier::ITALIC),
+    }
+}
+
+struct LineBuilder {
+    spans: Vec<Span<'static>>,
+    buf: String,
+    style: Style,
+}
+
+impl LineBuilder {
+    fn push(&mut self, c: char, style: Style) {
+        if style != self.style && !self.buf.is_empty() {
+            self.spans.push(Span::styled(std::mem::take(&mut self.buf), self.style));
+        }
+        self.style = style;
+        self.buf.push(c);
+    }
+
+    fn push_str(&mut self, s: &str, style: Style) {
+        for c in s.chars() {
+            self.push(c, style);
+        }
+    }
+
+    fn finish(&mut self) -> Vec<Span<'static>> {
+        if !self.buf.is_empty() {
+            self.spans.push(Span::styled(std::mem::take(&mut self.buf), self.style));
+        }
+        std::mem::take(&mut self.spans)
+    }
+}
+
+// Line-numbered HTML source with tags, attributes, strings and comments coloured.
+// The tokenizer state carries across lines so multi-line tags and comments stay coloured.
+pub fn highlight_html(source: &str) -> Vec<Line<'static>> {
+    let total = source.lines().count().max(1);
+    let gutter = total.to_string().len();
+    let number_style = Style::default().fg(Color::DarkGray);
+    let bracket = Style::default().fg(Color::Cyan);
+    let mut state = State::Text;
+    let mut lines = Vec::new();
+
+    for (n, raw) in source.lines().enumerate() {
+        let mut b = LineBuilder { spans: Vec::new(), buf: String::new(), style: Style::default() };
+        b.push_str(&format!("{:>width$} ", n + 1, width = gutter), number_style);
+        let chars: Vec<char> = raw.replace('\t', "    ").chars().collect();
+        let mut i = 0;
+        while i < chars.len() {
+            let c = chars[i];
+            let rest: String = chars[i..chars.len().min(i + 4)].iter().collect();
+            match state {
+                State::Text if rest.starts_with("<!--") => {
+                    state = State::Comment;
+                    b.push_str("<!--", style_for(state));
+                    i += 4;
+                    continue;
+                }
+                State::Text if c == '<' => {
+                    b.push(c, bracket);
+                    state = State::TagName;
+                }
+                State::Text => b.push(c, style_for(state)),
+                State::Comment if rest.starts_with("-->") => {
+                    b.push_str("-->", style_for(state));
+                    state = State::Text;
+                    i += 3;
+                    continue;
+                }
+                State::Comment => b.push(c, style_for(state)),
+                State::TagName | State::InTag if c == '>' => {
+                    b.push(c, bracket);
+                    state = State::Text;
+                }
+                State::TagName if c.is_whitespace() => {
+                    state = State::InTag;
+                    b.push(c, style_for(state));
+                }
+                State::TagName => b.push(c, style_for(state)),
+                State::InTag if c == '"' || c == '\'' => {
+                    state = State::AttrValue(c);
+                    b.push(c, style_for(state));
+                }
+                State::InTag if c == '=' || c == '/' => b.push(c, bracket),
+                State::InTag => b.push(c, style_for(state)),
+                State::AttrValue(q) => {
+                    b.push(c, style_for(state));
+                    if c == q {
+                        state = State::InTag;
+                    }
+                }
+            }
+            i += 1;
+        }
+        lines.push(Line::from(b.finish()));
+    }
+    lines
+}
@@ -0,0 +1,100 @@
+use crate::app::App;
+use crate::i18n::tr;
+use crate::types::{is_link_style, Mode, ToastKind};
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{style::{Color, Modifier, Style}, text::Span};
+
+// Home row first, like Vimium; every label on screen has the same length, so typing
+// one never completes another
+const HINT_CHARS: &[char] = &['a', 's', 'd', 'f', 'g', 'h', 'j', 'k', 'l'];
+
+// `f`: a label before each link on screen, followed by typing it
+pub struct Hints {
+    // (link index, label)
+    labels: Vec<(usize, String)>,
+    typed: String,
+}
+
+fn make_labels(n: usize) -> Vec<String> {
+    let k = HINT_CHARS.len();
+    let mut len = 1;
+    while k.pow(len) < n {
+        len += 1;
+    }
+    (0..n).map(|i| {
+        let mut rest = i;
+        let mut label = vec![' '; len as usize];
+        for slot in label.iter_mut().rev() {
+            *slot = HINT_CHARS[rest % k];
+            rest /= k;
+        }
+        label.into_iter().collect()
+    }).collect()
+}
+
+impl App {
+    pub fn start_hints(&mut self) {
+        let (top, bottom) = (self.scroll as usize, self.scroll as usize + self.viewport_height.max(1) as usize);
+        let mut visible = Vec::new();
+        let mut link_idx = 0;
+        for (row, line) in self.content_lines.iter().enumerate().take(bottom) {
+            let links = line.spans.iter().filter(|s| is_link_style(s.style)).count();
+            if row >= top {
+                visible.extend(link_idx..link_idx + links);
+            }
+            link_idx += links;
+        }
+        if visible.is_empty() {
+            self.status = tr!("No links on screen");
+            return;
+        }
+        let labels = make_labels(visible.len());
+        self.hints = Some(Hints { labels: visible.into_iter().zip(labels).collect(), typed: String::new() });
+        self.mode = Mode::Hint;
+    }
+
+    fn end_hints(&mut self) {
+        self.hints = None;
+        self.mode = Mode::Normal;
+    }
+
+    pub async fn handle_hint_key(&mut self, key: KeyEvent) {
+        let Some(hints) = self.hints.as_mut() else { return self.end_hints() };
+        match key.code {
+            KeyCode::Esc => {
+                self.end_hints();
+                self.status.clear();
+            }
+            KeyCode::Backspace => { hints.typed.pop(); }
+            KeyCode::Char(c) => {
+                let typed = format!("{}{}", hints.typed, c.to_ascii_lowercase());
+                let matching: Vec<usize> = hints.labels.iter().filter(|(_, l)| l.starts_with(&typed)).map(|(i, _)| *i).collect();
+                match matching[..] {
+                    [] => self.status = tr!("No link is labelled {}", typed),
+                    [idx] => {
+                        self.end_hints();
+                        self.selected_link_idx = idx;
+                        if let Err(e) = self.follow_link(idx).await {
+                            self.notify(ToastKind::Error, tr!("Fetch failed: {}", e));
+                        }
+                    }
+                    _ => hints.typed = typed,
+                }
+            }
+            _ => {}
+        }
+    }
+
+    // The rest of the label still to type, drawn just before link `idx`
+    pub fn hint_label(&self, idx: usize) -> Option<Span<'static>> {
+        let hints = self.hints.as_ref()?;
+        let (_, label) = hints.labels.iter().find(|(i, l)| *i == idx && l.starts_with(&hints.typed))?;
+        let style = Style::default().fg(Color::Black).bg(Color::Yellow).add_modifier(Modifier::BOLD);
+        Some(Span::styled(label[hints.typed.len()..].to_string(), style))
+    }
+
+    pub fn hint_prompt(&self) -> String {
+        let typed = self.hints.as_ref().map_or("", |h| h.typed.as_str());
+        tr!("Follow: {} (type a label, Backspace undoes, Esc cancels)", typed)
+    }
+}
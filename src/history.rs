@@ -0,0 +1,138 @@
+use crate::types::{HistoryEntry, PagePosition};
+use std::collections::HashMap;
+use std::time::SystemTime;
+
+pub struct HistoryNode {
+    pub entry: HistoryEntry,
+    pub parent: Option<usize>,
+    pub children: Vec<usize>,
+    // The child `forward` returns to: the branch most recently taken from here
+    recent_child: Option<usize>,
+}
+
+// Navigation history as a tree, so going back and then elsewhere keeps the old branch
+pub struct HistoryTree {
+    nodes: Vec<HistoryNode>,
+    pub current: usize,
+}
+
+impl HistoryTree {
+    pub fn new(url: &str) -> Self {
+        let root = HistoryNode { entry: HistoryEntry::new(url), parent: None, children: Vec::new(), recent_child: None };
+        Self { nodes: vec![root], current: 0 }
+    }
+
+    pub fn node(&self, idx: usize) -> &HistoryNode {
+        &self.nodes[idx]
+    }
+
+    // Refreshes the current node with the title and reading position of the page being left
+    pub fn save(&mut self, entry: HistoryEntry) {
+        self.nodes[self.current].entry = entry;
+    }
+
+    pub fn visit(&mut self, url: &str) {
+        // A new tab starts on an empty entry, which its first page takes over
+        if self.nodes.len() == 1 && self.nodes[0].entry.url.is_empty() {
+            self.nodes[0].entry = HistoryEntry::new(url);
+            return;
+        }
+        let idx = self.nodes.len();
+        self.nodes.push(HistoryNode { entry: HistoryEntry::new(url), parent: Some(self.current), children: Vec::new(), recent_child: None });
+        self.nodes[self.current].children.push(idx);
+        self.set_current(idx);
+    }
+
+    pub fn back(&mut self) -> Option<HistoryEntry> {
+        let parent = self.nodes[self.current].parent?;
+        self.current = parent;
+        Some(self.nodes[parent].entry.clone())
+    }
+
+    pub fn forward(&mut self) -> Option<HistoryEntry> {
+        let child = self.nodes[self.current].recent_child?;
+        self.current = child;
+        Some(self.nodes[child].entry.clone())
+    }
+
+    pub fn jump(&mut self, idx: usize) -> Option<HistoryEntry> {
+        self.nodes.get(idx)?;
+        self.set_current(idx);
+        Some(self.nodes[idx].entry.clone())
+    }
+
+    fn set_current(&mut self, idx: usize) {
+        self.current = idx;
+        let mut child = idx;
+        while let Some(parent) = self.nodes[child].parent {
+            self.nodes[parent].recent_child = Some(child);
+            child = parent;
+        }
+    }
+
+    // Root to current, then on along the branch `forward` would follow
+    pub fn path(&self) -> Vec<usize> {
+        let mut path = vec![self.current];
+        while let Some(parent) = self.nodes[path[0]].parent {
+            path.insert(0, parent);
+        }
+        let mut next = self.nodes[self.current].recent_child;
+        while let Some(child) = next {
+            path.push(child);
+            next = self.nodes[child].recent_child;
+        }
+        path
+    }
+
+    // Distinct URLs with their latest title, most frecent first: every visit counts,
+    // weighted by how recent it was
+    pub fn frecent(&self) -> Vec<(String, Option<String>)> {
+        let mut scores: HashMap<&str, (u32, SystemTime, Option<String>)> = HashMap::new();
+        for node in &self.nodes {
+            let e = &node.entry;
+            let age = e.visited_at.elapsed().map(|d| d.as_secs()).unwrap_or(0);
+            let weight = match age {
+                a if a < 3600 => 100,
+                a if a < 86_400 => 70,
+                a if a < 7 * 86_400 => 50,
+                _ => 30,
+            };
+            let slot = scores.entry(e.url.as_str()).or_insert((0, e.visited_at, None));
+            slot.0 += weight;
+            let newer = e.visited_at >= slot.1;
+            if newer {
+                slot.1 = e.visited_at;
+            }
+            if e.title.is_some() && (newer || slot.2.is_none()) {
+                slot.2 = e.title.clone();
+            }
+        }
+        let mut ranked: Vec<_> = scores.into_iter().collect();
+        ranked.sort_by(|a, b| b.1.0.cmp(&a.1.0).then(b.1.1.cmp(&a.1.1)));
+        ranked.into_iter().map(|(url, (_, _, title))| (url.to_string(), title)).collect()
+    }
+
+    // (node, indent) in display order. A node's first child continues at its indent; later
+    // children are branches, drawn indented right after the node they split from.
+    pub fn layout(&self) -> Vec<(usize, usize)> {
+        let mut out = Vec::new();
+        let mut stack = vec![(0, 0)];
+        while let Some((idx, indent)) = stack.pop() {
+            out.push((idx, indent));
+            let children = &self.nodes[idx].children;
+            if let Some(&first) = children.first() {
+                stack.push((first, indent));
+            }
+            for &branch in children.iter().skip(1).rev() {
+                stack.push((branch, indent + 1));
+            }
+        }
+        out
+    }
+}
+
+impl HistoryEntry {
+    pub fn new(url: &str) -> Self {
+        Self { url: url.to_string(), title: None, visited_at: SystemTime::now(), position: PagePosition::default() }
+    }
+}
@@ -0,0 +1,64 @@
+use url::Url;
+
+// Popular names lookalike domains are made to imitate
+const KNOWN_NAMES: &[&str] = &[
+    "google", "apple", "paypal", "amazon", "microsoft", "facebook", "github", "twitter", "wikipedia",
+    "youtube", "netflix", "instagram", "linkedin", "dropbox", "yahoo", "outlook", "icloud", "ebay",
+];
+
+#[derive(Clone, Copy, PartialEq)]
+enum Script {
+    Latin,
+    Cyrillic,
+    Greek,
+    Armenian,
+    Other,
+}
+
+fn script(c: char) -> Option<Script> {
+    match c as u32 {
+        _ if c.is_ascii_digit() || c == '-' => None,
+        0x41..=0x5a | 0x61..=0x7a | 0xc0..=0x24f | 0x1e00..=0x1eff => Some(Script::Latin),
+        0x370..=0x3ff | 0x1f00..=0x1fff => Some(Script::Greek),
+        0x400..=0x52f => Some(Script::Cyrillic),
+        0x530..=0x58f => Some(Script::Armenian),
+        _ => Some(Script::Other),
+    }
+}
+
+// Latin letters that Cyrillic, Greek and Armenian characters are indistinguishable from
+fn latin_lookalike(c: char) -> Option<char> {
+    Some(match c {
+        'а' | 'α' => 'a', 'Ь' | 'в' => 'b', 'с' | 'ϲ' => 'c', 'ԁ' => 'd', 'е' | 'ε' => 'e', 'һ' => 'h',
+        'і' | 'ι' => 'i', 'ј' => 'j', 'κ' | 'к' => 'k', 'ӏ' => 'l', 'ո' => 'n', 'о' | 'ο' | 'օ' => 'o',
+        'р' | 'ρ' => 'p', 'ԛ' => 'q', 'ѕ' => 's', 'τ' | 'т' => 't', 'υ' | 'ս' => 'u', 'ν' | 'ѵ' => 'v',
+        'ԝ' | 'ω' => 'w', 'х' | 'χ' => 'x', 'у' | 'γ' => 'y', 'ᴢ' => 'z',
+        c if c.is_ascii() => c,
+        _ => return None,
+    })
+}
+
+// Why the URL's host looks like a spoofing attempt, if it does: a label mixing Latin with
+// another alphabet, or one spelled entirely in characters that pass for Latin letters
+pub fn check(url: &Url) -> Option<String> {
+    let host = url.host_str()?;
+    if !host.split('.').any(|l| l.starts_with("xn--")) {
+        return None;
+    }
+    let (unicode, _) = idna::domain_to_unicode(host);
+    for label in unicode.split('.') {
+        let mut scripts: Vec<Script> = label.chars().filter_map(script).collect();
+        scripts.sort_by_key(|s| *s as u8);
+        scripts.dedup();
+        let confusable = |s: &Script| matches!(s, Script::Latin | Script::Cyrillic | Script::Greek | Script::Armenian);
+        if scripts.len() > 1 && scripts.iter().all(confusable) {
+            return Some(format!("{} mixes alphabets ({})", unicode, host));
+        }
+        let skeleton: Option<String> = label.chars().map(latin_lookalike).collect();
+        if let Some(skeleton) = skeleton.filter(|s| s != label) {
+            let imitates = KNOWN_NAMES.iter().find(|n| **n == skeleton).map(|n| format!(", imitating {}", n)).unwrap_or_default();
+            return Some(format!("{} reads as \"{}\" but isn't Latin ({}){}", unicode, skeleton, host, imitates));
+        }
+    }
+    None
+}
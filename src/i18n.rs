@@ -0,0 +1,631 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::sync::LazyLock;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+// Messages are looked up by their English text, gettext style: English needs no catalog,
+// and a message missing from another one still shows, in English.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Locale {
+    En,
+    Ja,
+}
+
+impl Locale {
+    pub fn parse(value: &str) -> Result<Self, String> {
+        match value.to_ascii_lowercase().split(['_', '-', '.']).next().unwrap_or_default() {
+            "en" | "c" | "posix" => Ok(Locale::En),
+            "ja" => Ok(Locale::Ja),
+            _ => Err(format!("unknown locale {:?} (expected en or ja)", value)),
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Locale::En => "en",
+            Locale::Ja => "ja",
+        }
+    }
+
+    // VOYAGER_LOCALE, then the usual POSIX variables in their order of precedence
+    pub fn detect() -> Self {
+        ["VOYAGER_LOCALE", "LC_ALL", "LC_MESSAGES", "LANG"].iter()
+            .filter_map(|var| std::env::var(var).ok().filter(|v| !v.is_empty()))
+            .next()
+            .and_then(|v| Locale::parse(&v).ok())
+            .unwrap_or(Locale::En)
+    }
+
+    fn catalog(self) -> Option<&'static HashMap<&'static str, &'static str>> {
+        match self {
+            Locale::En => None,
+            Locale::Ja => Some(&JA),
+        }
+    }
+}
+
+// Process-wide, like the terminal it is shown on; set from the locale setting
+static LOCALE: AtomicU8 = AtomicU8::new(Locale::En as u8);
+
+pub fn set_locale(locale: Locale) {
+    LOCALE.store(locale as u8, Ordering::Relaxed);
+}
+
+pub fn locale() -> Locale {
+    if LOCALE.load(Ordering::Relaxed) == Locale::Ja as u8 { Locale::Ja } else { Locale::En }
+}
+
+pub fn lookup(msgid: &str) -> &str {
+    locale().catalog().and_then(|c| c.get(msgid).copied()).unwrap_or(msgid)
+}
+
+// Fills `{}` placeholders in order. A translation that needs the arguments in another
+// order numbers them instead: `{1}` is the second.
+pub fn fill(template: &str, args: &[&dyn Display]) -> String {
+    let mut out = String::new();
+    let mut next = 0;
+    let mut rest = template;
+    while let Some(open) = rest.find('{') {
+        out.push_str(&rest[..open]);
+        let Some(close) = rest[open..].find('}').map(|c| open + c) else { break };
+        let index = match &rest[open + 1..close] {
+            "" => { next += 1; Some(next - 1) }
+            n => n.parse::<usize>().ok(),
+        };
+        match index.and_then(|i| args.get(i)) {
+            Some(arg) => out.push_str(&arg.to_string()),
+            None => out.push_str(&rest[open..=close]),
+        }
+        rest = &rest[close + 1..];
+    }
+    out.push_str(rest);
+    out
+}
+
+// Left-aligns `text` in `width` columns; `{:<n}` counts characters, and most Japanese
+// ones take two columns
+pub fn pad(text: &str, width: usize) -> String {
+    let used = ratatui::text::Line::from(text).width();
+    format!("{}{}", text, " ".repeat(width.saturating_sub(used)))
+}
+
+// tr!("Loaded: {}", url): the message in the current locale, with its arguments filled in
+macro_rules! tr {
+    ($msg:literal) => { $crate::i18n::lookup($msg).to_string() };
+    ($msg:literal, $($arg:expr),+ $(,)?) => { $crate::i18n::fill($crate::i18n::lookup($msg), &[$(&$arg),+]) };
+}
+pub(crate) use tr;
+
+static JA: LazyLock<HashMap<&'static str, &'static str>> = LazyLock::new(|| JA_MESSAGES.iter().copied().collect());
+
+const JA_MESSAGES: &[(&str, &str)] = &[
+    ("{}\n\nThis may be a phishing site. Open it anyway?", "{}\n\nフィッシングサイトの可能性があります。それでも開きますか？"),
+    ("No previous page", "前のページはありません"),
+    ("No next page in history", "履歴に次のページはありません"),
+    ("AMP page, loading {} instead", "AMP ページのため、代わりに {} を読み込みます"),
+    ("Refresh to {} in {}s (R: follow now, X: cancel)", "{1} 秒後に {0} へ移動します (R: 今すぐ移動, X: 取り消し)"),
+    ("Refresh loop stopped at {}", "{} でリフレッシュのループを止めました"),
+    ("Loaded from cache: {}", "キャッシュから読み込みました: {}"),
+    ("Loaded from reading list: {}", "リーディングリストから読み込みました: {}"),
+    ("Fetching {}...", "{} を取得中..."),
+    ("Downloaded {} to {}", "{1} に {0} をダウンロードしました"),
+    ("Download to {} failed: {}", "{} へのダウンロードに失敗しました: {}"),
+    ("Querying {} engines...", "{} 個の検索エンジンに問い合わせ中..."),
+    ("Fetching the {} for {}...", "{1} の {0} を取得中..."),
+    ("Loaded: {}", "読み込みました: {}"),
+    ("References", "参照"),
+    ("Rendered view: {}", "表示ビュー: {}"),
+    ("Source view ({} lines) — \\ to toggle", "ソース表示 ({} 行) — \\ で切り替え"),
+    ("Image previews are off in ascii mode: {}", "ASCII モードでは画像プレビューは無効です: {}"),
+    ("Processing Image AA: {}...", "画像を AA に変換中: {}..."),
+    ("Image previews are off in ascii mode", "ASCII モードでは画像プレビューは無効です"),
+    ("Image AA Loaded. Press ESC to close.", "画像 AA を読み込みました。ESC で閉じます。"),
+    ("Image loaded. Press ESC to close.", "画像を読み込みました。ESC で閉じます。"),
+    ("{} (redirected: {})", "{} (リダイレクト: {})"),
+    ("This redirect wasn't followed: it would go past the limit of {} redirects (:set redirects) or back to an address already visited.", "このリダイレクトはたどりませんでした: 上限の {} 回 (:set redirects) を超えるか、すでに訪れたアドレスに戻るためです。"),
+    ("The server offers several versions of this page; its own page below lists them.", "サーバーはこのページの複数の版を提示しています。下のサーバー自身のページに一覧があります。"),
+    ("The server says the page hasn't changed, and sent no copy of it.", "サーバーはページが変更されていないとして、内容を送りませんでした。"),
+    ("This redirect isn't one that is followed automatically; the link below leads on.", "このリダイレクトは自動ではたどりません。下のリンクから先に進めます。"),
+    ("The server answered with a redirect but didn't say where to.", "サーバーはリダイレクトを返しましたが、行き先を示しませんでした。"),
+    ("The server couldn't make sense of the request.", "サーバーはリクエストを解釈できませんでした。"),
+    ("This page needs you to sign in.", "このページを見るにはサインインが必要です。"),
+    ("The server refuses to show this page.", "サーバーはこのページの表示を拒否しています。"),
+    ("There is no page at this address. It may have moved, or the link may be mistyped.", "このアドレスにページはありません。移動したか、リンクが間違っている可能性があります。"),
+    ("This page has been removed for good.", "このページは完全に削除されました。"),
+    ("Too many requests: the server asks you to wait before trying again.", "リクエストが多すぎます: 時間をおいてから再試行するようサーバーが求めています。"),
+    ("The server turned the request down.", "サーバーはリクエストを受け付けませんでした。"),
+    ("The server, or one in front of it, is down or overloaded. Trying again later may work.", "サーバー (またはその手前のサーバー) が停止しているか過負荷です。時間をおくと表示できるかもしれません。"),
+    ("The server ran into an error answering the request.", "サーバーでリクエストの処理中にエラーが起きました。"),
+    ("Address:", "アドレス:"),
+    ("Redirects to:", "リダイレクト先:"),
+    ("Retry after: {}", "再試行まで: {}"),
+    (":reload tries again.", ":reload で再試行します。"),
+    ("The server's own page follows.", "以下はサーバーが返したページです。"),
+    ("line {}/{} ({}%)", "{}/{} 行 ({}%)"),
+    ("Lookalike Domain", "紛らわしいドメイン"),
+    ("fill: profiles are {}", "fill: プロファイルは {} です"),
+    ("fill: no form fields on this page", "fill: このページにはフォーム項目がありません"),
+    ("Autofill: {}", "自動入力: {}"),
+    ("fill: filled {} of {} field(s) from {}", "fill: {2} から {1} 項目中 {0} 項目を入力しました"),
+    ("Appending {}...", "{} を追加中..."),
+    ("autopage: {}", "autopage: {}"),
+    ("Appended page {} of {}", "{1} の {0} ページ目を追加しました"),
+    ("{}: expected a pattern", "{}: パターンを指定してください"),
+    ("{}: no matching links", "{}: 一致するリンクはありません"),
+    ("Open {} matching links in background tabs?", "一致した {} 件のリンクをバックグラウンドのタブで開きますか？"),
+    ("Opened {} background tabs (gt to go through them)", "{} 個のタブをバックグラウンドで開きました (gt で順に表示)"),
+    ("New tab", "新しいタブ"),
+    ("Download {} matching links into {}?\nExisting files are kept; new names get a -N suffix.", "一致した {} 件のリンクを {} にダウンロードしますか？\n既存のファイルは残し、新しい名前には -N を付けます。"),
+    ("Downloading {} files in the background", "{} 個のファイルをバックグラウンドでダウンロード中"),
+    ("Open All", "すべて開く"),
+    ("Download All", "すべてダウンロード"),
+    ("-- CARET -- (v: select, y: yank, K: define, Esc: exit)", "-- キャレット -- (v: 選択, y: ヤンク, K: 辞書, Esc: 終了)"),
+    ("Caret mode off", "キャレットモードを終了しました"),
+    ("No link under cursor", "カーソル位置にリンクはありません"),
+    ("cert: no certificate seen for this host in this session", "cert: このセッションではこのホストの証明書を受け取っていません"),
+    ("{} to {} UTC  (not valid now)", "{} から {} まで (UTC)  (現在は無効)"),
+    ("{} to {} UTC", "{} から {} まで (UTC)"),
+    ("could not parse ({} bytes)", "解析できません ({} バイト)"),
+    ("Certificate chain: {}", "証明書チェーン: {}"),
+    ("{} certificate(s) — j / k to scroll", "証明書 {} 件 — j / k でスクロール"),
+    ("Subject", "サブジェクト"),
+    ("Issuer", "発行者"),
+    ("Key", "鍵"),
+    ("Valid", "有効期間"),
+    ("Showing the first {} of {} (:more or the [Load more] link continues)", "{1} のうち先頭 {0} を表示中 (:more か [Load more] リンクで続きを読み込みます)"),
+    ("Showing the first {} (:more or the [Load more] link continues)", "先頭 {} を表示中 (:more か [Load more] リンクで続きを読み込みます)"),
+    ("more: this page is already fully loaded", "more: このページはすべて読み込み済みです"),
+    ("{}: not on a web page", "{}: ウェブページではありません"),
+    ("Saved to reading list: {}", "リーディングリストに保存しました: {}"),
+    ("Watching for changes (checks are off until :set monitor=<minutes>)", "変更を監視します (:set monitor=<分> を設定するまで確認は行いません)"),
+    ("Watching for changes every {} min", "{} 分ごとに変更を監視します"),
+    ("No longer watching this page", "このページの監視をやめました"),
+    ("read-watch: save the page with :read-later first", "read-watch: 先に :read-later でページを保存してください"),
+    ("Removed from reading list: {}", "リーディングリストから削除しました: {}"),
+    ("read-done: this page isn't on the reading list", "read-done: このページはリーディングリストにありません"),
+    ("Bookmarked ({} tags)", "ブックマークしました (タグ {} 個)"),
+    ("Bookmarked in {} ({} tags)", "{} にブックマークしました (タグ {} 個)"),
+    ("Bookmarks not loaded: {}", "ブックマークを読み込めませんでした: {}"),
+    ("Bookmark removed: {}", "ブックマークを削除しました: {}"),
+    ("bm del: expected a bookmark number, as shown by :bm list", "bm del: :bm list に表示されるブックマーク番号を指定してください"),
+    ("bm del: no bookmark {} ({} saved)", "bm del: ブックマーク {} はありません (保存数 {})"),
+    ("bm: expected add, list, del <n> or watch", "bm: add、list、del <n>、watch のいずれかを指定してください"),
+    ("bm watch: bookmark the page first", "bm watch: 先にページをブックマークしてください"),
+    ("Bookmark changed: {}", "ブックマークしたページが変更されています: {}"),
+    ("bookmarks: {}", "ブックマーク: {}"),
+    ("unbookmark: this page isn't bookmarked", "unbookmark: このページはブックマークされていません"),
+    ("frame: no frame {} ({} on page)", "frame: フレーム {} はありません (このページには {} 個)"),
+    ("set: {}", "set: {}"),
+    ("timing: no fetch recorded for this page", "timing: このページの取得記録はありません"),
+    ("frontend: none configured for this site (:set frontend=<site>=<host>)", "frontend: このサイトには設定されていません (:set frontend=<サイト>=<ホスト>)"),
+    ("select: {}", "select: {}"),
+    ("xpath: {}", "xpath: {}"),
+    ("Saved {} lines to {}", "{1} に {0} 行を保存しました"),
+    ("Saved {} lines of Markdown to {}", "{1} に Markdown を {0} 行保存しました"),
+    ("Saved the page's HTML to {}", "{} にページの HTML を保存しました"),
+    ("Download cancelled", "ダウンロードを取り消しました"),
+    ("abort: no response is waiting", "abort: 保留中のレスポンスはありません"),
+    ("Wrote {} HAR entries to {}", "{1} に HAR エントリを {0} 件書き出しました"),
+    ("Copied {} lines of Markdown ({})", "Markdown を {} 行コピーしました ({})"),
+    ("search-site: current page has no domain", "search-site: 現在のページにはドメインがありません"),
+    ("Unknown command: {}", "不明なコマンド: {}"),
+    ("Network log shown", "ネットワークログを表示しました"),
+    ("Network log hidden", "ネットワークログを隠しました"),
+    ("No next page found", "次のページが見つかりません"),
+    ("No previous page found", "前のページが見つかりません"),
+    ("Showing translation ({})", "翻訳を表示中 ({})"),
+    ("Translation ({})", "翻訳 ({})"),
+    ("Translated the selection to {}", "選択範囲を {} に翻訳しました"),
+    ("Showing original", "原文を表示中"),
+    ("Translating to {}...", "{} に翻訳中..."),
+    ("Showing translation ({}) — :translate to toggle", "翻訳を表示中 ({}) — :translate で切り替え"),
+    ("Translation of {} dropped: the page has changed", "ページが変わったため {} の翻訳を破棄しました"),
+    ("define: no word given", "define: 単語を指定してください"),
+    ("define: dict lookups can't go through the proxy; :set dictionary=web:<url> instead", "define: dict の検索はプロキシを経由できません。代わりに :set dictionary=web:<url> を使ってください"),
+    ("Proxy: none, connecting directly", "プロキシ: なし (直接接続)"),
+    ("Proxy: {} (from the environment)", "プロキシ: {} (環境変数から)"),
+    ("Proxy: none set in the environment, connecting directly", "プロキシ: 環境変数に設定なし (直接接続)"),
+    ("Proxy: {}", "プロキシ: {}"),
+    ("Looking up {}...", "{} を調べています..."),
+    ("No definition found for {}", "{} の定義は見つかりませんでした"),
+    ("Define: {}", "定義: {}"),
+    ("Defined {}", "{} を調べました"),
+    ("{}: no matches", "{}: 一致なし"),
+    ("{}: {} match(es) — Esc to return to the page", "{}: {} 件一致 — Esc でページに戻ります"),
+    ("table-export: no tables on this page", "table-export: このページには表がありません"),
+    ("{} rows x {} columns: {}", "{} 行 x {} 列: {}"),
+    ("Which table?", "どの表ですか？"),
+    ("table-export: expected a table number 1..{}", "table-export: 表の番号 1..{} を指定してください"),
+    ("Wrote {} rows x {} columns to {}", "{2} に {0} 行 x {1} 列を書き出しました"),
+    ("yank-links: {}", "yank-links: {}"),
+    ("yank-links: no matching links", "yank-links: 一致するリンクはありません"),
+    ("Wrote {} links to {}", "{1} にリンクを {0} 件書き出しました"),
+    ("Copied {} links ({})", "リンクを {} 件コピーしました ({})"),
+    ("outline: no headings on this page", "outline: このページには見出しがありません"),
+    ("Timing", "タイミング"),
+    ("Save", "保存"),
+    ("HAR Export", "HAR 書き出し"),
+    ("Messages", "メッセージ"),
+    ("Page Info", "ページ情報"),
+    ("Export Table", "表の書き出し"),
+    ("Yank Links", "リンクのヤンク"),
+    ("Outline", "アウトライン"),
+    ("History Tree", "履歴ツリー"),
+    ("History", "履歴"),
+    ("URL", "URL"),
+    ("Title", "タイトル"),
+    ("Description", "説明"),
+    ("Canonical", "正規 URL"),
+    ("Length", "長さ"),
+    ("HTTP", "HTTP"),
+    ("Security", "セキュリティ"),
+    ("Save to file:", "保存先のファイル:"),
+    ("{} already exists. Replace it?", "{} はすでに存在します。置き換えますか？"),
+    ("Write {} (replacing the existing file)", "{} に書き込みます (既存のファイルを置き換えます)"),
+    ("Write {}", "{} に書き込みます"),
+    ("Run: sh -c {}", "実行: sh -c {}"),
+    ("Allow once", "今回だけ許可"),
+    ("Cancel", "キャンセル"),
+    ("Cancelled", "キャンセルしました"),
+    ("Overwrite?", "上書きしますか？"),
+    ("Write File", "ファイルの書き込み"),
+    ("Always allow file writes", "ファイルの書き込みを常に許可"),
+    ("Run Command", "コマンドの実行"),
+    ("Always allow external commands", "外部コマンドを常に許可"),
+    ("diff: no earlier copy of this page", "diff: このページの以前のコピーはありません"),
+    ("diff: unchanged since the last visit", "diff: 前回の訪問から変更はありません"),
+    ("--- {} (last visit)", "--- {} (前回の訪問)"),
+    ("+++ {} (now)", "+++ {} (現在)"),
+    ("diff: {} line(s) removed, {} added — Esc to return to the page", "diff: {} 行削除、{} 行追加 — Esc でページに戻ります"),
+    ("Binary content ({}), not rendered", "バイナリデータ ({}) のため表示しません"),
+    ("{} is {}, waiting for confirmation", "{} は {} あります。確認を待っています"),
+    ("Continue and load {}", "続けて {} を読み込む"),
+    ("Save to disk instead", "代わりにディスクに保存する"),
+    ("{} is {}.", "{} は {} あります。"),
+    ("continue: no response is waiting", "continue: 保留中のレスポンスはありません"),
+    ("Downloading {}...", "{} をダウンロード中..."),
+    ("stream-to: no response is waiting", "stream-to: 保留中のレスポンスはありません"),
+    ("Stream the response to:", "レスポンスの保存先:"),
+    ("Downloading to {} in the background", "{} にバックグラウンドでダウンロード中"),
+    ("Save {} ({}, {}) to:", "{} ({}, {}) の保存先:"),
+    ("download: nothing to save on this page", "download: このページには保存するものがありません"),
+    ("Saved {} to {}", "{1} に {0} を保存しました"),
+    ("Large Response", "大きなレスポンス"),
+    ("Save to Disk", "ディスクに保存"),
+    ("Download", "ダウンロード"),
+    ("Help", "ヘルプ"),
+    ("Voyager help", "Voyager ヘルプ"),
+    ("Keys", "キー"),
+    ("Commands", "コマンド"),
+    ("Settings", "設定"),
+    ("Inspector: {} elements (l/h expand/collapse, Enter jump, Esc close)", "インスペクタ: 要素 {} 個 (l/h 展開/折りたたみ, Enter 移動, Esc 閉じる)"),
+    ("Node has no text to locate", "このノードには探せるテキストがありません"),
+    ("Jumped to line {}", "{} 行目に移動しました"),
+    ("Node text not found in rendered view", "表示中のビューにノードのテキストが見つかりません"),
+    ("Inspector closed", "インスペクタを閉じました"),
+    ("Show the <noscript> content", "<noscript> の内容を表示する"),
+    ("Open {}", "{} を開く"),
+    ("Load the Wayback Machine snapshot", "Wayback Machine のスナップショットを読み込む"),
+    ("Keep this page", "このページのままにする"),
+    ("This page seems to need JavaScript to show its content.", "このページは内容の表示に JavaScript が必要なようです。"),
+    ("noscript: the page has no <noscript> content", "noscript: このページには <noscript> の内容がありません"),
+    ("<noscript> content — Esc to return to the page", "<noscript> の内容 — Esc でページに戻ります"),
+    ("JavaScript Page", "JavaScript のページ"),
+    ("Fetch failed: {}", "取得に失敗しました: {}"),
+    ("Preview closed", "プレビューを閉じました"),
+    ("Refresh cancelled", "リフレッシュを取り消しました"),
+    ("mirror: expected a depth, e.g. :mirror 2", "mirror: 深さを指定してください (例: :mirror 2)"),
+    ("mirror: only web pages can be mirrored", "mirror: ミラーできるのはウェブページだけです"),
+    ("Mirroring {} links deep in the background (at most {} pages)", "リンク {} 段先までバックグラウンドでミラー中 (最大 {} ページ)"),
+    ("Mirroring: {} pages fetched", "ミラー中: {} ページ取得済み"),
+    ("Mirrored {} pages into {}", "{1} に {0} ページをミラーしました"),
+    ("Mirror failed: {}", "ミラーに失敗しました: {}"),
+    ("Changed since saved: {}", "保存後に変更されています: {}"),
+    ("reading list: {}", "リーディングリスト: {}"),
+    ("{} words, {} min read", "{} 語, 約 {} 分"),
+    ("No links on this page", "このページにはリンクがありません"),
+    ("Command Palette", "コマンドパレット"),
+    ("Find Link", "リンク検索"),
+    ("Open", "開く"),
+    ("Register \"{}: y yanks the link URL, Y the page URL, p opens its contents", "レジスタ \"{}: y でリンクの URL、Y でページの URL をヤンク、p で内容を開きます"),
+    ("Not a register: {}", "レジスタではありません: {}"),
+    ("Yanked {} ({} chars) into \"{}{}", "{} ({} 文字) を \"{} にヤンクしました{}"),
+    ("Register \"{} is empty", "レジスタ \"{} は空です"),
+    ("No registers set; yank with y, Y or caret-mode y, optionally after \"x", "レジスタは空です。y、Y またはキャレットモードの y でヤンクします (\"x を前に付けることもできます)"),
+    ("Registers", "レジスタ"),
+    ("Compose request — Ctrl-S send, Esc cancel", "リクエストの作成 — Ctrl-S 送信, Esc 取り消し"),
+    ("Request cancelled", "リクエストを取り消しました"),
+    ("request: {}", "request: {}"),
+    ("{} {} -> {} ({} headers)", "{} {} -> {} (ヘッダー {} 個)"),
+    ("Resolving {}...", "{} の行き先を確認中..."),
+    ("{}\nleads to\n{}\n\nOpen it?", "{}\nの行き先は\n{}\n\n開きますか？"),
+    ("{}\nleads to\n{}\n\n{}\n\nThis may be a phishing site. Open it anyway?", "{}\nの行き先は\n{}\n\n{}\n\nフィッシングサイトの可能性があります。それでも開きますか？"),
+    ("This redirect wasn't followed: it leads to a lookalike domain. {}", "このリダイレクトはたどりませんでした: 類似ドメインへ向かいます。{}"),
+    ("This redirect wasn't followed: it leads to another site, and the request can't be sent again there without the headers voyager.toml sets for this one.", "このリダイレクトはたどりませんでした: 別のサイトへ向かいますが、voyager.toml がこのサイト用に設定したヘッダーを外してリクエストを送り直せません。"),
+    ("{} doesn't redirect anywhere, opening it", "{} はリダイレクトしないので、そのまま開きます"),
+    ("Short Link", "短縮リンク"),
+    ("Streaming {}...", "{} をストリーミング中..."),
+    ("Streaming {} ({} bytes)", "{} をストリーミング中 ({} バイト)"),
+    ("Stream interrupted after {} bytes: {}", "{} バイトでストリームが中断しました: {}"),
+    ("Stream ended: {} bytes from {}", "ストリーム終了: {1} から {0} バイト"),
+    ("theme {}: {}", "テーマ {}: {}"),
+    ("Network ({} requests)", "ネットワーク (リクエスト {} 件)"),
+    ("Voyager URL", "Voyager URL"),
+    ("Link", "リンク"),
+    ("Image AA Preview", "画像 AA プレビュー"),
+    ("Image Preview", "画像プレビュー"),
+    ("Request (METHOD URL / headers / blank line / body)", "リクエスト (メソッド URL / ヘッダー / 空行 / 本文)"),
+    ("any other key cancels", "ほかのキーで取り消し"),
+    ("Stopped watching {}", "{} の監視をやめました"),
+    ("watch: not watching anything", "watch: 監視中のページはありません"),
+    ("Watching {} every {}s (:watch off to stop)", "{1} 秒ごとに {0} を監視中 (:watch off で停止)"),
+    ("watch: {}", "watch: {}"),
+    ("Page changed: {} (redrawn when you return to it)", "ページが変更されました: {} (ページに戻ると再描画します)"),
+    ("Page changed: {} (:diff shows what)", "ページが変更されました: {} (:diff で差分を表示)"),
+    ("Play with {}", "{} で再生"),
+    ("Save to disk", "ディスクに保存"),
+    ("Media", "メディア"),
+    ("play: expected a URL", "play: URL を指定してください"),
+    ("play: no player configured (:set player=<command>)", "play: プレーヤーが設定されていません (:set player=<コマンド>)"),
+    ("Playing {} with {}", "{1} で {0} を再生中"),
+    ("save-url: expected a URL", "save-url: URL を指定してください"),
+    ("Save {} to:", "{} の保存先:"),
+    ("resources: the page declares no subresources", "resources: このページはサブリソースを宣言していません"),
+    ("Checking the sizes of {} resources...", "{} 個のリソースのサイズを確認中..."),
+    ("{} resources, {} known in total — Enter to open, inspect or save one", "リソース {} 個、判明した合計 {} — Enter で開く / 調べる / 保存する"),
+    ("Resources ({})", "リソース ({})"),
+    ("Show headers (HEAD)", "ヘッダーを表示 (HEAD)"),
+    ("Resource", "リソース"),
+    ("stylesheet", "スタイル"),
+    ("icon", "アイコン"),
+    ("preload", "プリロード"),
+    ("prefetch", "プリフェッチ"),
+    ("script", "スクリプト"),
+    ("image", "画像"),
+    ("media", "メディア"),
+    ("frame", "フレーム"),
+    ("Tab {}/{}: {}", "タブ {}/{}: {}"),
+    ("tabclose: this is the last tab (:q quits)", "tabclose: 最後のタブです (:q で終了)"),
+    ("Tab closed; {} left", "タブを閉じました (残り {} 個)"),
+    ("tab: expected a tab number 1..{}", "tab: タブの番号 1..{} を指定してください"),
+    ("Pattern not found: {}", "見つかりません: {}"),
+    ("/{}: match {} of {} (wrapped to the top)", "/{0}: {2} 件中 {1} 件目 (先頭に戻りました)"),
+    ("/{}: match {} of {} (wrapped to the bottom)", "/{0}: {2} 件中 {1} 件目 (末尾に戻りました)"),
+    ("/{}: match {} of {}", "/{0}: {2} 件中 {1} 件目"),
+    ("-- INSERT -- (Enter: submit, Tab: next field, Esc: done)", "-- 挿入 -- (Enter: 送信, Tab: 次の欄, Esc: 完了)"),
+    ("-- INSERT -- {}: {}", "-- 挿入 -- {}: {}"),
+    ("Choose {}", "{} を選択"),
+    ("submit: no forms on this page", "submit: このページにフォームはありません"),
+    ("submit: expected a form number 1..{}", "submit: フォームの番号 1..{} を指定してください"),
+    ("submit: can't send a form to {}", "submit: {} にはフォームを送信できません"),
+    ("Submitting {} field(s) to {}...", "{1} に {0} 項目を送信中..."),
+    ("Submit failed: {}", "送信に失敗しました: {}"),
+    ("cookies clear: expected a domain, or all", "cookies clear: ドメインか all を指定してください"),
+    ("Removed {} cookie(s)", "クッキーを {} 個削除しました"),
+    ("Stopped loading {}", "{} の読み込みを中止しました"),
+    ("Tab {}: fetch failed: {}", "タブ {}: 取得に失敗しました: {}"),
+    ("tab {}: {}", "タブ {}: {}"),
+    ("bookmark: {}", "ブックマーク: {}"),
+    ("Reader mode off", "リーダーモードをオフにしました"),
+    ("Reader mode on", "リーダーモードをオンにしました"),
+    ("No links on screen", "画面内にリンクがありません"),
+    ("Restored from memory: {} (:reload fetches it again)", "メモリから復元しました: {} (:reload で再取得)"),
+    ("No link is labelled {}", "{} というラベルのリンクはありません"),
+    ("Follow: {} (type a label, Backspace undoes, Esc cancels)", "リンクを開く: {} (ラベルを入力、Backspace で 1 文字戻す、Esc で中止)"),
+    ("Loaded voyager.toml: {} key binding(s), {} alias(es), {} header(s)", "voyager.toml を読み込みました: キー割り当て {} 個、エイリアス {} 個、ヘッダー {} 個"),
+    ("Reader mode on (no article found on this page, so it is shown whole)", "リーダーモードをオンにしました (このページには記事が見つからないため、ページ全体を表示します)"),
+    ("Waiting for {}... {}s (Esc cancels)", "{} の応答を待っています... {} 秒 (Esc で中止)"),
+    ("Loading {}: {} (Esc cancels)", "{} を読み込み中: {} (Esc で中止)"),
+    ("Loading {}: {} of {} (Esc cancels)", "{} を読み込み中: {} / {} (Esc で中止)"),
+    ("Search cleared", "検索の強調表示を解除しました"),
+    ("Make / searches case-sensitive when the pattern has a capital letter", "パターンに大文字を含むとき / 検索で大文字と小文字を区別する"),
+    ("Link title: {}", "リンクのタイトル: {}"),
+    ("No titles on screen", "画面上にタイトルはありません"),
+    ("Zoom is remembered per site; this page has no host", "ズームはサイトごとに記憶されますが、このページにはホストがありません"),
+    ("Zoom not saved: {}", "ズームを保存できませんでした: {}"),
+    ("Zoom reset for {}", "{} のズームを元に戻しました"),
+    ("Zoom {} for {}: {} columns", "{1} のズーム {0}: {2} 桁"),
+    ("Normal", "ノーマル"),
+    ("Enter a command", "コマンドを入力"),
+    ("Show this help", "このヘルプを表示"),
+    ("Open the command palette", "コマンドパレットを開く"),
+    ("Fuzzy-find a link on the page and follow it", "ページ内のリンクをあいまい検索して開く"),
+    ("Type an address or a search, or edit the current address; Tab completes from bookmarks and history", "アドレスか検索語を入力、または現在のアドレスを編集 (Tab でブックマークと履歴から補完)"),
+    ("Scroll down / up (see the scroll, wheel and smooth settings)", "下 / 上にスクロール (scroll、wheel、smooth 設定を参照)"),
+    ("Scroll down / up half a screen", "半画面ずつ下 / 上にスクロール"),
+    ("Scroll down / up a screen, keeping two lines in view", "1 画面ずつ下 / 上にスクロール (2 行は残して表示)"),
+    ("Go to the top / bottom of the page", "ページの先頭 / 末尾へ移動"),
+    ("Select the next / previous link", "次 / 前のリンクを選択"),
+    ("Follow the selected link (images open a preview, audio and video offer the player)", "選択中のリンクを開く (画像はプレビュー、音声と動画はプレーヤーを提案)"),
+    ("Switch to the next / previous tab", "次 / 前のタブに切り替え"),
+    ("Go to the next / previous page of a paginated document", "複数ページの文書の次 / 前のページへ"),
+    ("Zoom in / out: a narrower, centered or wider text column, remembered per site", "拡大 / 縮小: 本文の幅を狭く (中央寄せ) または広くし、サイトごとに記憶"),
+    ("Yank the selected link's URL / the page URL", "選択中のリンクの URL / ページの URL をヤンク"),
+    ("Open the URL (or search the text) held in a register", "レジスタの URL を開く (テキストなら検索)"),
+    ("Use register x for the next y, Y or p, e.g. \"ay", "次の y、Y、p でレジスタ x を使う (例: \"ay)"),
+    ("Show the selected link's title, or what the abbreviations on screen stand for", "選択中のリンクのタイトル、または画面上の略語の意味を表示"),
+    ("Enter caret mode", "キャレットモードに入る"),
+    ("Look up a word (prefills :define)", "単語を調べる (:define を入力済みにする)"),
+    ("Toggle the page source view", "ページのソース表示を切り替え"),
+    ("Search the page; matches are highlighted (Esc clears them)", "ページ内を検索し、一致箇所を強調表示 (Esc で解除)"),
+    ("Next / previous search match", "次 / 前の検索一致箇所へ"),
+    ("Follow / cancel a pending meta refresh", "保留中の meta refresh に従う / 取り消す"),
+    ("Return from an alternate view to the page", "別のビューからページに戻る"),
+    ("Caret", "キャレット"),
+    ("Insert", "挿入"),
+    ("Hint", "ヒント"),
+    ("Complete from bookmarks and history, next / previous", "ブックマークと履歴から補完 (次 / 前)"),
+    ("Move to the start / end, by a character, by a word", "先頭 / 末尾へ、1 文字、1 単語移動"),
+    ("Cut the word before the cursor, cut to the start / end, paste the cut", "カーソル前の単語を切り取り、先頭 / 末尾まで切り取り、切り取った文字を貼り付け"),
+    ("Search history by frecency, starting from the typed text", "入力中のテキストからよく使う履歴を検索"),
+    ("Open the address (or search the text), cancel", "アドレスを開く (テキストなら検索)、キャンセル"),
+    ("Go to", "移動先"),
+    ("No bookmarks or history match {}", "{} に一致するブックマークや履歴はありません"),
+    ("Type into a text box, toggle a checkbox or radio button, choose an option, or submit", "テキスト欄に入力、チェックボックスやラジオボタンを切り替え、選択肢を選ぶ、または送信"),
+    ("Submit the form (a new line in a text area)", "フォームを送信 (テキストエリアでは改行)"),
+    ("Keep the text and move to the next field", "入力を確定して次の欄へ"),
+    ("Keep the text and leave insert mode", "入力を確定して挿入モードを終了"),
+    ("Clear the field", "欄を空にする"),
+    ("Stop a page load in progress", "読み込み中のページを中止"),
+    ("Go back / forward in history (Backspace also goes back)", "履歴を戻る / 進む (Backspace でも戻る)"),
+    ("Read voyager.toml again: key bindings and command aliases", "voyager.toml (キー割り当てとコマンドのエイリアス) を読み込み直す"),
+    ("Key bindings", "キー割り当て"),
+    ("none", "なし"),
+    ("Label the links on screen; type a label to follow it", "画面内のリンクにラベルを付け、ラベルを入力して開く"),
+    ("Pages kept in memory so back / forward show them without fetching, 0 for none", "戻る / 進むで再取得せずに表示するためメモリに保持するページ数 (0 で保持しない)"),
+    ("Fetch the page again, bypassing the caches, and keep the reading position", "キャッシュを使わずにページを再取得し、読んでいた位置を保つ"),
+    ("Type a label, take back a letter, cancel", "ラベルを入力 / 1 文字戻す / 中止"),
+    ("Normal-mode keys can be rebound in voyager.toml in the config directory: under [keys], a key = an action below, a :command, or none. Under [aliases], name = a command to run for :name.", "ノーマルモードのキーは設定ディレクトリの voyager.toml で割り当て直せます: [keys] に「キー = 下のアクション、:コマンド、または none」、[aliases] に「名前 = :名前 で実行するコマンド」を書きます。"),
+    ("Toggle reader mode: just the article text, without menus, sidebars and footers (:set reader)", "リーダーモードを切り替え: メニューやサイドバー、フッターを除いた記事の本文だけを表示 (:set reader)"),
+    ("Reader mode: lay out only the main article of each page, without menus and footers (VOYAGER_READER)", "リーダーモード: 各ページの本文の記事だけを表示し、メニューやフッターを省く (VOYAGER_READER)"),
+    ("Show the stored cookies by site (about:cookies), or remove a site's or all of them", "保存されたクッキーをサイト別に表示 (about:cookies)、またはサイトごと / すべて削除"),
+    ("Submit form n, or the form of the selected field", "n 番目のフォーム、または選択中の欄のフォームを送信"),
+    ("Move the caret", "キャレットを移動"),
+    ("Start or clear a selection", "選択を開始 / 解除"),
+    ("Yank the selection (\"xy into register x)", "選択範囲をヤンク (\"xy でレジスタ x へ)"),
+    ("Define the word under the caret", "キャレット位置の単語を調べる"),
+    ("Follow the link under the caret", "キャレット位置のリンクを開く"),
+    ("Leave caret mode", "キャレットモードを終了"),
+    ("Inspector", "インスペクタ"),
+    ("Move, expand / collapse nodes", "移動、ノードの展開 / 折りたたみ"),
+    ("Jump to the node's text in the page", "ページ内のノードのテキストへ移動"),
+    ("Request", "リクエスト"),
+    ("Send the composed request", "作成したリクエストを送信"),
+    ("Popup", "ポップアップ"),
+    ("Scroll or select, run the entry, close", "スクロール / 選択、項目を実行、閉じる"),
+    ("Picker", "ピッカー"),
+    ("Filter, move, choose, cancel", "絞り込み、移動、決定、取り消し"),
+    ("Dialog", "ダイアログ"),
+    ("Answer, submit or choose, cancel", "回答 / 送信 / 選択、取り消し"),
+    ("Next tab", "次のタブ"),
+    ("Previous tab", "前のタブ"),
+    ("Top of the page", "ページの先頭"),
+    ("Next page of a paginated document", "複数ページの文書の次のページ"),
+    ("Previous page of a paginated document", "複数ページの文書の前のページ"),
+    ("Use that register for the next y, Y or p (+ is the clipboard)", "次の y、Y、p でそのレジスタを使う (+ はクリップボード)"),
+    ("Open an address or a local path (:url! skips the short link and lookalike domain checks)", "アドレスかローカルのパスを開く (:url! は短縮リンクと類似ドメインの確認を省略)"),
+    ("Open every matching link in a background tab", "一致するリンクをすべてバックグラウンドのタブで開く"),
+    ("Open an address (default: this page) in a new tab (also :tabe)", "アドレス (既定はこのページ) を新しいタブで開く (:tabe でも可)"),
+    ("Switch to tab n", "タブ n に切り替え"),
+    ("Switch to the next tab", "次のタブに切り替え"),
+    ("Switch to the previous tab", "前のタブに切り替え"),
+    ("Close the current tab", "現在のタブを閉じる"),
+    ("Go back in history (also :b)", "履歴を戻る (:b でも可)"),
+    ("Go forward in history (also :f)", "履歴を進む (:f でも可)"),
+    ("Show every page visited, across sessions, optionally only those matching (about:history)", "これまでに訪れたすべてのページを表示 (セッションをまたいで保存、語で絞り込み可) (about:history)"),
+    ("Pick an entry from the back/forward history", "戻る / 進む履歴から選ぶ"),
+    ("Pick an entry from the full history tree", "履歴ツリー全体から選ぶ"),
+    ("Reopen the tabs and history left by a run that didn't exit cleanly (offered at startup)", "正常に終了しなかった前回のタブと履歴を開き直す (起動時に提案されます)"),
+    ("Load the next chunk of a large page", "大きなページの続きを読み込む"),
+    ("Follow the rel=next page", "rel=next のページへ"),
+    ("Follow the rel=prev page", "rel=prev のページへ"),
+    ("Search with the configured engine", "設定した検索エンジンで検索"),
+    ("Search within the current site, through its own search form when the page has one", "現在のサイト内を検索。ページにサイト内検索フォームがあればそれを使う"),
+    ("Query several search engines at once", "複数の検索エンジンに同時に問い合わせる"),
+    ("Search pages visited so far", "これまでに訪れたページを検索"),
+    ("Bookmark the page (as :bookmark), list the bookmarks, delete bookmark n from the list, or toggle checking this bookmark for changes", "ページをブックマーク (:bookmark と同じ)、ブックマークを一覧、一覧の n 番を削除、このブックマークの変更監視を切り替え"),
+    ("Bookmark the page with tags, in a folder; again to move it or add tags", "ページをタグ付きでフォルダにブックマーク (もう一度で移動 / タグを追加)"),
+    ("Remove the page's bookmark", "ページのブックマークを削除"),
+    ("Show the bookmarks by folder, optionally only one tag or folder (about:bookmarks)", "ブックマークをフォルダごとに表示 (タグかフォルダで絞り込み可、about:bookmarks)"),
+    ("Save the page to the reading list", "ページをリーディングリストに保存"),
+    ("Remove the page from the reading list", "ページをリーディングリストから削除"),
+    ("Toggle checking the saved page for changes (see the monitor setting)", "保存したページの変更確認を切り替え (monitor 設定を参照)"),
+    ("Show the reading list (readable offline and in --sandbox)", "リーディングリストを表示 (オフラインや --sandbox でも読めます)"),
+    ("Show the site's sitemap (found through robots.txt) as links", "サイトのサイトマップ (robots.txt から探します) をリンクとして表示"),
+    ("Show the site's robots.txt rules grouped by user agent", "サイトの robots.txt のルールをユーザーエージェントごとに表示"),
+    ("List the page's frames", "ページのフレームを一覧表示"),
+    ("Open frame n", "フレーム n を開く"),
+    ("Show page metadata and the negotiated HTTP version", "ページのメタデータと使われた HTTP バージョンを表示"),
+    ("Show the certificate chain the site presented", "サイトが示した証明書チェーンを表示"),
+    ("Jump to a heading", "見出しへ移動"),
+    ("Show the page's <noscript> content", "ページの <noscript> の内容を表示"),
+    ("Open the page on its configured alternative front end", "設定した代替フロントエンドでページを開く"),
+    ("Open the Wayback Machine snapshot of the page", "ページの Wayback Machine スナップショットを開く"),
+    ("Show what changed since the last visit to this page", "このページの前回の訪問からの変更を表示"),
+    ("Re-fetch the page on an interval and flag changes", "ページを一定間隔で再取得し、変更を知らせる"),
+    ("Open the DOM inspector", "DOM インスペクタを開く"),
+    ("List the stylesheets, scripts, images and preloads the page declares, with sizes, to open, inspect or save", "ページが宣言するスタイルシート、スクリプト、画像、プリロードをサイズ付きで一覧表示し、開く / 調べる / 保存する"),
+    ("Show only elements matching a CSS selector", "CSS セレクタに一致する要素だけを表示"),
+    ("Show the results of an XPath expression", "XPath 式の結果を表示"),
+    ("Write a table as CSV or TSV", "表を CSV か TSV で書き出す"),
+    ("Write the page to a file: its HTML, Markdown, or the displayed text (by extension unless given)", "ページをファイルに書き出す: HTML、Markdown、または表示中のテキスト (指定がなければ拡張子で決定)"),
+    ("Save a binary response that was not displayed", "表示しなかったバイナリのレスポンスを保存"),
+    ("Save the site's pages up to depth links away as text (or HTML) with local links, into dir (default ./mirror)", "サイトのページをリンク depth 段先まで、ローカルのリンク付きのテキスト (または HTML) として dir に保存 (既定は ./mirror)"),
+    ("Play an audio or video URL with the configured player", "音声や動画の URL を設定したプレーヤーで再生"),
+    ("Download a URL in the background, e.g. a media or resource link (also :save-media)", "URL をバックグラウンドでダウンロード (メディアやリソースのリンクなど、:save-media でも可)"),
+    ("Download every link whose text or URL matches into the working directory", "テキストか URL が一致するリンクをすべて作業ディレクトリにダウンロード"),
+    ("Load a response held back by the size limit", "サイズ制限で止めたレスポンスを読み込む"),
+    ("Stream a held-back response straight to a file", "止めたレスポンスを直接ファイルに保存"),
+    ("Drop a held-back response without downloading it", "止めたレスポンスをダウンロードせずに破棄"),
+    ("Copy the page (or caret selection) as Markdown", "ページ (またはキャレットの選択範囲) を Markdown としてコピー"),
+    ("Copy the page's link URLs, one per line, or write them to a file (the > needs a space before and after); the regex filters on link text or URL", "ページのリンクの URL を 1 行ずつコピー、またはファイルに書き出す (> の前後には空白が必要)。正規表現でリンクのテキストか URL を絞り込む"),
+    ("Translate the page or the caret selection, or toggle back to the original", "ページかキャレットの選択範囲を翻訳する、または原文に戻す"),
+    ("Look up a word", "単語を調べる"),
+    ("Fill the page's form fields from an autofill profile (autofill.json in the config directory)", "自動入力のプロファイル (設定ディレクトリの autofill.json) からページのフォーム項目を入力する"),
+    ("Compose and send a custom request", "カスタムリクエストを作成して送信"),
+    ("Show the status and headers of a HEAD request (default: this page)", "HEAD リクエストのステータスとヘッダーを表示 (既定はこのページ)"),
+    ("Show the status, headers and allowed methods from an OPTIONS request", "OPTIONS リクエストのステータス、ヘッダー、許可されたメソッドを表示"),
+    ("Show the timing breakdown of the current page's fetch", "現在のページの取得にかかった時間の内訳を表示"),
+    ("Write the session's requests as a HAR file", "セッションのリクエストを HAR ファイルに書き出す"),
+    ("Review past status messages", "過去のステータスメッセージを見る"),
+    ("Show the contents of the yank registers", "ヤンクレジスタの内容を表示"),
+    ("Show browsing statistics: visits, top sites, pages per day, reading time (about:stats)", "閲覧の統計を表示: 訪問数、よく見るサイト、1 日あたりのページ数、読書時間 (about:stats)"),
+    ("Change a setting (see below)", "設定を変更 (下を参照)"),
+    ("Fetch through an HTTP or SOCKS5 proxy, e.g. socks5h://127.0.0.1:9050; without an argument, show the one in use", "HTTP または SOCKS5 プロキシ経由で取得 (例: socks5h://127.0.0.1:9050)。引数なしで使用中のプロキシを表示"),
+    ("Quit", "終了"),
+    ("A command that would ask first, to overwrite a file or run an external program, runs without asking when it ends in !, e.g. :save! to overwrite.", "ファイルの上書きや外部プログラムの実行の前に確認するコマンドは、末尾に ! を付けると確認せずに実行します (例: :save! で上書き)。"),
+    ("Language of messages and help: en or ja (VOYAGER_LOCALE, else LC_ALL, LC_MESSAGES or LANG)", "メッセージとヘルプの言語: en か ja (VOYAGER_LOCALE、なければ LC_ALL、LC_MESSAGES、LANG)"),
+    ("default, or <name> for themes/<name>.yaml in the config directory: a base16 scheme or role: color lines (VOYAGER_THEME)", "default、または設定ディレクトリの themes/<name>.yaml を使う <name>: base16 のスキームか「役割: 色」の行 (VOYAGER_THEME)"),
+    ("Colors the terminal shows: auto (COLORTERM, terminfo), truecolor, 256, 16 or none; theme colors are mapped to fit (VOYAGER_COLORS)", "端末で表示できる色: auto (COLORTERM, terminfo)、truecolor、256、16、none。テーマの色はこれに合わせて変換 (VOYAGER_COLORS)"),
+    ("Terminal background the colors suit: auto (asked at startup), dark or light (VOYAGER_BACKGROUND)", "配色を合わせる端末の背景: auto (起動時に問い合わせ)、dark、light (VOYAGER_BACKGROUND)"),
+    ("How image previews are drawn: auto (from TERM and the terminal's own variables), kitty, sixel or none for ASCII art (VOYAGER_GRAPHICS)", "画像プレビューの表示方法: auto (TERM と端末固有の環境変数から判定)、kitty、sixel、none (AA) (VOYAGER_GRAPHICS)"),
+    ("Show [N] labels before links", "リンクの前に [N] のラベルを表示"),
+    ("inline: [N] before each link; footnote: a superscript number after it and the URLs listed at the end", "inline: 各リンクの前に [N]、footnote: リンクの後に上付きの番号を付け、URL を末尾に一覧表示"),
+    ("Show each link's URL, dimmed, after its text", "各リンクのテキストの後に URL を薄く表示"),
+    ("Show image placeholders", "画像のプレースホルダを表示"),
+    ("Low-bandwidth mode: ASCII borders and glyphs, no images or OSC 8 hyperlinks, a short status line (--ascii, VOYAGER_ASCII)", "低帯域モード: 枠と記号を ASCII にし、画像と OSC 8 ハイパーリンクを使わず、ステータス行を短くする (--ascii, VOYAGER_ASCII)"),
+    ("Emit OSC 8 terminal hyperlinks", "OSC 8 の端末ハイパーリンクを出力"),
+    ("Append the next page on reaching the end", "末尾に着いたら次のページを追加"),
+    ("Lines j / k scroll per press (VOYAGER_SCROLL)", "j / k を 1 回押したときにスクロールする行数 (VOYAGER_SCROLL)"),
+    ("Lines the mouse wheel scrolls per notch (VOYAGER_WHEEL)", "マウスホイール 1 目盛りでスクロールする行数 (VOYAGER_WHEEL)"),
+    ("Animate scrolling over a few frames instead of jumping (VOYAGER_SMOOTH)", "スクロールを一気に飛ばさず数フレームかけて動かす (VOYAGER_SMOOTH)"),
+    ("Load bodies larger than this many KB in chunks, 0 for never", "この KB 数より大きい本文は分割して読み込む (0 で分割しない)"),
+    ("Ask before downloading bodies larger than this many MB, 0 for never", "この MB 数より大きい本文はダウンロード前に確認 (0 で確認しない)"),
+    ("auto (from HTTPS_PROXY / ALL_PROXY), none, tor, or a proxy URL: http://, socks5://, or socks5h:// to resolve names through the proxy (VOYAGER_PROXY)", "auto (HTTPS_PROXY / ALL_PROXY から)、none、tor、またはプロキシの URL: http://、socks5://、名前解決もプロキシで行う socks5h:// (VOYAGER_PROXY)"),
+    ("User-Agent sent to every site, or default; voyager.toml can set it and other headers per site (VOYAGER_USER_AGENT)", "すべてのサイトに送る User-Agent (default で既定値)。voyager.toml でサイトごとに他のヘッダーとあわせて設定可能 (VOYAGER_USER_AGENT)"),
+    ("Redirects to follow per load, 0 for none; a redirect past this is shown as a page with a link on (VOYAGER_REDIRECTS)", "1 回の読み込みでたどるリダイレクトの数 (0 でたどらない)。これを超えるリダイレクトはリンク付きのページとして表示 (VOYAGER_REDIRECTS)"),
+    ("Minutes between checks of watched reading list pages and bookmarks, 0 for off", "監視中のリーディングリストのページとブックマークを確認する間隔 (分、0 でオフ)"),
+    ("Show where short links (bit.ly, t.co, ...) lead before following them", "短縮リンク (bit.ly, t.co など) は開く前に行き先を表示"),
+    ("Rewrite AMP pages to the publisher's canonical URL", "AMP ページを発行元の正規 URL に書き換える"),
+    ("Comma-separated sites whose AMP pages are kept", "AMP ページのままにするサイト (カンマ区切り)"),
+    ("<site>=<host>: alternative front end offered for JavaScript-only pages", "<site>=<host>: JavaScript が必要なページに提案する代替フロントエンド"),
+    ("Blank columns left and right of the text: <n> or <left>,<right> (VOYAGER_MARGIN)", "本文の左右の空白の桁数: <n> または <左>,<右> (VOYAGER_MARGIN)"),
+    ("Blank lines between paragraphs, or auto to keep the page's (VOYAGER_SPACING)", "段落の間の空行の数。auto でページのまま (VOYAGER_SPACING)"),
+    ("Indent the first line of each paragraph by this many columns (VOYAGER_INDENT)", "各段落の 1 行目をこの桁数だけ字下げ (VOYAGER_INDENT)"),
+    ("Search URL template, {} is the query", "検索 URL のテンプレート。{} が検索語になります"),
+    ("Target language for :translate", ":translate の翻訳先の言語"),
+    ("libretranslate:<url>, deepl:<key> or cmd:<command>", "libretranslate:<url>、deepl:<key>、cmd:<command>"),
+    ("dict:<host>, web:<url template> or cmd:<command>", "dict:<host>、web:<URL テンプレート>、cmd:<command>"),
+    ("Command that plays audio and video links, given the URL, e.g. mpv (VOYAGER_PLAYER)", "音声と動画のリンクを再生するコマンド。URL を渡します (例: mpv) (VOYAGER_PLAYER)"),
+    ("HTTP version: auto, 1.1, 2 or 3", "HTTP バージョン: auto、1.1、2、3"),
+    ("Write files without asking first", "確認せずにファイルを書き込む"),
+    ("Run external translator/dictionary commands without asking", "外部の翻訳 / 辞書コマンドを確認せずに実行"),
+    ("server certificate", "サーバー証明書"),
+    ("last certificate sent", "送られた最後の証明書"),
+    ("intermediate", "中間証明書"),
+    ("Local page, no network connection", "ローカルのページ (ネットワーク接続なし)"),
+    ("Unencrypted HTTP", "暗号化されていない HTTP"),
+    ("HTTPS with a verified certificate", "検証済みの証明書による HTTPS"),
+    ("HTTPS, but not fetched over a verified connection (saved copy)", "HTTPS ですが、検証済みの接続で取得していません (保存したコピー)"),
+    ("y: yes   n / Enter / Esc: no", "y: はい   n / Enter / Esc: いいえ"),
+    ("Enter: submit   Esc: cancel", "Enter: 決定   Esc: キャンセル"),
+    ("j / k or 1-9: choose   Enter: confirm   Esc: cancel", "j / k か 1-9: 選択   Enter: 決定   Esc: キャンセル"),
+    ("text", "テキスト"),
+    ("link URL", "リンクの URL"),
+    ("page URL", "ページの URL"),
+    ("Session autosave failed: {}", "セッションの自動保存に失敗しました: {}"),
+    ("Voyager didn't exit cleanly last time.\nRestore that session ({} tabs, {} pages of history, at {})?", "前回の Voyager は正常に終了しませんでした。\nそのセッション (タブ {} 個、履歴 {} ページ、{} を表示中) を復元しますか？"),
+    ("Restore Session", "セッションの復元"),
+    ("restore-session: no session to restore", "restore-session: 復元するセッションはありません"),
+    ("Restored {} tabs, {} pages of history", "タブ {} 個、履歴 {} ページを復元しました"),
+];
@@ -0,0 +1,199 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+
+const MAX_PAGES: usize = 500;
+pub const MAX_TEXT_CHARS: usize = 20_000;
+const SNIPPET_RADIUS: usize = 60;
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct IndexedPage {
+    pub url: String,
+    #[serde(default)]
+    pub title: String,
+    pub text: String,
+}
+
+pub struct SearchHit {
+    pub url: String,
+    pub title: String,
+    pub snippet: String,
+    pub score: u32,
+}
+
+// Inverted index over visited pages, persisted as an append-only JSON lines file.
+pub struct SearchIndex {
+    path: PathBuf,
+    pages: Vec<IndexedPage>,
+    postings: HashMap<String, HashMap<usize, u32>>,
+    by_url: HashMap<String, usize>,
+    // When each page was last indexed, parallel to `pages`; the one seen longest ago is evicted
+    last_visit: Vec<u64>,
+    visits: u64,
+}
+
+fn tokenize(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|t| !t.is_empty())
+        .map(|t| t.to_lowercase())
+}
+
+impl SearchIndex {
+    pub fn load(path: PathBuf) -> Self {
+        let mut index = Self { path, pages: Vec::new(), postings: HashMap::new(), by_url: HashMap::new(), last_visit: Vec::new(), visits: 0 };
+        let mut lines = 0;
+        if let Ok(data) = fs::read_to_string(&index.path) {
+            for line in data.lines() {
+                if let Ok(page) = serde_json::from_str::<IndexedPage>(line) {
+                    index.insert(page);
+                    lines += 1;
+                }
+            }
+        }
+        if lines > index.pages.len() * 2 {
+            index.compact();
+        }
+        index
+    }
+
+    // Lines in the file are in visit order, so loading them replays the visits too
+    fn insert(&mut self, page: IndexedPage) {
+        self.visits += 1;
+        if let Some(&idx) = self.by_url.get(&page.url) {
+            self.remove_postings(idx);
+            self.pages[idx] = page;
+            self.last_visit[idx] = self.visits;
+            self.add_postings(idx);
+        } else {
+            if self.pages.len() >= MAX_PAGES {
+                self.evict_oldest();
+            }
+            self.by_url.insert(page.url.clone(), self.pages.len());
+            self.pages.push(page);
+            self.last_visit.push(self.visits);
+            self.add_postings(self.pages.len() - 1);
+        }
+    }
+
+    fn add_postings(&mut self, idx: usize) {
+        let page = &self.pages[idx];
+        for term in tokenize(&page.title).chain(tokenize(&page.text)).collect::<Vec<_>>() {
+            *self.postings.entry(term).or_default().entry(idx).or_insert(0) += 1;
+        }
+    }
+
+    fn remove_postings(&mut self, idx: usize) {
+        let page = &self.pages[idx];
+        for term in tokenize(&page.title).chain(tokenize(&page.text)).collect::<Vec<_>>() {
+            if let Some(docs) = self.postings.get_mut(&term) {
+                docs.remove(&idx);
+                if docs.is_empty() {
+                    self.postings.remove(&term);
+                }
+            }
+        }
+    }
+
+    // The last page takes the evicted one's slot, so only its postings have to be renumbered
+    fn evict_oldest(&mut self) {
+        let Some(idx) = (0..self.pages.len()).min_by_key(|&i| self.last_visit[i]) else { return };
+        self.remove_postings(idx);
+        let last = self.pages.len() - 1;
+        if idx != last {
+            self.move_postings(last, idx);
+        }
+        let evicted = self.pages.swap_remove(idx);
+        self.last_visit.swap_remove(idx);
+        self.by_url.remove(&evicted.url);
+        if let Some(moved) = self.pages.get(idx) {
+            self.by_url.insert(moved.url.clone(), idx);
+        }
+    }
+
+    fn move_postings(&mut self, from: usize, to: usize) {
+        let page = &self.pages[from];
+        for term in tokenize(&page.title).chain(tokenize(&page.text)).collect::<Vec<_>>() {
+            if let Some(docs) = self.postings.get_mut(&term)
+                && let Some(count) = docs.remove(&from)
+            {
+                docs.insert(to, count);
+            }
+        }
+    }
+
+    // Written oldest visit first, as the appended lines were
+    fn compact(&mut self) {
+        let mut order: Vec<usize> = (0..self.pages.len()).collect();
+        order.sort_by_key(|&i| self.last_visit[i]);
+        let body: String = order.iter()
+            .map(|&i| &self.pages[i])
+            .filter_map(|p| serde_json::to_string(p).ok())
+            .map(|l| l + "\n")
+            .collect();
+        let _ = fs::write(&self.path, body);
+    }
+
+    pub fn text_of(&self, url: &str) -> Option<&str> {
+        self.by_url.get(url).map(|&idx| self.pages[idx].text.as_str())
+    }
+
+    pub fn add_page(&mut self, url: &str, title: &str, text: &str) {
+        let text: String = text.chars().take(MAX_TEXT_CHARS).collect();
+        let page = IndexedPage { url: url.to_string(), title: title.to_string(), text };
+        if let Some(dir) = self.path.parent() {
+            let _ = fs::create_dir_all(dir);
+        }
+        if let (Ok(line), Ok(mut file)) = (serde_json::to_string(&page), OpenOptions::new().create(true).append(true).open(&self.path)) {
+            let _ = writeln!(file, "{}", line);
+        }
+        self.insert(page);
+    }
+
+    pub fn search(&self, query: &str) -> Vec<SearchHit> {
+        let terms: Vec<String> = tokenize(query).collect();
+        if terms.is_empty() {
+            return Vec::new();
+        }
+        let mut scores: Option<HashMap<usize, u32>> = None;
+        for term in &terms {
+            let docs = match self.postings.get(term) {
+                Some(d) => d,
+                None => return Vec::new(),
+            };
+            scores = Some(match scores {
+                None => docs.clone(),
+                Some(prev) => prev.into_iter()
+                    .filter_map(|(idx, s)| docs.get(&idx).map(|n| (idx, s + n)))
+                    .collect(),
+            });
+        }
+        let mut hits: Vec<SearchHit> = scores.unwrap_or_default().into_iter()
+            .map(|(idx, score)| SearchHit {
+                url: self.pages[idx].url.clone(),
+                title: self.pages[idx].title.clone(),
+                snippet: snippet(&self.pages[idx].text, &terms[0]),
+                score,
+            })
+            .collect();
+        hits.sort_by(|a, b| b.score.cmp(&a.score).then_with(|| a.url.cmp(&b.url)));
+        hits
+    }
+}
+
+fn snippet(text: &str, term: &str) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let lower: Vec<char> = text.to_lowercase().chars().collect();
+    let needle: Vec<char> = term.chars().collect();
+    // to_lowercase can change the char count for some scripts; fall back to the start in that case
+    let pos = if lower.len() == chars.len() {
+        lower.windows(needle.len()).position(|w| w == needle.as_slice()).unwrap_or(0)
+    } else {
+        0
+    };
+    let start = pos.saturating_sub(SNIPPET_RADIUS);
+    let end = (pos + needle.len() + SNIPPET_RADIUS).min(chars.len());
+    let body: String = chars[start..end].iter().collect::<String>().split_whitespace().collect::<Vec<_>>().join(" ");
+    format!("{}{}{}", if start > 0 { "..." } else { "" }, body, if end < chars.len() { "..." } else { "" })
+}
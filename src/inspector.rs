@@ -0,0 +1,132 @@
+use crate::app::App;
+use crate::dom;
+use crate::i18n::tr;
+use crossterm::event::KeyCode;
+use markup5ever_rcdom::{Handle, NodeData};
+
+pub struct InspectNode {
+    pub depth: usize,
+    pub label: String,
+    pub attrs: Vec<(String, String)>,
+    pub text: String,
+    // Index one past this node's last descendant in `Inspector::nodes`
+    pub subtree_end: usize,
+    pub expanded: bool,
+}
+
+pub struct Inspector {
+    pub nodes: Vec<InspectNode>,
+    pub selected: usize,
+}
+
+fn label(handle: &Handle) -> String {
+    let tag = dom::tag_name(handle).unwrap_or_default();
+    let mut label = tag;
+    if let Some(id) = dom::attr(handle, "id") {
+        label.push_str(&format!("#{}", id));
+    }
+    if let Some(class) = dom::attr(handle, "class") {
+        for c in class.split_whitespace() {
+            label.push_str(&format!(".{}", c));
+        }
+    }
+    label
+}
+
+fn build(handle: &Handle, depth: usize, nodes: &mut Vec<InspectNode>) {
+    let idx = nodes.len();
+    let attrs = match handle.data {
+        NodeData::Element { ref attrs, .. } => attrs.borrow().iter().map(|a| (a.name.local.to_string(), a.value.to_string())).collect(),
+        _ => Vec::new(),
+    };
+    let text = dom::text_content(handle).split_whitespace().collect::<Vec<_>>().join(" ");
+    nodes.push(InspectNode { depth, label: label(handle), attrs, text, subtree_end: 0, expanded: depth < 2 });
+    for child in handle.children.borrow().iter() {
+        if let NodeData::Element { .. } = child.data {
+            build(child, depth + 1, nodes);
+        }
+    }
+    nodes[idx].subtree_end = nodes.len();
+}
+
+impl Inspector {
+    pub fn from_html(html: &str) -> Self {
+        let document = dom::parse(html);
+        let mut nodes = Vec::new();
+        for child in document.document.children.borrow().iter() {
+            if let NodeData::Element { .. } = child.data {
+                build(child, 0, &mut nodes);
+            }
+        }
+        Self { nodes, selected: 0 }
+    }
+
+    // Indices of nodes whose ancestors are all expanded, in display order
+    pub fn visible(&self) -> Vec<usize> {
+        let mut out = Vec::new();
+        let mut i = 0;
+        while i < self.nodes.len() {
+            out.push(i);
+            i = if self.nodes[i].expanded { i + 1 } else { self.nodes[i].subtree_end };
+        }
+        out
+    }
+
+    pub fn selected_node(&self) -> Option<&InspectNode> {
+        self.visible().get(self.selected).map(|&i| &self.nodes[i])
+    }
+
+    fn set_expanded(&mut self, expanded: bool) {
+        if let Some(&i) = self.visible().get(self.selected) && self.nodes[i].subtree_end > i + 1 {
+            self.nodes[i].expanded = expanded;
+        }
+    }
+}
+
+impl App {
+    pub fn open_inspector(&mut self) {
+        let inspector = Inspector::from_html(&self.raw_html);
+        self.status = tr!("Inspector: {} elements (l/h expand/collapse, Enter jump, Esc close)", inspector.nodes.len());
+        self.inspector = Some(inspector);
+    }
+
+    fn jump_to_text(&mut self, text: &str) {
+        let needle: String = text.chars().take(40).collect();
+        if needle.is_empty() {
+            self.status = tr!("Node has no text to locate");
+            return;
+        }
+        let found = self.content_lines.iter().position(|l| {
+            let line: String = l.spans.iter().map(|s| s.content.as_ref()).collect();
+            let line = line.split_whitespace().collect::<Vec<_>>().join(" ");
+            line.contains(&needle) || (line.len() > 8 && needle.contains(line.as_str()))
+        });
+        match found {
+            Some(line) => {
+                self.scroll = line as u16;
+                self.status = tr!("Jumped to line {}", line + 1);
+            }
+            None => self.status = tr!("Node text not found in rendered view"),
+        }
+    }
+
+    pub fn handle_inspector_key(&mut self, code: KeyCode) {
+        let Some(inspector) = self.inspector.as_mut() else { return };
+        let visible = inspector.visible().len();
+        match code {
+            KeyCode::Esc | KeyCode::Char('q') => {
+                self.inspector = None;
+                self.status = tr!("Inspector closed");
+            }
+            KeyCode::Char('j') | KeyCode::Down => inspector.selected = (inspector.selected + 1).min(visible.saturating_sub(1)),
+            KeyCode::Char('k') | KeyCode::Up => inspector.selected = inspector.selected.saturating_sub(1),
+            KeyCode::Char('l') | KeyCode::Right => inspector.set_expanded(true),
+            KeyCode::Char('h') | KeyCode::Left => inspector.set_expanded(false),
+            KeyCode::Enter => {
+                let text = inspector.selected_node().map(|n| n.text.clone()).unwrap_or_default();
+                self.jump_to_text(&text);
+            }
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,81 @@
+use crate::app::App;
+use crate::har::{Incoming, Outgoing};
+use crate::net::Net;
+use crate::types::ToastKind;
+use std::time::{Duration, Instant};
+
+// A response read on a job's task, logged by the event loop once it has been used
+pub struct Received {
+    pub outgoing: Outgoing,
+    pub incoming: Incoming,
+    pub ttfb: Duration,
+    pub receive: Duration,
+    pub body: Vec<u8>,
+}
+
+// Sends the request and reads the body, unless only the headers are wanted
+pub async fn receive(net: &Net, builder: reqwest::RequestBuilder, read_body: bool) -> Result<Received, String> {
+    let req = net.build(builder).map_err(|e| e.to_string())?;
+    let outgoing = Outgoing::capture(&req);
+    let res = net.execute(req, &outgoing.probe).await.map_err(|e| e.to_string())?;
+    let ttfb = outgoing.started.elapsed();
+    let incoming = Incoming::capture(&res);
+    let body_start = Instant::now();
+    let body = match read_body {
+        true => res.bytes().await.map_err(|e| e.to_string())?.to_vec(),
+        false => Vec::new(),
+    };
+    Ok(Received { outgoing, incoming, ttfb, receive: body_start.elapsed(), body })
+}
+
+// What a key or command that waits on the network hands back from its task. Each is shown by
+// the module it came from once `drain_jobs` picks it up.
+pub enum Job {
+    Image { url: String, result: Result<Received, String> },
+    // `page` is the address translated, so a result for a page since left is dropped
+    Translation { page: String, lang: String, selection: bool, result: Result<String, String> },
+    Definition { word: String, result: Result<String, String> },
+    Headers { method: reqwest::Method, url: String, result: Result<Received, String> },
+    ShortLink { url: String, result: Result<Option<String>, String> },
+}
+
+impl App {
+    // Runs `job` on its own task, the way page loads are, so the event loop keeps going
+    pub fn spawn_job(&self, job: impl Future<Output = Job> + Send + 'static) {
+        let tx = self.job_tx.clone();
+        tokio::spawn(async move {
+            let _ = tx.send(job.await);
+        });
+    }
+
+    // Called every tick from the event loop
+    pub async fn drain_jobs(&mut self) {
+        while let Ok(job) = self.job_rx.try_recv() {
+            let failed = match job {
+                Job::Image { url, result } => match result {
+                    Ok(received) => self.show_image(received).err().map(|e| e.to_string()),
+                    Err(e) => Some(format!("{}: {}", url, e)),
+                },
+                Job::Translation { page, lang, selection, result } => match result {
+                    Ok(text) => { self.show_translation(&page, &lang, selection, text); None }
+                    Err(e) => Some(format!("translate: {}", e)),
+                },
+                Job::Definition { word, result } => match result {
+                    Ok(text) => { self.show_definition(&word, &text); None }
+                    Err(e) => Some(format!("define: {}", e)),
+                },
+                Job::Headers { method, url, result } => match result {
+                    Ok(received) => { self.show_header_lines(method, &url, received); None }
+                    Err(e) => Some(format!("{}: {}", method.as_str().to_ascii_lowercase(), e)),
+                },
+                Job::ShortLink { url, result } => match result {
+                    Ok(target) => self.show_short_link(url, target).await.err().map(|e| e.to_string()),
+                    Err(e) => Some(format!("{}: {}", url, e)),
+                },
+            };
+            if let Some(e) = failed {
+                self.notify(ToastKind::Error, e);
+            }
+        }
+    }
+}
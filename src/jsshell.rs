@@ -0,0 +1,60 @@
+use crate::app::App;
+use crate::dialog::DialogKind;
+use crate::i18n::tr;
+use std::error::Error;
+use url::Url;
+
+// Fewer words than this, with scripts, and a <noscript> or empty mount point: a JS shell
+const SHELL_MAX_WORDS: usize = 50;
+
+impl App {
+    pub fn is_js_shell(&self) -> bool {
+        let info = &self.page_info;
+        self.stats.words < SHELL_MAX_WORDS && info.scripts > 0 && (!info.noscript.is_empty() || info.app_root)
+    }
+
+    // The configured alternative front end for this page's host, as a full URL
+    pub fn frontend_url(&self) -> Option<String> {
+        let mut url = Url::parse(&self.current_url).ok()?;
+        let host = url.host_str()?.trim_start_matches("www.").to_string();
+        let (_, alt) = self.settings.frontends.iter().find(|(site, _)| *site == host)?;
+        url.set_host(Some(alt)).ok()?;
+        Some(url.to_string())
+    }
+
+    pub fn wayback_url(&self) -> String {
+        format!("https://web.archive.org/web/2/{}", self.current_url)
+    }
+
+    // Offers the ways to read the page without JavaScript, each on a number key
+    pub fn check_js_shell(&mut self) {
+        if !self.is_js_shell() || self.current_url.starts_with("about:") || self.current_url.starts_with("file:") {
+            return;
+        }
+        let mut options = Vec::new();
+        if !self.page_info.noscript.is_empty() {
+            options.push((tr!("Show the <noscript> content"), "noscript".to_string()));
+        }
+        if let Some(alt) = self.frontend_url() {
+            options.push((tr!("Open {}", alt), "frontend".to_string()));
+        }
+        options.push((tr!("Load the Wayback Machine snapshot"), "wayback".to_string()));
+        options.push((tr!("Keep this page"), String::new()));
+        let message = tr!("This page seems to need JavaScript to show its content.");
+        self.open_dialog("JavaScript Page", message, DialogKind::Choice { options, selected: 0 });
+    }
+
+    pub fn show_noscript(&mut self) -> Result<(), Box<dyn Error>> {
+        let fragments = self.page_info.noscript.clone();
+        let base = Url::parse(&self.current_url)?;
+        let html = fragments.join("<hr>");
+        let (lines, links) = self.layout_html(&html, &base, 0);
+        if lines.is_empty() {
+            self.status = tr!("noscript: the page has no <noscript> content");
+            return Ok(());
+        }
+        self.show_view(crate::types::ViewKind::Extract, lines, links);
+        self.status = tr!("<noscript> content — Esc to return to the page");
+        Ok(())
+    }
+}
@@ -0,0 +1,262 @@
+use crate::app::App;
+use crate::cache::{looks_binary, Body};
+use crate::chunked::PartialBody;
+use crate::har::{Incoming, Outgoing};
+use crate::i18n::tr;
+use crate::net::Net;
+use crate::redirect::{self, Hop, RedirectLog};
+use crate::stream::is_streaming;
+use crate::timing::Probe;
+use crate::types::ToastKind;
+use crate::ui::format_size;
+use std::error::Error;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::task::JoinHandle;
+use url::Url;
+
+// Written by the fetch task, read by the status bar
+#[derive(Default)]
+struct Progress {
+    connected: AtomicBool,
+    received: AtomicUsize,
+    // From Content-Length; 0 when the server didn't say
+    total: AtomicUsize,
+}
+
+// What the fetch task hands back, with the redirects it went through. Bodies that are
+// streamed, announced larger than the chunk size or held for confirmation come back unread,
+// for `render_response` to deal with as before. One that turns out larger than the chunk size
+// as it arrives comes back as far as it got. An about: page that has to fetch something to be
+// built comes back as its finished HTML.
+pub enum Fetched {
+    Complete { incoming: Incoming, body: Body, ttfb: Duration, receive: Duration, url: Url, redirects: Vec<Hop> },
+    Partial { incoming: Incoming, partial: PartialBody, ttfb: Duration, receive: Duration, url: Url, redirects: Vec<Hop> },
+    Open(reqwest::Response, Vec<Hop>),
+    Internal(Url, String),
+}
+
+impl Fetched {
+    // The address the response came from, and the hops that led there
+    fn redirected(&self) -> (&Url, &[Hop]) {
+        match self {
+            Fetched::Complete { url, redirects, .. } | Fetched::Partial { url, redirects, .. } => (url, redirects),
+            Fetched::Open(res, redirects) => (res.url(), redirects),
+            Fetched::Internal(url, _) => (url, &[]),
+        }
+    }
+}
+
+// A page load running on its own task while the event loop keeps going
+pub struct Loading {
+    id: u64,
+    url: String,
+    started: Instant,
+    progress: Arc<Progress>,
+    task: JoinHandle<()>,
+    outgoing: Outgoing,
+    base_url: Url,
+}
+
+impl Loading {
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    pub fn abort(self) {
+        self.task.abort();
+    }
+}
+
+async fn fetch(net: Net, req: reqwest::Request, probe: Probe, log: RedirectLog, progress: Arc<Progress>, chunk_limit: usize, size_limit: u64) -> reqwest::Result<Fetched> {
+    let started = Instant::now();
+    let requested = req.url().to_string();
+    let mut res = net.execute(req, &probe).await?;
+    let ttfb = started.elapsed();
+    let redirects = log.take(&requested, res.url().as_str());
+    let incoming = Incoming::capture(&res);
+    let len = res.content_length();
+    progress.total.store(len.unwrap_or(0) as usize, Ordering::Relaxed);
+    progress.connected.store(true, Ordering::Relaxed);
+    let held = size_limit > 0 && len.is_some_and(|l| l > size_limit);
+    let can_chunk = chunk_limit > 0 && incoming.status < 300 && !incoming.mime_type.to_ascii_lowercase().starts_with("image/");
+    let chunked = can_chunk && len.is_some_and(|l| l as usize > chunk_limit);
+    if held || chunked || is_streaming(&res, &incoming.mime_type) {
+        return Ok(Fetched::Open(res, redirects));
+    }
+    let body_start = Instant::now();
+    let mut bytes = Vec::new();
+    while let Some(chunk) = res.chunk().await? {
+        bytes.extend_from_slice(&chunk);
+        progress.received.store(bytes.len(), Ordering::Relaxed);
+        // Without a Content-Length the size is only known by reading, so the cut is made here
+        if can_chunk && bytes.len() > chunk_limit && !looks_binary(&bytes) {
+            let url = res.url().clone();
+            let partial = PartialBody::new(url.as_str(), &incoming.mime_type, bytes, res);
+            return Ok(Fetched::Partial { incoming, partial, ttfb, receive: body_start.elapsed(), url, redirects });
+        }
+    }
+    let body = Body::from_bytes(&incoming.mime_type, bytes);
+    Ok(Fetched::Complete { incoming, body, ttfb, receive: body_start.elapsed(), url: res.url().clone(), redirects })
+}
+
+impl App {
+    // Sends the request on a background task; the page is rendered by `drain_load` once it's in.
+    // A load already running is dropped.
+    pub fn start_fetch(&mut self, builder: reqwest::RequestBuilder, base_url: Url) -> Result<(), Box<dyn Error>> {
+        let net = self.network()?.clone();
+        let req = net.build(builder)?;
+        if let Some(old) = self.loading.take() {
+            old.task.abort();
+        }
+        let outgoing = Outgoing::capture(&req);
+        let progress = Arc::new(Progress::default());
+        let (chunk_limit, size_limit) = (self.chunk_limit(), self.size_limit());
+        self.next_load_id += 1;
+        let id = self.next_load_id;
+        let (tx, log) = (self.load_tx.clone(), self.redirect_log.clone());
+        let (task_progress, probe) = (progress.clone(), outgoing.probe.clone());
+        let task = tokio::spawn(async move {
+            let result = fetch(net, req, probe, log, task_progress, chunk_limit, size_limit).await.map_err(|e| e.to_string());
+            let _ = tx.send((id, result));
+        });
+        self.loading = Some(Loading { id, url: outgoing.url.clone(), started: Instant::now(), progress, task, outgoing, base_url });
+        Ok(())
+    }
+
+    // An about: page built on a task, like about:meta, which queries the search engines. It is
+    // cancelled and handed back the way a fetched page is.
+    pub fn start_internal(&mut self, url: Url, page: impl Future<Output = String> + Send + 'static) {
+        if let Some(old) = self.loading.take() {
+            old.task.abort();
+        }
+        let outgoing = Outgoing::capture(&reqwest::Request::new(reqwest::Method::GET, url.clone()));
+        self.next_load_id += 1;
+        let id = self.next_load_id;
+        let (tx, task_url) = (self.load_tx.clone(), url.clone());
+        let task = tokio::spawn(async move {
+            let html = page.await;
+            let _ = tx.send((id, Ok(Fetched::Internal(task_url, html))));
+        });
+        let progress = Arc::new(Progress::default());
+        self.loading = Some(Loading { id, url: outgoing.url.clone(), started: Instant::now(), progress, task, outgoing, base_url: url });
+    }
+
+    // A page that comes from something other than a GET of its address: a form post or a
+    // composed request. It goes into the history like any other.
+    pub fn load_request(&mut self, url: &str, builder: reqwest::RequestBuilder) -> Result<(), Box<dyn Error>> {
+        let base_url = Url::parse(url)?;
+        // Checked before the history moves, so a refused load leaves the page as it was
+        self.network()?;
+        self.push_history(url);
+        self.current_url = url.to_string();
+        self.pending_refresh = None;
+        self.load_chain = vec![url.to_string()];
+        self.start_fetch(builder, base_url)
+    }
+
+    // Esc / Ctrl-C while loading: stops the request and goes back to the page still on screen
+    pub fn cancel_load(&mut self) {
+        let Some(loading) = self.loading.take() else { return };
+        loading.task.abort();
+        let (url, node) = self.shown.clone();
+        self.current_url = url;
+        self.history.current = node;
+        self.load_chain.clear();
+        self.restore_on_load = None;
+        self.status = tr!("Stopped loading {}", loading.url);
+    }
+
+    // Called every tick from the event loop: renders a load that has finished
+    pub async fn drain_load(&mut self) {
+        while let Ok((id, result)) = self.load_rx.try_recv() {
+            if let Some(loading) = self.loading.take_if(|l| l.id == id) {
+                if let Err(e) = self.finish_fetch(loading, result).await {
+                    self.notify(ToastKind::Error, tr!("Fetch failed: {}", e));
+                }
+                continue;
+            }
+            // A background tab's load is rendered into that tab. A load that was cancelled or
+            // replaced can still have sent its result.
+            let Some(idx) = self.tab_loading(id) else { continue };
+            let peek = self.peek_tab(idx);
+            let failed = match self.loading.take() {
+                Some(loading) => self.finish_fetch(loading, result).await.err(),
+                None => None,
+            };
+            self.unpeek_tab(peek);
+            if let Some(e) = failed {
+                self.notify(ToastKind::Error, tr!("Tab {}: fetch failed: {}", idx + 1, e));
+            }
+        }
+    }
+
+    async fn finish_fetch(&mut self, loading: Loading, result: Result<Fetched, String>) -> Result<(), Box<dyn Error>> {
+        let fetched = result?;
+        // After redirects the page is where they ended, and its links are relative to that
+        let (url, redirects) = fetched.redirected();
+        // A redirect to another host stopped so this one's site headers don't go along: followed
+        // here as a GET of its own, which gets the new host's headers. 307 and 308 keep the method,
+        // so only a GET is sent on for those; otherwise the redirect page says why it stopped.
+        if let Some((to, status)) = self.redirect_log.site_headers_stop(url.as_str())
+            && (loading.outgoing.method == "GET" || matches!(status, 301..=303))
+            && !self.load_chain.contains(&to)
+            && self.load_chain.len() <= self.settings.redirects
+            && let Ok(target) = Url::parse(&to)
+        {
+            self.redirect_log.take_stopped(url.as_str());
+            self.current_url = to.clone();
+            self.load_chain.push(to.clone());
+            let builder = self.network()?.get(&to);
+            return self.start_fetch(builder, target);
+        }
+        let (base_url, chain) = match redirects {
+            [] => (loading.base_url, None),
+            hops => {
+                self.current_url = url.to_string();
+                (url.clone(), Some(redirect::describe(hops, url.as_str())))
+            }
+        };
+        match fetched {
+            Fetched::Internal(_, html) => self.render_html(&html, &base_url),
+            Fetched::Open(res, _) => self.render_response(res, &base_url, loading.outgoing).await?,
+            Fetched::Complete { incoming, body, ttfb, receive, .. } => {
+                self.tls_verified = incoming.tls;
+                let render_start = Instant::now();
+                let rendered = self.render_received(&incoming, &body, &base_url);
+                self.log_exchange(loading.outgoing, incoming, (ttfb, receive, render_start.elapsed()), body.len(), false);
+                rendered?;
+            }
+            Fetched::Partial { incoming, mut partial, ttfb, receive, .. } => {
+                self.tls_verified = incoming.tls;
+                // Made against the address shown, which `:more` checks it still is
+                partial.url = self.current_url.clone();
+                let (render_start, bytes) = (Instant::now(), partial.received.len());
+                let rendered = self.render_partial(partial, &base_url);
+                self.log_exchange(loading.outgoing, incoming, (ttfb, receive, render_start.elapsed()), bytes, false);
+                rendered?;
+            }
+        }
+        if let Some(chain) = chain {
+            self.status = tr!("{} (redirected: {})", self.status, chain);
+        }
+        self.finish_load().await
+    }
+
+    // Status bar text while a load is running
+    pub fn load_progress(&self) -> String {
+        let Some(ref loading) = self.loading else { return String::new() };
+        let p = &loading.progress;
+        let (received, total) = (p.received.load(Ordering::Relaxed), p.total.load(Ordering::Relaxed));
+        if !p.connected.load(Ordering::Relaxed) {
+            // An about: page has no host, so it goes by its address
+            let host = Url::parse(&loading.url).ok().and_then(|u| u.host_str().map(str::to_string)).unwrap_or_else(|| loading.url.clone());
+            return tr!("Waiting for {}... {}s (Esc cancels)", host, loading.started.elapsed().as_secs());
+        }
+        match total {
+            0 => tr!("Loading {}: {} (Esc cancels)", loading.url, format_size(received)),
+            total => tr!("Loading {}: {} of {} (Esc cancels)", loading.url, format_size(received), format_size(total)),
+        }
+    }
+}
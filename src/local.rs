@@ -0,0 +1,49 @@
+use crate::about::escape_html;
+use crate::app::App;
+use crate::cache::Body;
+use std::error::Error;
+use std::path::Path;
+use url::Url;
+
+fn mime_for(path: &Path) -> &'static str {
+    match path.extension().and_then(|e| e.to_str()).map(|e| e.to_ascii_lowercase()).as_deref() {
+        Some("html" | "htm" | "xhtml") => "text/html",
+        Some("xml" | "svg") => "text/xml",
+        Some("png") => "image/png",
+        Some("jpg" | "jpeg") => "image/jpeg",
+        Some("gif") => "image/gif",
+        Some("webp") => "image/webp",
+        _ => "text/plain",
+    }
+}
+
+fn directory_page(dir: &Path) -> Result<String, Box<dyn Error>> {
+    let mut entries: Vec<_> = std::fs::read_dir(dir)?.filter_map(Result::ok).collect();
+    entries.sort_by_key(|e| (!e.path().is_dir(), e.file_name()));
+    let title = escape_html(&dir.display().to_string());
+    let mut html = format!("<title>{0}</title><h1>Index of {0}</h1><ul>", title);
+    if let Some(parent) = dir.parent().and_then(|p| Url::from_directory_path(p).ok()) {
+        html.push_str(&format!("<li><a href=\"{}\">../</a></li>", parent));
+    }
+    for entry in entries {
+        let path = entry.path();
+        let (url, suffix) = if path.is_dir() { (Url::from_directory_path(&path), "/") } else { (Url::from_file_path(&path), "") };
+        let Ok(url) = url else { continue };
+        html.push_str(&format!("<li><a href=\"{}\">{}{}</a></li>", url, escape_html(&entry.file_name().to_string_lossy()), suffix));
+    }
+    html.push_str("</ul>");
+    Ok(html)
+}
+
+impl App {
+    // file:// pages never touch the network, so they load the same way with or without --sandbox
+    pub fn load_file(&mut self, url: &Url) -> Result<(), Box<dyn Error>> {
+        let path = url.to_file_path().map_err(|_| format!("not a local path: {}", url))?;
+        if path.is_dir() {
+            let html = directory_page(&path)?;
+            return self.render_body("text/html", &Body::Text(html), url);
+        }
+        let mime = mime_for(&path);
+        self.render_body(mime, &Body::from_bytes(mime, std::fs::read(&path)?), url)
+    }
+}
@@ -1,69 +1,381 @@
 mod types;
+mod amp;
 mod app;
+mod autofill;
+mod autopage;
+mod batch;
+mod bookmarks;
+mod cache;
+mod caret;
+mod cert;
+mod chunked;
 mod ui;
+mod about;
+mod clipboard;
+mod colors;
+mod commands;
+mod config;
+mod cookies;
+mod dialog;
+mod dictionary;
+mod diff;
+mod dom;
+mod download;
+mod forms;
+mod graphics;
+mod har;
+mod headers;
+mod help;
+mod hints;
+mod highlight;
+mod history;
+mod homograph;
+mod i18n;
+mod index;
+mod inspector;
+mod jobs;
+mod jsshell;
+mod loader;
+mod local;
+mod markdown;
+mod media;
+mod meta;
+mod mirror;
+mod monitor;
+mod net;
+mod page;
+mod pattern;
+mod picker;
+mod proxy;
+mod reader;
+mod readlist;
+mod recovery;
+mod redirect;
+mod registers;
+mod request;
+mod resources;
+mod scroll;
+mod search;
+mod security;
+mod selector;
+mod shortlink;
+mod sitemap;
+mod stats;
+mod storage;
+mod stream;
+mod table;
+mod tabs;
+mod theme;
+mod timing;
+mod tooltip;
+mod translate;
+mod typeset;
+mod urlbar;
+mod visits;
+mod watch;
+mod xpath;
+mod zoom;
 
+use crate::config::Resolved;
+use crate::i18n::tr;
 use crate::types::*;
 use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode},
-    execute,
-    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+    cursor::MoveTo,
+    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyModifiers},
+    execute, queue,
+    style::{Print, PrintStyledContent, Stylize},
+    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen, SetTitle},
 };
-use ratatui::{backend::CrosstermBackend, Terminal};
-use std::{error::Error, io};
+use ratatui::{backend::CrosstermBackend, style::Modifier, Terminal};
+use std::{error::Error, io::{self, Write}, time::{Duration, Instant}};
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn Error>> {
+    // Put the terminal back before the panic message, so it is readable and the shell usable;
+    // the session's last autosave is left for the next start to offer
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let _ = disable_raw_mode();
+        let _ = execute!(io::stdout(), LeaveAlternateScreen, DisableMouseCapture);
+        default_hook(info);
+    }));
     enable_raw_mode()?;
+    let background = theme::detect_background();
     let mut stdout = io::stdout();
+    // Save the terminal's title on its title stack (XTWINOPS) so it can be restored on exit
+    print!("\x1b[22;0t");
     execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
     let mut terminal = Terminal::new(CrosstermBackend::new(stdout))?;
 
-    let mut app = app::App::new("https://www.rust-lang.org");
-    app.fetch_page().await?;
+    let mut sandbox = false;
+    let mut ascii = false;
+    let mut start = None;
+    for arg in std::env::args().skip(1) {
+        match arg.as_str() {
+            "--sandbox" => sandbox = true,
+            "--ascii" => ascii = true,
+            _ => start = Some(app::address_to_url(&arg)),
+        }
+    }
+    let default_start = if sandbox { "about:reading-list" } else { "https://www.rust-lang.org" };
+    let mut app = app::App::new(start.as_deref().unwrap_or(default_start));
+    app.sandbox = sandbox;
+    i18n::set_locale(app.settings.locale);
+    app.settings.ascii |= ascii;
+    app.terminal_background = background;
+    app.load_theme();
+    app.load_config();
+    if let Some(e) = app.bookmarks.unreadable.clone() {
+        app.notify(ToastKind::Error, tr!("Bookmarks not loaded: {}", e));
+    }
+    if let Err(e) = app.fetch_page().await {
+        app.notify(ToastKind::Error, tr!("Fetch failed: {}", e));
+    }
+    app.offer_recovery();
 
+    let mut window_title = String::new();
     loop {
+        app.drain_load().await;
+        app.drain_prefetches();
+        app.drain_exchanges();
+        app.drain_jobs().await;
+        app.drain_downloads();
+        app.drain_stream();
+        app.drain_mirror();
+        app.monitor_watched();
+        app.watch_tick();
+        app.log_status();
+        app.autosave_tick();
+        app.viewport_height = ui::page_area(terminal.size()?, &app).height;
+        let animating = app.animate_scroll();
         terminal.draw(|f| ui::draw(f, &app))?;
+        if app.settings.hyperlinks && !app.settings.ascii && app.popup.is_none() && app.picker.is_none() && app.dialog.is_none() && app.image_preview.is_none() && app.mode != Mode::Caret && app.search.is_none() && app.hints.is_none() && app.inspector.is_none() && !app.network_pane && app.toasts.is_empty() && app.pending_key.is_none() {
+            let screen = terminal.size()?;
+            let out = terminal.backend_mut();
+            for (x, y, span, url) in ui::hyperlink_regions(&app, screen) {
+                let mut styled = span.content.to_string().stylize();
+                if let Some(fg) = span.style.fg { styled = styled.with(fg.into()); }
+                if let Some(bg) = span.style.bg { styled = styled.on(bg.into()); }
+                if span.style.add_modifier.contains(Modifier::BOLD) { styled = styled.bold(); }
+                if span.style.add_modifier.contains(Modifier::ITALIC) { styled = styled.italic(); }
+                if span.style.add_modifier.contains(Modifier::UNDERLINED) { styled = styled.underlined(); }
+                queue!(out, MoveTo(x, y), Print(format!("\x1b]8;;{}\x1b\\", url)), PrintStyledContent(styled), Print("\x1b]8;;\x1b\\"))?;
+            }
+            out.flush()?;
+        }
+        if app.image_preview.is_some() {
+            let area = ui::image_area(terminal.size()?);
+            if let Some(seq) = app.image_escape(area) {
+                let out = terminal.backend_mut();
+                queue!(out, MoveTo(area.x, area.y), Print(seq))?;
+                out.flush()?;
+            }
+        }
+        let title = match app.page_info.title {
+            Some(ref t) => format!("{} — Voyager", t),
+            None => format!("{} — Voyager", app.current_url),
+        };
+        if title != window_title && !app.settings.ascii {
+            execute!(terminal.backend_mut(), SetTitle(&title))?;
+            window_title = title;
+        }
+
+        if let Some((deadline, _)) = &app.pending_refresh && Instant::now() >= *deadline {
+            app.follow_refresh().await?;
+            continue;
+        }
+        app.auto_paginate().await;
+        if !event::poll(if animating { scroll::FRAME } else { Duration::from_millis(250) })? {
+            continue;
+        }
 
-        if let Event::Key(key) = event::read()? {
+        let event = event::read()?;
+        if let Event::Mouse(mouse) = event {
+            if app.mode == Mode::Normal && app.popup.is_none() && app.image_preview.is_none() {
+                app.handle_mouse(mouse);
+            }
+            continue;
+        }
+        if let Event::Key(key) = event {
             if app.image_preview.is_some() {
                 if key.code == KeyCode::Esc {
                     app.image_preview = None;
-                    app.status = "Preview closed".to_string();
+                    // Kitty keeps its images until told otherwise, and sixel pixels stay until
+                    // their cells are written again, which a full redraw does
+                    if app.graphics() == graphics::Graphics::Kitty {
+                        execute!(terminal.backend_mut(), Print(graphics::KITTY_DELETE_ALL))?;
+                    }
+                    terminal.clear()?;
+                    app.status = tr!("Preview closed");
+                }
+                continue;
+            }
+            if let Some(popup) = app.popup.as_mut() {
+                let is_menu = !popup.actions.is_empty();
+                match key.code {
+                    KeyCode::Esc | KeyCode::Char('q') => app.popup = None,
+                    KeyCode::Char('j') | KeyCode::Down if is_menu => popup.selected = (popup.selected + 1).min(popup.actions.len() - 1),
+                    KeyCode::Char('k') | KeyCode::Up if is_menu => popup.selected = popup.selected.saturating_sub(1),
+                    KeyCode::Enter if is_menu => {
+                        let action = popup.actions[popup.selected].clone();
+                        app.popup = None;
+                        app.run_popup_action(action).await?;
+                    }
+                    KeyCode::Char('j') | KeyCode::Down => popup.scroll = popup.scroll.saturating_add(1),
+                    KeyCode::Char('k') | KeyCode::Up => popup.scroll = popup.scroll.saturating_sub(1),
+                    _ => {}
                 }
                 continue;
             }
 
+            if app.inspector.is_some() && app.mode == Mode::Normal && key.code != KeyCode::Char(':') {
+                app.handle_inspector_key(key.code);
+                continue;
+            }
+
+            // voyager.toml bindings stand in for the built-in normal-mode keys
+            let key = match app.mode {
+                Mode::Normal if app.pending_key.is_none() => match app.config.resolve(key) {
+                    Resolved::Key(key) => key,
+                    // A key bound to a prefixed action, like gt: as if the prefix had been typed first
+                    Resolved::Prefixed(prefix, key) => {
+                        app.pending_key = Some(prefix);
+                        key
+                    }
+                    Resolved::Command(cmd) => {
+                        match app.execute_command(&cmd).await {
+                            Ok(true) => break,
+                            Ok(false) => {}
+                            Err(e) => app.notify(ToastKind::Error, format!("{}: {}", cmd, e)),
+                        }
+                        continue;
+                    }
+                },
+                _ => key,
+            };
+
             match app.mode {
+                Mode::Normal if app.pending_key.is_some() => match (app.pending_key.take(), key.code) {
+                    (Some(']'), KeyCode::Char('p')) => app.follow_pagination(true).await?,
+                    (Some('['), KeyCode::Char('p')) => app.follow_pagination(false).await?,
+                    (Some('"'), KeyCode::Char(c)) => app.select_register(c),
+                    (Some('g'), KeyCode::Char('t')) => app.cycle_tab(true),
+                    (Some('g'), KeyCode::Char('T')) => app.cycle_tab(false),
+                    (Some('g'), KeyCode::Char('g')) => app.scroll_to(0),
+                    (Some('g'), KeyCode::Char('s')) => app.toggle_source(),
+                    _ => app.register = None,
+                }
                 Mode::Normal => match key.code {
+                    KeyCode::Esc if app.loading.is_some() => app.cancel_load(),
+                    KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) && app.loading.is_some() => app.cancel_load(),
                     KeyCode::Char(':') => { app.mode = Mode::Command; app.command_buffer.clear(); }
-                    KeyCode::Char('j') => app.scroll = app.scroll.saturating_add(1),
-                    KeyCode::Char('k') => app.scroll = app.scroll.saturating_sub(1),
-                    KeyCode::Char('l') | KeyCode::Tab => if !app.links.is_empty() {
+                    KeyCode::Char('/') => { app.mode = Mode::Search; app.command_buffer.clear(); }
+                    KeyCode::Char('p') if key.modifiers.contains(KeyModifiers::CONTROL) => app.open_command_palette(),
+                    KeyCode::Char('L') => app.open_link_finder(),
+                    KeyCode::Char('o') => app.open_url_bar(false),
+                    KeyCode::Char('O') => app.open_url_bar(true),
+                    KeyCode::Char('?') => app.navigate("about:help".to_string()).await?,
+                    KeyCode::Char('K') => { app.mode = Mode::Command; app.command_buffer = "define ".to_string(); }
+                    KeyCode::Char('R') if app.pending_refresh.is_some() => app.follow_refresh().await?,
+                    KeyCode::Char('X') if app.pending_refresh.is_some() => {
+                        app.pending_refresh = None;
+                        app.status = tr!("Refresh cancelled");
+                    }
+                    KeyCode::Char('+') => app.zoom_by(1),
+                    KeyCode::Char('-') => app.zoom_by(-1),
+                    KeyCode::Char('j') | KeyCode::Down => app.scroll_by(app.settings.scroll_step as i32),
+                    KeyCode::Char('k') | KeyCode::Up => app.scroll_by(-(app.settings.scroll_step as i32)),
+                    KeyCode::Char('d') if key.modifiers.contains(KeyModifiers::CONTROL) => app.scroll_screen(true, true),
+                    KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => app.scroll_screen(true, false),
+                    KeyCode::Char('f') if key.modifiers.contains(KeyModifiers::CONTROL) => app.scroll_screen(false, true),
+                    KeyCode::PageDown => app.scroll_screen(false, true),
+                    KeyCode::Char('b') if key.modifiers.contains(KeyModifiers::CONTROL) => app.scroll_screen(false, false),
+                    KeyCode::PageUp => app.scroll_screen(false, false),
+                    KeyCode::Home => app.scroll_to(0),
+                    KeyCode::Char('G') | KeyCode::End => app.scroll_to(app.max_scroll()),
+                    KeyCode::Char('l') | KeyCode::Tab if !app.links.is_empty() => {
                         app.selected_link_idx = (app.selected_link_idx + 1) % app.links.len();
                     }
-                    KeyCode::Char('h') => if !app.links.is_empty() {
+                    KeyCode::Char('h') if !app.links.is_empty() => {
                         app.selected_link_idx = if app.selected_link_idx == 0 { app.links.len() - 1 } else { app.selected_link_idx - 1 };
                     }
-                    KeyCode::Enter => if !app.links.is_empty() {
-                        let link = app.links[app.selected_link_idx].clone();
-                        if link.link_type == LinkType::Image { app.preview_image(&link.url).await?; }
-                        else { app.navigate(link.url).await?; }
+                    KeyCode::Char('B') | KeyCode::Backspace => if let Err(e) = app.go_back().await {
+                        app.notify(ToastKind::Error, tr!("Fetch failed: {}", e));
+                    }
+                    KeyCode::Char('F') => if let Err(e) = app.go_forward().await {
+                        app.notify(ToastKind::Error, tr!("Fetch failed: {}", e));
+                    }
+                    KeyCode::Char('f') => app.start_hints(),
+                    KeyCode::Char('t') => app.show_titles(),
+                    KeyCode::Char('v') => app.enter_caret(),
+                    KeyCode::Char('\\') => app.toggle_source(),
+                    KeyCode::Char('n') if key.modifiers.contains(KeyModifiers::CONTROL) => app.toggle_network_pane(),
+                    KeyCode::Char('n') if app.search.is_some() => app.next_match(true),
+                    KeyCode::Char('N') if app.search.is_some() => app.next_match(false),
+                    KeyCode::Char(c @ (']' | '[' | '"' | 'g')) => app.pending_key = Some(c),
+                    KeyCode::Char('y') if !app.links.is_empty() => {
+                        let url = app.links[app.selected_link_idx].url.clone();
+                        app.yank(url, "link URL");
+                    }
+                    KeyCode::Char('Y') => app.yank(app.current_url.clone(), "page URL"),
+                    KeyCode::Char('p') => app.open_register().await?,
+                    KeyCode::Esc if app.search.is_some() => {
+                        app.search = None;
+                        app.status = tr!("Search cleared");
+                    }
+                    KeyCode::Esc if app.saved_view.is_some() => {
+                        app.restore_view();
+                        app.status = tr!("Rendered view: {}", app.current_url);
+                    }
+                    KeyCode::Enter if !app.links.is_empty() => {
+                        if let Err(e) = app.follow_link(app.selected_link_idx).await {
+                            app.notify(ToastKind::Error, tr!("Fetch failed: {}", e));
+                        }
                     }
                     _ => {}
                 }
+                Mode::Caret => app.handle_caret_key(key.code).await?,
+                Mode::Hint => app.handle_hint_key(key).await,
+                Mode::Request => app.handle_request_key(key).await?,
+                Mode::Insert => if let Err(e) = app.handle_insert_key(key).await {
+                    app.notify(ToastKind::Error, tr!("Submit failed: {}", e));
+                }
+                Mode::Url => if let Err(e) = app.handle_url_key(key).await {
+                    app.notify(ToastKind::Error, tr!("Fetch failed: {}", e));
+                }
+                Mode::Dialog => if let Some(cmd) = app.handle_dialog_key(key.code) {
+                    match app.run_answer(&cmd).await {
+                        Ok(true) => break,
+                        Ok(false) => {}
+                        Err(e) => app.notify(ToastKind::Error, e.to_string()),
+                    }
+                }
+                Mode::Picker => match app.handle_picker_key(key).await {
+                    Ok(true) => break,
+                    Ok(false) => {}
+                    Err(e) => app.notify(ToastKind::Error, e.to_string()),
+                }
                 Mode::Command => match key.code {
                     KeyCode::Enter => {
                         let cmd = app.command_buffer.clone();
-                        if cmd == "q" { break; }
-                        else if cmd.starts_with("url ") { app.navigate(cmd[4..].to_string()).await?; }
-                        else if cmd == "back" || cmd == "b" {
-                            if let Some(prev) = app.history.pop() {
-                                app.future.push(app.current_url.clone());
-                                app.current_url = prev;
-                                app.fetch_page().await?;
-                            }
-                        }
                         app.mode = Mode::Normal;
+                        let quit = match app.execute_command(cmd.trim()).await {
+                            Ok(quit) => quit,
+                            Err(e) => { app.notify(ToastKind::Error, format!("{}: {}", cmd.trim(), e)); false }
+                        };
+                        app.caret.anchor = None;
+                        if quit { break; }
+                    }
+                    KeyCode::Esc => app.mode = Mode::Normal,
+                    KeyCode::Char(c) => app.command_buffer.push(c),
+                    KeyCode::Backspace => { app.command_buffer.pop(); }
+                    _ => {}
+                }
+                Mode::Search => match key.code {
+                    KeyCode::Enter => {
+                        app.mode = Mode::Normal;
+                        let query = std::mem::take(&mut app.command_buffer);
+                        app.start_search(&query);
                     }
                     KeyCode::Esc => app.mode = Mode::Normal,
                     KeyCode::Char(c) => app.command_buffer.push(c),
@@ -74,7 +386,9 @@ async fn main() -> Result<(), Box<dyn Error>> {
         }
     }
 
+    app.autosave.clear();
     disable_raw_mode()?;
     execute!(terminal.backend_mut(), LeaveAlternateScreen, DisableMouseCapture)?;
+    print!("\x1b[23;0t");
     Ok(())
 }
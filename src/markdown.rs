@@ -0,0 +1,179 @@
+use crate::dom;
+use markup5ever_rcdom::{Handle, NodeData};
+use url::Url;
+
+struct Writer<'a> {
+    out: String,
+    base: &'a Url,
+    list_stack: Vec<Option<usize>>,
+    quote_depth: usize,
+    in_pre: bool,
+}
+
+pub fn from_html(html: &str, base: &Url) -> String {
+    let document = dom::parse(html);
+    let mut w = Writer { out: String::new(), base, list_stack: Vec::new(), quote_depth: 0, in_pre: false };
+    w.children(&document.document);
+    let mut md = String::new();
+    let mut blank = 0;
+    for line in w.out.lines() {
+        let line = line.trim_end();
+        if line.trim_start_matches('>').trim().is_empty() {
+            blank += 1;
+            if blank > 1 { continue; }
+        } else {
+            blank = 0;
+        }
+        md.push_str(line);
+        md.push('\n');
+    }
+    md.trim().to_string() + "\n"
+}
+
+impl Writer<'_> {
+    fn resolve(&self, href: &str) -> String {
+        self.base.join(href).map(|u| u.to_string()).unwrap_or_else(|_| href.to_string())
+    }
+
+    fn newline(&mut self) {
+        self.out.push('\n');
+        for _ in 0..self.quote_depth {
+            self.out.push_str("> ");
+        }
+    }
+
+    fn block(&mut self) {
+        if !self.out.is_empty() && !self.out.ends_with("\n\n") {
+            self.newline();
+            self.newline();
+        }
+    }
+
+    fn children(&mut self, node: &Handle) {
+        for child in node.children.borrow().iter() {
+            self.node(child);
+        }
+    }
+
+    fn inline_text(&self, node: &Handle) -> String {
+        dom::text_content(node).split_whitespace().collect::<Vec<_>>().join(" ")
+    }
+
+    fn node(&mut self, node: &Handle) {
+        match node.data {
+            NodeData::Text { ref contents } => {
+                let text = contents.borrow();
+                if self.in_pre {
+                    self.out.push_str(&text);
+                } else {
+                    let collapsed = text.split_whitespace().collect::<Vec<_>>().join(" ");
+                    let lead = text.starts_with(char::is_whitespace) && !self.out.ends_with([' ', '\n']);
+                    let trail = text.ends_with(char::is_whitespace) && !collapsed.is_empty();
+                    if lead || (collapsed.is_empty() && !text.is_empty() && !self.out.ends_with([' ', '\n'])) {
+                        self.out.push(' ');
+                    }
+                    self.out.push_str(&collapsed);
+                    if trail {
+                        self.out.push(' ');
+                    }
+                }
+            }
+            NodeData::Element { .. } => self.element(node),
+            NodeData::Document => self.children(node),
+            _ => {}
+        }
+    }
+
+    fn element(&mut self, node: &Handle) {
+        let tag = dom::tag_name(node).unwrap_or_default();
+        match tag.as_str() {
+            "script" | "style" | "noscript" | "head" | "template" | "svg" => {}
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+                self.block();
+                let level = tag[1..].parse::<usize>().unwrap_or(1);
+                self.out.push_str(&format!("{} {}", "#".repeat(level), self.inline_text(node)));
+                self.block();
+            }
+            "p" | "div" | "section" | "article" | "main" | "header" | "footer" | "nav" | "table" | "form" | "figure" => {
+                self.block();
+                self.children(node);
+                self.block();
+            }
+            "tr" => {
+                self.newline();
+                self.children(node);
+            }
+            "td" | "th" => {
+                self.children(node);
+                self.out.push_str(" | ");
+            }
+            "br" => self.newline(),
+            "hr" => {
+                self.block();
+                self.out.push_str("---");
+                self.block();
+            }
+            "strong" | "b" => {
+                let text = self.inline_text(node);
+                if !text.is_empty() { self.out.push_str(&format!("**{}**", text)); }
+            }
+            "em" | "i" => {
+                let text = self.inline_text(node);
+                if !text.is_empty() { self.out.push_str(&format!("*{}*", text)); }
+            }
+            "code" if !self.in_pre => self.out.push_str(&format!("`{}`", dom::text_content(node))),
+            "pre" => {
+                self.block();
+                self.out.push_str("```");
+                self.newline();
+                self.in_pre = true;
+                self.children(node);
+                self.in_pre = false;
+                self.newline();
+                self.out.push_str("```");
+                self.block();
+            }
+            "blockquote" => {
+                self.block();
+                self.quote_depth += 1;
+                self.newline();
+                self.children(node);
+                self.quote_depth -= 1;
+                self.block();
+            }
+            "ul" | "ol" => {
+                let nested = !self.list_stack.is_empty();
+                if !nested { self.block(); }
+                self.list_stack.push(if tag == "ol" { Some(1) } else { None });
+                self.children(node);
+                self.list_stack.pop();
+                if !nested { self.block(); }
+            }
+            "li" => {
+                self.newline();
+                let indent = "  ".repeat(self.list_stack.len().saturating_sub(1));
+                let marker = match self.list_stack.last_mut() {
+                    Some(Some(n)) => { *n += 1; format!("{}.", *n - 1) }
+                    _ => "-".to_string(),
+                };
+                self.out.push_str(&format!("{}{} ", indent, marker));
+                self.children(node);
+            }
+            "a" => match dom::attr(node, "href") {
+                Some(href) if !href.starts_with("javascript:") => {
+                    let text = self.inline_text(node);
+                    let url = self.resolve(&href);
+                    self.out.push_str(&format!("[{}]({})", if text.is_empty() { &url } else { &text }, url));
+                }
+                _ => self.children(node),
+            },
+            "img" => {
+                if let Some(src) = dom::attr(node, "src") {
+                    let alt = dom::attr(node, "alt").unwrap_or_default();
+                    self.out.push_str(&format!("![{}]({})", alt, self.resolve(&src)));
+                }
+            }
+            _ => self.children(node),
+        }
+    }
+}
@@ -0,0 +1,47 @@
+use crate::app::App;
+use crate::dialog::{DialogKind, SideEffect};
+use crate::download::suggested_name;
+use crate::i18n::tr;
+use std::error::Error;
+use std::process::Stdio;
+
+fn shell_quote(s: &str) -> String {
+    format!("'{}'", s.replace('\'', "'\\''"))
+}
+
+impl App {
+    // Following an <audio>/<video> source offers the external player and saving it
+    pub fn open_media(&mut self, url: &str) {
+        let mut options = Vec::new();
+        if !self.settings.player.is_empty() {
+            options.push((tr!("Play with {}", self.settings.player), format!("play {}", url)));
+        }
+        options.push((tr!("Save to disk"), format!("save-url {}", url)));
+        options.push((tr!("Cancel"), String::new()));
+        self.open_dialog("Media", url.to_string(), DialogKind::Choice { options, selected: 0 });
+    }
+
+    // Hands `url` to the configured player, which runs on its own without the terminal
+    pub fn play_media(&mut self, url: &str, force: bool) -> Result<(), Box<dyn Error>> {
+        if url.is_empty() {
+            self.status = tr!("play: expected a URL");
+            return Ok(());
+        }
+        if self.settings.player.is_empty() {
+            self.status = tr!("play: no player configured (:set player=<command>)");
+            return Ok(());
+        }
+        // The player goes over the network itself, so --sandbox refuses it too
+        self.network()?;
+        let command = format!("{} {}", self.settings.player, shell_quote(url));
+        if !self.may_proceed(SideEffect::Exec(command.clone()), force, format!("play! {}", url)) {
+            return Ok(());
+        }
+        std::process::Command::new("sh")
+            .arg("-c").arg(&command)
+            .stdin(Stdio::null()).stdout(Stdio::null()).stderr(Stdio::null())
+            .spawn()?;
+        self.status = tr!("Playing {} with {}", suggested_name(url), self.settings.player);
+        Ok(())
+    }
+}
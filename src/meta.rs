@@ -0,0 +1,85 @@
+use crate::net::Net;
+use html2text::render::text_renderer::RichAnnotation;
+use std::collections::HashMap;
+use tokio::task::JoinSet;
+use url::Url;
+
+pub struct MetaResult {
+    pub url: String,
+    pub title: String,
+    pub sources: Vec<String>,
+}
+
+// Result pages often wrap targets in redirectors like /l/?uddg=<target>
+fn unwrap_redirect(url: &Url) -> Option<String> {
+    url.query_pairs()
+        .find(|(k, v)| matches!(k.as_ref(), "uddg" | "url" | "u" | "q") && v.starts_with("http"))
+        .map(|(_, v)| v.into_owned())
+}
+
+fn extract_results(html: &str, page_url: &Url) -> Vec<(String, String)> {
+    let engine_host = page_url.host_str().unwrap_or_default().to_string();
+    let mut results: Vec<(String, String)> = Vec::new();
+    for line in html2text::from_read_rich(html.as_bytes(), 200) {
+        for ts in line.tagged_strings() {
+            let Some(target) = ts.tag.iter().find_map(|a| match a {
+                RichAnnotation::Link(t) => Some(t.clone()),
+                _ => None,
+            }) else { continue };
+            let Ok(abs) = page_url.join(&target) else { continue };
+            let url = match unwrap_redirect(&abs) {
+                Some(u) => u,
+                None if abs.host_str().is_some_and(|h| !h.ends_with(&engine_host) && !engine_host.ends_with(h)) => abs.to_string(),
+                None => continue,
+            };
+            match results.last_mut() {
+                Some((last, title)) if *last == url => title.push_str(&ts.s),
+                _ => results.push((url, ts.s.clone())),
+            }
+        }
+    }
+    results.retain(|(_, title)| title.trim().len() > 2);
+    results
+}
+
+pub async fn meta_search(net: &Net, engines: &[(String, String)], query: &str) -> Vec<MetaResult> {
+    let encoded: String = url::form_urlencoded::byte_serialize(query.as_bytes()).collect();
+    let mut tasks = JoinSet::new();
+    for (order, (name, template)) in engines.iter().enumerate() {
+        let net = net.clone();
+        let name = name.clone();
+        let url = template.replace("{}", &encoded);
+        tasks.spawn(async move {
+            let page_url = Url::parse(&url).ok()?;
+            let html = net.send(net.get(url)).await.ok()?.text().await.ok()?;
+            Some((order, name, extract_results(&html, &page_url)))
+        });
+    }
+    let mut per_engine = Vec::new();
+    while let Some(res) = tasks.join_next().await {
+        if let Ok(Some(found)) = res {
+            per_engine.push(found);
+        }
+    }
+    per_engine.sort_by_key(|(order, _, _)| *order);
+
+    // Interleave engines rank by rank so no single engine dominates the top of the page
+    let mut merged: Vec<MetaResult> = Vec::new();
+    let mut seen: HashMap<String, usize> = HashMap::new();
+    let depth = per_engine.iter().map(|(_, _, r)| r.len()).max().unwrap_or(0);
+    for rank in 0..depth {
+        for (_, name, results) in &per_engine {
+            let Some((url, title)) = results.get(rank) else { continue };
+            let key = url.trim_end_matches('/').to_string();
+            if let Some(&idx) = seen.get(&key) {
+                if !merged[idx].sources.contains(name) {
+                    merged[idx].sources.push(name.clone());
+                }
+            } else {
+                seen.insert(key, merged.len());
+                merged.push(MetaResult { url: url.clone(), title: title.trim().to_string(), sources: vec![name.clone()] });
+            }
+        }
+    }
+    merged
+}
@@ -0,0 +1,195 @@
+use crate::app::App;
+use crate::dialog::SideEffect;
+use crate::dom;
+use crate::i18n::tr;
+use crate::net::Net;
+use crate::types::ToastKind;
+use markup5ever_rcdom::NodeData;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::error::Error;
+use std::path::{Path, PathBuf};
+use tokio::sync::mpsc::UnboundedSender;
+use url::Url;
+
+// A crawl stops here whatever the depth, so a mistyped depth can't fetch a whole site
+const MAX_PAGES: usize = 500;
+
+pub enum MirrorEvent {
+    Progress(usize),
+    Done(Result<(usize, String), String>),
+}
+
+// File for `url` relative to the mirror root: host/path, with directories as index files,
+// the query folded into the name and the extension set by the output format
+fn local_path(url: &Url, ext: &str) -> String {
+    let mut path = format!("{}{}", url.host_str().unwrap_or("site"), url.path());
+    if path.ends_with('/') {
+        path.push_str("index");
+    }
+    if let Some(q) = url.query() {
+        let q: String = q.chars().map(|c| if c.is_alphanumeric() { c } else { '_' }).collect();
+        path = format!("{}_{}", path, q);
+    }
+    let stem = match path.rsplit_once('.') {
+        Some((stem, e)) if !e.contains('/') && matches!(e, "html" | "htm" | "php" | "asp" | "aspx") => stem.to_string(),
+        _ => path,
+    };
+    format!("{}.{}", stem, ext)
+}
+
+// `to` as seen from the directory holding `from`, both relative to the same root
+fn relative(from: &str, to: &str) -> String {
+    let from_dirs: Vec<&str> = from.split('/').collect();
+    let to_parts: Vec<&str> = to.split('/').collect();
+    let from_dirs = &from_dirs[..from_dirs.len() - 1];
+    let common = from_dirs.iter().zip(&to_parts).take_while(|(a, b)| a == b).count();
+    let mut out = "../".repeat(from_dirs.len() - common);
+    out.push_str(&to_parts[common..].join("/"));
+    out
+}
+
+fn same_origin(a: &Url, b: &Url) -> bool {
+    a.scheme() == b.scheme() && a.host_str() == b.host_str() && a.port_or_known_default() == b.port_or_known_default()
+}
+
+fn page_key(url: &Url) -> Url {
+    let mut url = url.clone();
+    url.set_fragment(None);
+    url
+}
+
+// Same-origin links of a page, absolute and without fragments
+fn links(html: &str, base: &Url) -> Vec<Url> {
+    let document = dom::parse(html);
+    dom::elements(&document.document).iter()
+        .filter(|e| dom::tag_name(e).as_deref() == Some("a"))
+        .filter_map(|e| dom::attr(e, "href"))
+        .filter_map(|href| base.join(&href).ok())
+        .filter(|u| same_origin(u, base))
+        .map(|u| page_key(&u))
+        .collect()
+}
+
+// Points links to mirrored pages at their local copies; everything else becomes absolute
+fn rewrite(html: &str, base: &Url, own: &str, saved: &HashMap<Url, String>) -> String {
+    let document = dom::parse(html);
+    for element in dom::elements(&document.document) {
+        let attr_name = match dom::tag_name(&element).as_deref() {
+            Some("a" | "link") => "href",
+            Some("img" | "script") => "src",
+            _ => continue,
+        };
+        let NodeData::Element { ref attrs, .. } = element.data else { continue };
+        for attr in attrs.borrow_mut().iter_mut().filter(|a| a.name.local.as_ref() == attr_name) {
+            let Ok(target) = base.join(&attr.value) else { continue };
+            let value = match saved.get(&page_key(&target)) {
+                Some(path) => match target.fragment() {
+                    Some(f) => format!("{}#{}", relative(own, path), f),
+                    None => relative(own, path),
+                },
+                None => target.to_string(),
+            };
+            attr.value = value.into();
+        }
+    }
+    dom::serialize(&document)
+}
+
+async fn crawl(net: Net, start: Url, depth: usize, tx: &UnboundedSender<MirrorEvent>) -> Vec<(Url, String)> {
+    let mut pages = Vec::new();
+    let mut seen = HashSet::from([page_key(&start)]);
+    let mut queue = VecDeque::from([(page_key(&start), 0)]);
+    while let Some((url, level)) = queue.pop_front() {
+        if pages.len() >= MAX_PAGES {
+            break;
+        }
+        let Ok(res) = net.send(net.get(url.clone())).await else { continue };
+        let is_html = res.headers().get(reqwest::header::CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .is_some_and(|v| v.contains("html"));
+        if !res.status().is_success() || !is_html {
+            continue;
+        }
+        // Redirects can leave the site
+        let final_url = page_key(res.url());
+        if !same_origin(&final_url, &start) {
+            continue;
+        }
+        let Ok(html) = res.text().await else { continue };
+        if level < depth {
+            for link in links(&html, &final_url) {
+                if seen.insert(link.clone()) {
+                    queue.push_back((link, level + 1));
+                }
+            }
+        }
+        pages.push((final_url, html));
+        let _ = tx.send(MirrorEvent::Progress(pages.len()));
+    }
+    pages
+}
+
+fn write_mirror(root: &Path, pages: &[(Url, String)], as_html: bool) -> std::io::Result<usize> {
+    let ext = if as_html { "html" } else { "txt" };
+    let saved: HashMap<Url, String> = pages.iter().map(|(u, _)| (u.clone(), local_path(u, ext))).collect();
+    for (url, html) in pages {
+        let own = &saved[url];
+        let html = rewrite(html, url, own, &saved);
+        let body = if as_html { html } else { html2text::from_read(html.as_bytes(), 100) };
+        let path = root.join(own);
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(path, body)?;
+    }
+    Ok(pages.len())
+}
+
+impl App {
+    // `<depth> [html] [dir]`: text by default, `html` keeps the markup
+    pub fn start_mirror(&mut self, arg: &str, force: bool) -> Result<(), Box<dyn Error>> {
+        let mut words = arg.split_whitespace();
+        let Some(depth) = words.next().and_then(|d| d.parse::<usize>().ok()) else {
+            self.status = tr!("mirror: expected a depth, e.g. :mirror 2");
+            return Ok(());
+        };
+        let mut as_html = false;
+        let mut dir = None;
+        for word in words {
+            match word {
+                "html" => as_html = true,
+                "text" => as_html = false,
+                d => dir = Some(d.to_string()),
+            }
+        }
+        let start = Url::parse(&self.current_url)?;
+        if !matches!(start.scheme(), "http" | "https") {
+            self.status = tr!("mirror: only web pages can be mirrored");
+            return Ok(());
+        }
+        let net = self.network()?.clone();
+        let root = PathBuf::from(dir.unwrap_or_else(|| "mirror".to_string()));
+        let shown = root.display().to_string();
+        if !self.may_proceed(SideEffect::Write(shown.clone()), force, format!("mirror! {}", arg)) {
+            return Ok(());
+        }
+        let tx = self.mirror_tx.clone();
+        tokio::spawn(async move {
+            let pages = crawl(net, start, depth, &tx).await;
+            let result = write_mirror(&root, &pages, as_html).map(|n| (n, shown)).map_err(|e| e.to_string());
+            let _ = tx.send(MirrorEvent::Done(result));
+        });
+        self.status = tr!("Mirroring {} links deep in the background (at most {} pages)", depth, MAX_PAGES);
+        Ok(())
+    }
+
+    pub fn drain_mirror(&mut self) {
+        while let Ok(event) = self.mirror_rx.try_recv() {
+            match event {
+                MirrorEvent::Progress(n) => self.status = tr!("Mirroring: {} pages fetched", n),
+                MirrorEvent::Done(Ok((n, dir))) => self.notify(ToastKind::Info, tr!("Mirrored {} pages into {}", n, dir)),
+                MirrorEvent::Done(Err(e)) => self.notify(ToastKind::Error, tr!("Mirror failed: {}", e)),
+            }
+        }
+    }
+}
@@ -0,0 +1,148 @@
+use crate::app::App;
+use crate::i18n::tr;
+use crate::page;
+use crate::types::ToastKind;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+use url::Url;
+
+// The rendered text of each watched bookmark as last seen, kept out of bookmarks.json so that
+// stays small enough to edit by hand
+pub struct BookmarkCopies {
+    path: PathBuf,
+    texts: HashMap<String, String>,
+}
+
+impl BookmarkCopies {
+    pub fn load(path: PathBuf) -> Self {
+        let texts = fs::read_to_string(&path).ok().and_then(|data| serde_json::from_str(&data).ok()).unwrap_or_default();
+        Self { path, texts }
+    }
+
+    pub fn get(&self, url: &str) -> Option<&str> {
+        self.texts.get(url).map(String::as_str)
+    }
+
+    pub fn set(&mut self, url: &str, text: Option<String>) -> io::Result<()> {
+        match text {
+            Some(text) => self.texts.insert(url.to_string(), text),
+            None => self.texts.remove(url),
+        };
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(&self.path, serde_json::to_string(&self.texts)?)
+    }
+}
+
+impl App {
+    // Text as the page would be displayed, for comparing two versions of it
+    pub fn rendered_text(&self, html: &str, base_url: &Url) -> String {
+        let (html, _) = page::prepare(html, base_url, &[]);
+        let (lines, _) = self.layout_html(&html, base_url, 0);
+        lines.iter()
+            .map(|line| line.spans.iter().map(|s| s.content.as_ref()).collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    // `:bm watch`: watching starts from the page as it is on screen now
+    pub fn toggle_bookmark_watch(&mut self) -> Result<(), Box<dyn Error>> {
+        let url = self.current_url.clone();
+        let Some(watch) = self.bookmarks.toggle_watch(&url)? else {
+            self.status = tr!("bm watch: bookmark the page first");
+            return Ok(());
+        };
+        let text = match (watch, Url::parse(&url)) {
+            (true, Ok(base_url)) if self.shown.0 == url => Some(self.rendered_text(&self.raw_html, &base_url)),
+            _ => None,
+        };
+        self.bookmark_copies.set(&url, text)?;
+        self.status = match watch {
+            true if self.settings.monitor_mins == 0 => tr!("Watching for changes (checks are off until :set monitor=<minutes>)"),
+            true => tr!("Watching for changes every {} min", self.settings.monitor_mins),
+            false => tr!("No longer watching this page"),
+        };
+        Ok(())
+    }
+
+    // A watched bookmark opened again is seen as it is now: the flag goes and the copy moves on
+    pub fn bookmark_seen(&mut self) {
+        let url = self.current_url.clone();
+        if !self.bookmarks.get(&url).is_some_and(|b| b.watch) {
+            return;
+        }
+        let Ok(base_url) = Url::parse(&url) else { return };
+        let text = self.rendered_text(&self.raw_html, &base_url);
+        if let Err(e) = self.bookmarks.set_changed(&url, false).and_then(|_| self.bookmark_copies.set(&url, Some(text))) {
+            self.notify(ToastKind::Error, tr!("bookmarks: {}", e));
+        }
+    }
+
+    // A fresh copy of a watched bookmark; the first one fetched is what later ones are compared with
+    fn check_bookmark(&mut self, url: &str, html: &str) {
+        if !self.bookmarks.get(url).is_some_and(|b| b.watch && !b.changed) {
+            return;
+        }
+        let Ok(base_url) = Url::parse(url) else { return };
+        let text = self.rendered_text(html, &base_url);
+        let result = match self.bookmark_copies.get(url) {
+            Some(copy) if copy == text => Ok(()),
+            Some(_) => self.bookmarks.set_changed(url, true).map(|_| {
+                let title = self.bookmarks.get(url).map(|b| b.title.clone()).filter(|t| !t.is_empty()).unwrap_or_else(|| url.to_string());
+                self.notify(ToastKind::Info, tr!("Bookmark changed: {}", title));
+            }),
+            None => self.bookmark_copies.set(url, Some(text)),
+        };
+        if let Err(e) = result {
+            self.notify(ToastKind::Error, tr!("bookmarks: {}", e));
+        }
+    }
+
+    // Called every pass of the event loop. Every `monitor` minutes the watched reading list
+    // entries and bookmarks are re-fetched in the background; each result is compared with the
+    // saved copy.
+    pub fn monitor_watched(&mut self) {
+        while let Ok((url, html)) = self.monitor_rx.try_recv() {
+            self.check_bookmark(&url, &html);
+            let Some(saved) = self.reading_list.get(&url) else { continue };
+            if saved.changed {
+                continue;
+            }
+            let Ok(base_url) = Url::parse(&url) else { continue };
+            if self.rendered_text(&saved.html, &base_url) != self.rendered_text(&html, &base_url) {
+                let title = if saved.title.is_empty() { url.clone() } else { saved.title.clone() };
+                match self.reading_list.mark_changed(&url) {
+                    Ok(()) => self.notify(ToastKind::Info, tr!("Changed since saved: {}", title)),
+                    Err(e) => self.notify(ToastKind::Error, tr!("reading list: {}", e)),
+                }
+            }
+        }
+        let minutes = self.settings.monitor_mins;
+        if minutes == 0 || self.sandbox || self.last_monitor.elapsed() < Duration::from_secs(minutes.saturating_mul(60)) {
+            return;
+        }
+        self.last_monitor = Instant::now();
+        let pages = self.reading_list.pages.iter().filter(|p| p.watch && !p.changed).map(|p| p.url.clone());
+        let bookmarks = self.bookmarks.entries.iter().filter(|b| b.watch && !b.changed).map(|b| b.url.clone());
+        let mut urls: Vec<String> = pages.chain(bookmarks).collect();
+        urls.sort();
+        urls.dedup();
+        for url in urls {
+            let net = self.net.clone();
+            let tx = self.monitor_tx.clone();
+            tokio::spawn(async move {
+                let Ok(res) = net.send(net.get(&url)).await else { return };
+                if !res.status().is_success() {
+                    return;
+                }
+                let Ok(html) = res.text().await else { return };
+                let _ = tx.send((url, html));
+            });
+        }
+    }
+}
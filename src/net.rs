@@ -0,0 +1,115 @@
+use crate::har::{Exchange, Incoming, Outgoing};
+use crate::headers::HeaderRules;
+use crate::redirect::RedirectLog;
+use crate::timing::Probe;
+use crate::types::Settings;
+use reqwest::{Client, IntoUrl, Method, Request, RequestBuilder, Response};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc::UnboundedSender;
+use url::Url;
+
+// What every request goes out through, page loads and background fetches alike: the
+// voyager.toml headers and User-Agent are added, the connection is timed, and the exchange
+// goes to the event loop for the network pane and :har-export. Cheap to clone into a task.
+#[derive(Clone)]
+pub struct Net {
+    client: Client,
+    headers: Arc<HeaderRules>,
+    user_agent: String,
+    // Where the client's redirect policy says why it stopped, and how many redirects it follows
+    redirects: RedirectLog,
+    redirect_limit: usize,
+    log: UnboundedSender<Exchange>,
+}
+
+impl Net {
+    pub fn new(client: Client, headers: Arc<HeaderRules>, settings: &Settings, redirects: RedirectLog, log: UnboundedSender<Exchange>) -> Self {
+        Self { client, headers, user_agent: settings.user_agent.clone(), redirects, redirect_limit: settings.redirects, log }
+    }
+
+    pub fn set_client(&mut self, client: Client, settings: &Settings) {
+        self.client = client;
+        self.user_agent = settings.user_agent.clone();
+        self.redirect_limit = settings.redirects;
+    }
+
+    pub fn set_headers(&mut self, headers: Arc<HeaderRules>) {
+        self.headers = headers;
+    }
+
+    pub fn get(&self, url: impl IntoUrl) -> RequestBuilder {
+        self.client.get(url)
+    }
+
+    pub fn head(&self, url: impl IntoUrl) -> RequestBuilder {
+        self.client.head(url)
+    }
+
+    pub fn post(&self, url: impl IntoUrl) -> RequestBuilder {
+        self.client.post(url)
+    }
+
+    pub fn request(&self, method: Method, url: impl IntoUrl) -> RequestBuilder {
+        self.client.request(method, url)
+    }
+
+    // The User-Agent is made explicit so the logged headers match what goes on the wire
+    fn prepare(&self, mut req: Request) -> Request {
+        let url = req.url().clone();
+        self.headers.apply(&url, req.headers_mut());
+        if let Ok(agent) = reqwest::header::HeaderValue::from_str(&self.user_agent) {
+            req.headers_mut().entry(reqwest::header::USER_AGENT).or_insert(agent);
+        }
+        req
+    }
+
+    // For a request whose exchange is logged by the caller once its body is read
+    pub fn build(&self, builder: RequestBuilder) -> reqwest::Result<Request> {
+        builder.build().map(|req| self.prepare(req))
+    }
+
+    // Sends a built request, timing its connection into `probe`
+    pub async fn execute(&self, req: Request, probe: &Probe) -> reqwest::Result<Response> {
+        probe.watch(self.client.execute(req)).await
+    }
+
+    // Builds, sends and logs as soon as the response headers are in, with the size the server
+    // gives. The builder's own client is used, so one without redirects still gets all this.
+    pub async fn send(&self, builder: RequestBuilder) -> reqwest::Result<Response> {
+        let (client, req) = builder.build_split();
+        let mut req = req?;
+        let mut hops = 0;
+        loop {
+            let (method, timeout) = (req.method().clone(), req.timeout().copied());
+            let res = self.send_one(&client, req).await?;
+            // A redirect stopped so this host's site headers don't go along (see redirect.rs) is
+            // followed with a fresh request, which gets the new host's headers instead. 307 and
+            // 308 keep the method, so only a GET or HEAD is sent on for those, as page loads do.
+            let Some((to, status)) = self.redirects.site_headers_stop(res.url().as_str()) else { return Ok(res) };
+            let Ok(to) = Url::parse(&to) else { return Ok(res) };
+            let resendable = method == Method::GET || method == Method::HEAD || matches!(status, 301..=303);
+            if !resendable || hops >= self.redirect_limit {
+                return Ok(res);
+            }
+            hops += 1;
+            self.redirects.take_stopped(res.url().as_str());
+            req = Request::new(if method == Method::HEAD { Method::HEAD } else { Method::GET }, to);
+            *req.timeout_mut() = timeout;
+        }
+    }
+
+    async fn send_one(&self, client: &Client, req: Request) -> reqwest::Result<Response> {
+        let req = self.prepare(req);
+        let outgoing = Outgoing::capture(&req);
+        let res = outgoing.probe.watch(client.execute(req)).await?;
+        let ttfb = outgoing.started.elapsed();
+        let bytes = res.content_length().unwrap_or(0) as usize;
+        self.log(Exchange::new(outgoing, Incoming::capture(&res), (ttfb, Duration::ZERO, Duration::ZERO), bytes, false));
+        Ok(res)
+    }
+
+    pub fn log(&self, exchange: Exchange) {
+        let _ = self.log.send(exchange);
+    }
+}
@@ -0,0 +1,231 @@
+use crate::dom;
+use crate::download;
+use crate::forms::{self, Field, FieldKind, Form};
+use crate::i18n::tr;
+use markup5ever_rcdom::Handle;
+use url::Url;
+
+#[derive(Default, Clone)]
+pub struct PageInfo {
+    pub frames: Vec<String>,
+    pub refresh: Option<(u64, String)>,
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub canonical: Option<String>,
+    // og:* and twitter:* properties in document order
+    pub social: Vec<(String, String)>,
+    pub headings: Vec<(usize, String)>,
+    // rel=next/prev targets, from <link>, <a rel> or unambiguous "Next page" anchors
+    pub next: Option<String>,
+    pub prev: Option<String>,
+    // Signs of a page that only renders with JavaScript: its <noscript> markup,
+    // how many scripts it loads, and an empty framework mount point
+    pub noscript: Vec<String>,
+    pub scripts: usize,
+    pub app_root: bool,
+    // <html amp> or <html ⚡>
+    pub amp: bool,
+    // (text, title) of each <abbr>/<acronym>, and (absolute href, title) of titled links
+    pub abbreviations: Vec<(String, String)>,
+    pub link_titles: Vec<(String, String)>,
+    // Sources of <audio> and <video> elements, which are replaced by links to them
+    pub media: Vec<String>,
+    // Every <form> with the values of its controls, which are replaced by field links (see forms.rs)
+    pub forms: Vec<Form>,
+}
+
+const WORDS_PER_MINUTE: usize = 230;
+// Mount points of the common client-side frameworks
+const APP_ROOT_IDS: &[&str] = &["root", "app", "__next", "__nuxt", "___gatsby", "svelte", "ember-app"];
+
+#[derive(Default, Clone, Copy)]
+pub struct TextStats {
+    pub words: usize,
+    pub minutes: usize,
+}
+
+impl TextStats {
+    pub fn from_text(text: &str) -> Self {
+        // Link labels like "[12]" are UI chrome, not prose
+        let words = text.split_whitespace()
+            .filter(|w| w.chars().any(char::is_alphanumeric))
+            .filter(|w| !(w.starts_with('[') && w.ends_with(']') && w[1..w.len() - 1].chars().all(|c| c.is_ascii_digit())))
+            .count();
+        Self { words, minutes: words.div_ceil(WORDS_PER_MINUTE) }
+    }
+
+    pub fn summary(&self) -> String {
+        tr!("{} words, {} min read", self.words, self.minutes.max(1))
+    }
+}
+
+// Single DOM pass before html2text: collects page metadata and rewrites elements html2text drops.
+// `filled` is the page's forms from a previous pass, whose values are kept.
+pub fn prepare(html: &str, base_url: &Url, filled: &[Form]) -> (String, PageInfo) {
+    let document = dom::parse(html);
+    let mut info = PageInfo::default();
+    let mut form_nodes: Vec<Handle> = Vec::new();
+    let resolve = |href: &str| base_url.join(href).map(|u| u.to_string()).unwrap_or_else(|_| href.to_string());
+
+    let elements = dom::elements(&document.document);
+    // (for, text) of each <label>, gathered first since one can come after its control
+    let labels: Vec<(String, String)> = elements.iter()
+        .filter(|e| dom::tag_name(e).as_deref() == Some("label"))
+        .filter_map(|l| Some((dom::attr(l, "for")?, collapse_whitespace(&dom::text_content(l)))))
+        .collect();
+
+    for node in elements {
+        match dom::tag_name(&node).as_deref() {
+            Some("iframe") | Some("frame") => {
+                if let Some(src) = dom::attr(&node, "src").filter(|s| !s.trim().is_empty()) {
+                    let abs = resolve(src.trim());
+                    let label = format!("[FRAME: {}]", abs);
+                    let placeholder = dom::new_element("p", &[], "");
+                    dom::append(&placeholder, dom::new_element("a", &[("href", &abs)], &label));
+                    dom::replace(&node, placeholder);
+                    info.frames.push(abs);
+                }
+            }
+            Some("meta") if dom::attr(&node, "http-equiv").is_some_and(|v| v.eq_ignore_ascii_case("refresh")) => {
+                if let Some((delay, target)) = dom::attr(&node, "content").as_deref().and_then(parse_refresh) {
+                    let target = target.map(|t| resolve(&t)).unwrap_or_else(|| base_url.to_string());
+                    info.refresh.get_or_insert((delay, target));
+                }
+            }
+            Some(tag @ ("audio" | "video")) => {
+                let sources: Vec<String> = dom::attr(&node, "src").into_iter()
+                    .chain(dom::elements(&node).iter().filter(|n| dom::tag_name(n).as_deref() == Some("source")).filter_map(|n| dom::attr(n, "src")))
+                    .filter(|s| !s.trim().is_empty())
+                    .map(|s| resolve(s.trim()))
+                    .collect();
+                if sources.is_empty() {
+                    continue;
+                }
+                // Drops the fallback content inside, which is for browsers that can't play media at all
+                let placeholder = dom::new_element("div", &[], "");
+                for src in sources {
+                    let label = format!("[{}: {}]", tag.to_uppercase(), download::suggested_name(&src));
+                    let line = dom::new_element("p", &[], "");
+                    dom::append(&line, dom::new_element("a", &[("href", &src)], &label));
+                    dom::append(&placeholder, line);
+                    info.media.push(src);
+                }
+                dom::replace(&node, placeholder);
+            }
+            Some("form") => {
+                let action = dom::attr(&node, "action").map(|a| a.trim().to_string()).filter(|a| !a.is_empty());
+                let post = dom::attr(&node, "method").is_some_and(|m| m.trim().eq_ignore_ascii_case("post"));
+                info.forms.push(Form { action: action.map_or_else(|| base_url.to_string(), |a| resolve(&a)), post, fields: Vec::new(), search: None });
+                form_nodes.push(node.clone());
+            }
+            Some("input" | "textarea" | "select" | "button") => {
+                let Some(form) = form_nodes.iter().rposition(|f| dom::is_ancestor(f, &node)) else { continue };
+                let Some(mut field) = Field::from_node(&node) else { continue };
+                let id = dom::attr(&node, "id").filter(|id| !id.is_empty());
+                let label = labels.iter().find(|(f, _)| id.as_ref() == Some(f)).map(|(_, t)| t.clone())
+                    .or_else(|| dom::parent(&node).filter(|p| dom::tag_name(p).as_deref() == Some("label")).map(|p| collapse_whitespace(&dom::text_content(&p))));
+                field.hints.extend(label);
+                let idx = info.forms[form].fields.len();
+                let search_box = dom::attr(&node, "type").is_some_and(|t| t.eq_ignore_ascii_case("search"))
+                    || field.kind == FieldKind::Text && in_search_role(&form_nodes[form]);
+                if search_box && info.forms[form].search.is_none() {
+                    info.forms[form].search = Some(idx);
+                }
+                if let Some(prev) = filled.get(form).and_then(|f| f.fields.get(idx)).filter(|p| p.name == field.name && p.kind == field.kind) {
+                    field.value = prev.value.clone();
+                    field.checked = prev.checked;
+                }
+                if let Some(label) = field.label() {
+                    dom::replace(&node, dom::new_element("a", &[("href", &forms::field_url(form, idx))], &label));
+                }
+                info.forms[form].fields.push(field);
+            }
+            Some(tag @ ("h1" | "h2" | "h3" | "h4" | "h5" | "h6")) => {
+                let text = collapse_whitespace(&dom::text_content(&node));
+                if !text.is_empty() {
+                    info.headings.push((tag[1..].parse().unwrap_or(1), text));
+                }
+            }
+            Some("title") if info.title.is_none() => {
+                let title = collapse_whitespace(&dom::text_content(&node));
+                info.title = (!title.is_empty()).then_some(title);
+            }
+            Some("meta") => {
+                let key = dom::attr(&node, "property").or_else(|| dom::attr(&node, "name")).unwrap_or_default().to_lowercase();
+                let Some(content) = dom::attr(&node, "content") else { continue };
+                if key == "description" {
+                    info.description.get_or_insert(collapse_whitespace(&content));
+                } else if key.starts_with("og:") || key.starts_with("twitter:") {
+                    info.social.push((key, collapse_whitespace(&content)));
+                }
+            }
+            Some("html") => info.amp = dom::attr(&node, "amp").is_some() || dom::attr(&node, "⚡").is_some(),
+            Some("noscript") => {
+                let markup = dom::text_content(&node);
+                if !markup.trim().is_empty() {
+                    info.noscript.push(markup);
+                }
+            }
+            Some("script") => info.scripts += 1,
+            Some("div") if dom::attr(&node, "id").is_some_and(|id| APP_ROOT_IDS.contains(&id.as_str())) => {
+                info.app_root |= collapse_whitespace(&dom::text_content(&node)).is_empty();
+            }
+            Some("link") if has_rel(&node, "canonical") => {
+                if let Some(href) = dom::attr(&node, "href") {
+                    info.canonical.get_or_insert(resolve(href.trim()));
+                }
+            }
+            // <acronym> is obsolete, but still common on older pages
+            Some("abbr" | "acronym") => {
+                let text = collapse_whitespace(&dom::text_content(&node));
+                let title = collapse_whitespace(&dom::attr(&node, "title").unwrap_or_default());
+                if !text.is_empty() && !title.is_empty() && !info.abbreviations.iter().any(|(t, _)| *t == text) {
+                    info.abbreviations.push((text, title));
+                }
+            }
+            Some(tag @ ("link" | "a")) => {
+                if tag == "a" && let (Some(href), Some(title)) = (dom::attr(&node, "href"), dom::attr(&node, "title")) {
+                    let title = collapse_whitespace(&title);
+                    if !title.is_empty() {
+                        info.link_titles.push((resolve(href.trim()), title));
+                    }
+                }
+                let Some(href) = dom::attr(&node, "href").filter(|h| !h.trim().is_empty() && !h.starts_with('#')) else { continue };
+                let text = collapse_whitespace(&dom::text_content(&node)).to_lowercase();
+                if has_rel(&node, "next") || text == "next page" {
+                    info.next.get_or_insert(resolve(href.trim()));
+                } else if has_rel(&node, "prev") || has_rel(&node, "previous") || text == "previous page" || text == "prev page" {
+                    info.prev.get_or_insert(resolve(href.trim()));
+                }
+            }
+            _ => {}
+        }
+    }
+    (dom::serialize(&document), info)
+}
+
+fn in_search_role(node: &Handle) -> bool {
+    std::iter::successors(Some(node.clone()), dom::parent)
+        .any(|n| dom::attr(&n, "role").is_some_and(|r| r.trim().eq_ignore_ascii_case("search")))
+}
+
+fn has_rel(node: &Handle, rel: &str) -> bool {
+    dom::attr(node, "rel").is_some_and(|r| r.split_whitespace().any(|t| t.eq_ignore_ascii_case(rel)))
+}
+
+// content="5; url=/next" (the url part is optional and may be quoted)
+fn parse_refresh(content: &str) -> Option<(u64, Option<String>)> {
+    let (delay, rest) = content.split_once([';', ',']).unwrap_or((content, ""));
+    let delay = delay.trim().split('.').next()?.parse().ok()?;
+    let rest = rest.trim();
+    let target = rest.get(..4)
+        .filter(|p| p.eq_ignore_ascii_case("url="))
+        .map(|_| rest[4..].trim())
+        .unwrap_or(rest)
+        .trim_matches(|c| c == '\'' || c == '"');
+    Some((delay, (!target.is_empty()).then(|| target.to_string())))
+}
+
+fn collapse_whitespace(s: &str) -> String {
+    s.split_whitespace().collect::<Vec<_>>().join(" ")
+}
@@ -0,0 +1,24 @@
+use regex::{Regex, RegexBuilder};
+
+// A regular expression in the `regex` crate's syntax, with smart case: a pattern without
+// capitals matches case-insensitively
+pub struct Pattern(Regex);
+
+impl Pattern {
+    pub fn parse(pattern: &str) -> Result<Self, String> {
+        RegexBuilder::new(pattern)
+            .case_insensitive(!pattern.chars().any(char::is_uppercase))
+            .build()
+            .map(Self)
+            // The crate's syntax errors draw the pattern with a caret over several lines; the
+            // status line only has room for the last, which says what is wrong
+            .map_err(|e| {
+                let e = e.to_string();
+                e.lines().last().map_or(e.clone(), |l| l.trim().trim_start_matches("error: ").to_string())
+            })
+    }
+
+    pub fn is_match(&self, text: &str) -> bool {
+        self.0.is_match(text)
+    }
+}
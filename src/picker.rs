@@ -0,0 +1,211 @@
+use crate::app::App;
+use crate::help;
+use crate::i18n::{self, tr};
+use crate::types::Mode;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::collections::HashSet;
+use std::error::Error;
+
+#[derive(Clone)]
+pub enum PickerAction {
+    // Runs a command; one that needs arguments is prefilled on the command line instead
+    Command { name: &'static str, args: &'static str },
+    FollowLink(usize),
+    // An address, or search terms when it doesn't look like one
+    Open(String),
+    SwitchTab(usize),
+}
+
+pub struct PickerItem {
+    pub label: String,
+    pub detail: String,
+    pub action: PickerAction,
+}
+
+// A filter-as-you-type list: `matches` holds indices into `items`, best match first
+pub struct Picker {
+    pub title: String,
+    pub query: String,
+    pub items: Vec<PickerItem>,
+    pub matches: Vec<usize>,
+    pub selected: usize,
+    // Enter with nothing matched opens the typed text itself
+    pub open_query: bool,
+}
+
+// Subsequence match scored for consecutive runs and word starts; None when `query` doesn't match
+pub fn fuzzy_score(query: &str, candidate: &str) -> Option<i32> {
+    let candidate: Vec<char> = candidate.to_lowercase().chars().collect();
+    let mut score = 0;
+    let mut pos = 0;
+    let mut prev_match: Option<usize> = None;
+    for q in query.to_lowercase().chars().filter(|c| !c.is_whitespace()) {
+        let found = (pos..candidate.len()).find(|&i| candidate[i] == q)?;
+        score += 1;
+        if prev_match == Some(found.wrapping_sub(1)) {
+            score += 5;
+        }
+        if found == 0 || !candidate[found - 1].is_alphanumeric() {
+            score += 3;
+        }
+        score -= (found - pos).min(10) as i32 / 2;
+        prev_match = Some(found);
+        pos = found + 1;
+    }
+    Some(score)
+}
+
+impl Picker {
+    pub fn new(title: &str, items: Vec<PickerItem>) -> Self {
+        let mut picker = Self { title: format!(" {} ", i18n::lookup(title)), query: String::new(), items, matches: Vec::new(), selected: 0, open_query: false };
+        picker.refilter();
+        picker
+    }
+
+    fn refilter(&mut self) {
+        let mut scored: Vec<(i32, usize)> = self.items.iter().enumerate()
+            .filter_map(|(i, item)| {
+                let label = fuzzy_score(&self.query, &item.label);
+                let detail = fuzzy_score(&self.query, &item.detail).map(|s| s - 5);
+                label.max(detail).map(|s| (s, i))
+            })
+            .collect();
+        // Stable sort keeps the original order among equal scores
+        scored.sort_by_key(|&(s, _)| -s);
+        self.matches = scored.into_iter().map(|(_, i)| i).collect();
+        self.selected = 0;
+    }
+
+    pub fn chosen(&self) -> Option<&PickerItem> {
+        self.matches.get(self.selected).map(|&i| &self.items[i])
+    }
+}
+
+impl App {
+    pub fn open_picker(&mut self, picker: Picker) {
+        self.picker = Some(picker);
+        self.mode = Mode::Picker;
+    }
+
+    pub fn open_command_palette(&mut self) {
+        let items = help::COMMANDS.iter().map(|&(name, args, desc)| PickerItem {
+            label: format!("{} {}", name, args).trim_end().to_string(),
+            detail: i18n::lookup(desc).to_string(),
+            action: PickerAction::Command { name, args },
+        }).collect();
+        self.open_picker(Picker::new("Command Palette", items));
+    }
+
+    // Links of the current page, matched on their visible text and URL
+    pub fn open_link_finder(&mut self) {
+        if self.links.is_empty() {
+            self.status = tr!("No links on this page");
+            return;
+        }
+        let items = self.link_texts().into_iter().zip(&self.links).enumerate()
+            .map(|(i, (text, link))| PickerItem {
+                label: text.split_whitespace().collect::<Vec<_>>().join(" "),
+                detail: link.url.clone(),
+                action: PickerAction::FollowLink(i),
+            })
+            .collect();
+        self.open_picker(Picker::new("Find Link", items));
+    }
+
+    // The other open tabs, then bookmarks, then history by frecency, each address once; the typed
+    // text is opened as an address or search when nothing matches
+    pub fn open_omnibar(&mut self, query: &str) {
+        self.history.save(self.current_entry());
+        let mut items = Vec::new();
+        let mut seen = HashSet::new();
+        for (i, (label, shown)) in self.tab_labels().into_iter().enumerate() {
+            let url = self.tabs[i].url();
+            if !shown && seen.insert(url.to_string()) {
+                items.push(PickerItem { label, detail: tr!("tab {}: {}", i + 1, url), action: PickerAction::SwitchTab(i) });
+            }
+        }
+        for bookmark in &self.bookmarks.entries {
+            if seen.insert(bookmark.url.clone()) {
+                let label = if bookmark.title.is_empty() { bookmark.url.clone() } else { bookmark.title.clone() };
+                items.push(PickerItem { label, detail: tr!("bookmark: {}", bookmark.url), action: PickerAction::Open(bookmark.url.clone()) });
+            }
+        }
+        for (url, title) in self.history.frecent() {
+            if seen.insert(url.clone()) {
+                items.push(PickerItem { label: title.unwrap_or_else(|| url.clone()), detail: url.clone(), action: PickerAction::Open(url) });
+            }
+        }
+        let mut picker = Picker::new("Open", items);
+        picker.open_query = true;
+        picker.query = query.to_string();
+        picker.refilter();
+        self.open_picker(picker);
+    }
+
+    // Returns Ok(true) when the chosen action quits the browser
+    pub async fn handle_picker_key(&mut self, key: KeyEvent) -> Result<bool, Box<dyn Error>> {
+        let Some(picker) = self.picker.as_mut() else {
+            self.mode = Mode::Normal;
+            return Ok(false);
+        };
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        match key.code {
+            KeyCode::Esc => {
+                self.picker = None;
+                self.mode = Mode::Normal;
+            }
+            KeyCode::Down | KeyCode::Tab => picker.selected = (picker.selected + 1).min(picker.matches.len().saturating_sub(1)),
+            KeyCode::Char('n') if ctrl => picker.selected = (picker.selected + 1).min(picker.matches.len().saturating_sub(1)),
+            KeyCode::Up | KeyCode::BackTab => picker.selected = picker.selected.saturating_sub(1),
+            KeyCode::Char('p') if ctrl => picker.selected = picker.selected.saturating_sub(1),
+            KeyCode::Backspace => {
+                picker.query.pop();
+                picker.refilter();
+            }
+            KeyCode::Char(c) if !ctrl => {
+                picker.query.push(c);
+                picker.refilter();
+            }
+            KeyCode::Enter => {
+                let action = match picker.chosen() {
+                    Some(item) => Some(item.action.clone()),
+                    None if picker.open_query && !picker.query.trim().is_empty() => Some(PickerAction::Open(picker.query.trim().to_string())),
+                    None => None,
+                };
+                self.picker = None;
+                self.mode = Mode::Normal;
+                if let Some(action) = action {
+                    return self.run_picker_action(action).await;
+                }
+            }
+            _ => {}
+        }
+        Ok(false)
+    }
+
+    async fn run_picker_action(&mut self, action: PickerAction) -> Result<bool, Box<dyn Error>> {
+        match action {
+            PickerAction::Command { name, args } if !args.is_empty() => {
+                self.mode = Mode::Command;
+                self.command_buffer = format!("{} ", name);
+            }
+            PickerAction::Command { name, .. } => return self.execute_command(name).await,
+            PickerAction::FollowLink(idx) => {
+                self.selected_link_idx = idx;
+                self.follow_link(idx).await?;
+            }
+            PickerAction::Open(text) => self.open_typed(text).await?,
+            PickerAction::SwitchTab(idx) => self.switch_tab(idx),
+        }
+        Ok(false)
+    }
+
+    // An address, or search terms when it doesn't look like one
+    pub async fn open_typed(&mut self, text: String) -> Result<(), Box<dyn Error>> {
+        if text.contains(' ') || !text.contains(['.', ':']) {
+            let url = self.search_url(&text);
+            return self.navigate(url).await;
+        }
+        self.navigate(text).await
+    }
+}
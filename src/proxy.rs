@@ -0,0 +1,84 @@
+use crate::app::App;
+use crate::i18n::tr;
+use reqwest::ClientBuilder;
+use url::Url;
+
+// Tor's SOCKS port. socks5h, so names are resolved by Tor instead of leaking to the local resolver.
+pub const TOR: &str = "socks5h://127.0.0.1:9050";
+// What reqwest reads when the setting is auto, most specific first
+const ENV_VARS: &[&str] = &["HTTPS_PROXY", "https_proxy", "HTTP_PROXY", "http_proxy", "ALL_PROXY", "all_proxy"];
+
+// The proxy setting: "auto" takes the proxy from the environment (see ENV_VARS, and NO_PROXY),
+// "none" connects directly, "tor" is Tor's SOCKS port, and anything else is a proxy URL
+pub fn parse(value: &str) -> Result<String, String> {
+    match value {
+        "auto" | "none" => Ok(value.to_string()),
+        "tor" => Ok(TOR.to_string()),
+        _ => match Url::parse(value) {
+            Ok(url) if matches!(url.scheme(), "http" | "https" | "socks5" | "socks5h") && url.host_str().is_some() => Ok(value.to_string()),
+            _ => Err(format!("expected auto, none, tor or an http://, https://, socks5:// or socks5h:// URL, got {:?}", value)),
+        },
+    }
+}
+
+// Every client that fetches pages goes through here, so none of them connects around the proxy
+pub fn apply(builder: ClientBuilder, proxy: &str) -> ClientBuilder {
+    match proxy {
+        "auto" => builder,
+        "none" => builder.no_proxy(),
+        url => match reqwest::Proxy::all(url) {
+            Ok(proxy) => builder.proxy(proxy),
+            Err(_) => builder,
+        },
+    }
+}
+
+fn from_env() -> Option<String> {
+    ENV_VARS.iter().find_map(|k| std::env::var(k).ok().filter(|v| !v.is_empty()))
+}
+
+// The URL with any password hidden, for the status line and help
+pub fn shown(proxy: &str) -> String {
+    match Url::parse(proxy) {
+        Ok(mut url) if url.password().is_some() => {
+            let _ = url.set_password(Some("***"));
+            url.to_string()
+        }
+        _ => proxy.to_string(),
+    }
+}
+
+impl App {
+    // Whether requests leave through a proxy, from the setting or the environment
+    pub fn proxied(&self) -> bool {
+        match self.settings.proxy.as_str() {
+            "none" => false,
+            "auto" => from_env().is_some(),
+            _ => true,
+        }
+    }
+
+    // `:proxy [auto|none|tor|<url>]`; without an argument, says which proxy is in use
+    pub fn set_proxy(&mut self, arg: &str) {
+        if !arg.is_empty() {
+            match parse(arg) {
+                Ok(proxy) => {
+                    self.settings.proxy = proxy;
+                    self.rebuild_client();
+                }
+                Err(e) => {
+                    self.status = tr!("proxy: {}", e);
+                    return;
+                }
+            }
+        }
+        self.status = match self.settings.proxy.as_str() {
+            "none" => tr!("Proxy: none, connecting directly"),
+            "auto" => match from_env() {
+                Some(proxy) => tr!("Proxy: {} (from the environment)", shown(&proxy)),
+                None => tr!("Proxy: none set in the environment, connecting directly"),
+            },
+            proxy => tr!("Proxy: {}", shown(proxy)),
+        };
+    }
+}
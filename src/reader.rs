@@ -0,0 +1,151 @@
+use crate::about::escape_html;
+use crate::app::App;
+use crate::dom;
+use crate::i18n::tr;
+use markup5ever_rcdom::{Handle, Node};
+use std::collections::HashMap;
+use std::rc::Rc;
+
+// Never part of an article, whatever they contain
+const STRIP_TAGS: &[&str] = &["script", "style", "noscript", "template", "nav", "aside", "footer", "form", "iframe", "button", "svg"];
+// Words in a class or id that mark page furniture, and ones that mark the text itself
+const UNLIKELY: &[&str] = &[
+    "comment", "sidebar", "footer", "menu", "nav", "share", "social", "related", "promo", "advert", "sponsor",
+    "banner", "cookie", "newsletter", "subscribe", "popup", "modal", "breadcrumb", "widget", "masthead",
+];
+const LIKELY: &[&str] = &["article", "content", "main", "post", "entry", "story", "body"];
+// Paragraphs shorter than this don't count towards a container's score
+const MIN_PARAGRAPH_CHARS: usize = 25;
+// Below this much text in the best container there is no article, and the page is left whole
+const MIN_ARTICLE_CHARS: usize = 250;
+
+fn text_len(node: &Handle) -> usize {
+    dom::text_content(node).split_whitespace().map(|w| w.chars().count() + 1).sum()
+}
+
+fn class_and_id(node: &Handle) -> String {
+    format!("{} {}", dom::attr(node, "class").unwrap_or_default(), dom::attr(node, "id").unwrap_or_default()).to_lowercase()
+}
+
+fn is_unlikely(node: &Handle) -> bool {
+    let words = class_and_id(node);
+    UNLIKELY.iter().any(|w| words.contains(w)) && !LIKELY.iter().any(|w| words.contains(w))
+}
+
+fn is_hidden(node: &Handle) -> bool {
+    dom::attr(node, "hidden").is_some()
+        || dom::attr(node, "aria-hidden").is_some_and(|v| v == "true")
+        || dom::attr(node, "style").is_some_and(|s| s.replace(' ', "").to_lowercase().contains("display:none"))
+}
+
+// Share of the text that is link text; navigation blocks are mostly links
+fn link_density(node: &Handle) -> f64 {
+    let total = text_len(node);
+    if total == 0 {
+        return 0.0;
+    }
+    let links: usize = dom::elements(node).iter().filter(|n| dom::tag_name(n).as_deref() == Some("a")).map(text_len).sum();
+    links as f64 / total as f64
+}
+
+// What a container starts with before its paragraphs are counted
+fn initial_score(node: &Handle) -> f64 {
+    let words = class_and_id(node);
+    let weight = if LIKELY.iter().any(|w| words.contains(w)) { 25.0 } else { 0.0 }
+        - if UNLIKELY.iter().any(|w| words.contains(w)) { 25.0 } else { 0.0 };
+    let tag = match dom::tag_name(node).as_deref() {
+        Some("article") => 10.0,
+        Some("div" | "main" | "section") => 5.0,
+        Some("pre" | "td" | "blockquote") => 3.0,
+        Some("ul" | "ol" | "dl" | "form") => -3.0,
+        Some("h1" | "h2" | "h3" | "h4" | "h5" | "h6" | "th") => -5.0,
+        _ => 0.0,
+    };
+    weight + tag
+}
+
+// Readability-style extraction: strips the furniture, scores each container by the
+// paragraphs in it (and, at half weight, in its children), and keeps the best one with
+// any siblings that look like more of the same text. None when nothing reads like an article.
+pub fn extract(html: &str) -> Option<String> {
+    let document = dom::parse(html);
+    let all = dom::elements(&document.document);
+    // Taken before stripping, for articles whose heading sits outside the text
+    let heading = all.iter()
+        .find(|n| dom::tag_name(n).as_deref() == Some("h1"))
+        .or_else(|| all.iter().find(|n| dom::tag_name(n).as_deref() == Some("title")))
+        .map(|n| dom::text_content(n).split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|h| !h.is_empty());
+
+    for node in &all {
+        let tag = dom::tag_name(node).unwrap_or_default();
+        let structural = matches!(tag.as_str(), "html" | "body" | "article" | "main");
+        if STRIP_TAGS.contains(&tag.as_str()) || is_hidden(node) || (!structural && is_unlikely(node)) {
+            dom::remove(node);
+        }
+    }
+
+    let mut scores: HashMap<*const Node, (Handle, f64)> = HashMap::new();
+    for node in dom::elements(&document.document) {
+        if !matches!(dom::tag_name(&node).as_deref(), Some("p" | "pre" | "td" | "blockquote")) {
+            continue;
+        }
+        let text = dom::text_content(&node);
+        let len = text.split_whitespace().map(|w| w.chars().count() + 1).sum::<usize>();
+        if len < MIN_PARAGRAPH_CHARS {
+            continue;
+        }
+        let score = 1.0 + text.matches(',').count() as f64 + (len / 100).min(3) as f64;
+        let parent = dom::parent(&node);
+        let grandparent = parent.as_ref().and_then(dom::parent);
+        for (ancestor, share) in [(parent, 1.0), (grandparent, 0.5)] {
+            let Some(ancestor) = ancestor else { continue };
+            let entry = scores.entry(Rc::as_ptr(&ancestor)).or_insert_with(|| (ancestor.clone(), initial_score(&ancestor)));
+            entry.1 += score * share;
+        }
+    }
+    // Lots of link text makes a container less likely to be the article
+    let scored: Vec<(Handle, f64)> = scores.into_values().map(|(node, score)| {
+        let density = link_density(&node);
+        (node, score * (1.0 - density))
+    }).collect();
+    let (best, best_score) = scored.iter().max_by(|a, b| a.1.total_cmp(&b.1))?;
+    if text_len(best) < MIN_ARTICLE_CHARS {
+        return None;
+    }
+
+    // Articles are often split over sibling blocks, around a figure or an inline ad
+    let threshold = (best_score * 0.2).max(10.0);
+    let siblings = dom::parent(best).map(|p| dom::child_elements(&p)).unwrap_or_else(|| vec![best.clone()]);
+    let mut parts = Vec::new();
+    for sibling in siblings {
+        let score = scored.iter().find(|(n, _)| Rc::ptr_eq(n, &sibling)).map_or(0.0, |(_, s)| *s);
+        let keep = Rc::ptr_eq(&sibling, best)
+            || score >= threshold
+            || (dom::tag_name(&sibling).as_deref() == Some("p") && text_len(&sibling) > 80 && link_density(&sibling) < 0.25);
+        if keep {
+            parts.push(dom::serialize_node(&sibling));
+        }
+    }
+    let mut html = String::from("<article>");
+    let has_heading = parts.iter().any(|p| p.contains("<h1"));
+    if let Some(heading) = heading.filter(|_| !has_heading) {
+        html.push_str(&format!("<h1>{}</h1>", escape_html(&heading)));
+    }
+    html.push_str(&parts.concat());
+    html.push_str("</article>");
+    Some(html)
+}
+
+impl App {
+    // `:reader` flips the `reader` setting, which stays on for the pages loaded after
+    pub fn toggle_reader(&mut self) {
+        self.settings.reader = !self.settings.reader;
+        self.rerender();
+        self.status = match self.settings.reader {
+            false => tr!("Reader mode off"),
+            true if extract(&self.raw_html).is_none() => tr!("Reader mode on (no article found on this page, so it is shown whole)"),
+            true => tr!("Reader mode on"),
+        };
+    }
+}
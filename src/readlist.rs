@@ -0,0 +1,105 @@
+use crate::about::escape_html;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct SavedPage {
+    pub url: String,
+    #[serde(default)]
+    pub title: String,
+    // The page's HTML as it was when saved, so it can be read again offline
+    pub html: String,
+    pub saved_at: u64,
+    // Re-fetched in the background and compared with `html` (see monitor.rs)
+    #[serde(default)]
+    pub watch: bool,
+    // The live page no longer renders the same as the saved copy
+    #[serde(default)]
+    pub changed: bool,
+}
+
+// Pages kept for later reading, one JSON object per line
+pub struct ReadingList {
+    path: PathBuf,
+    pub pages: Vec<SavedPage>,
+}
+
+impl ReadingList {
+    pub fn load(path: PathBuf) -> Self {
+        let pages = fs::read_to_string(&path)
+            .map(|data| data.lines().filter_map(|l| serde_json::from_str(l).ok()).collect())
+            .unwrap_or_default();
+        Self { path, pages }
+    }
+
+    // Saving a page again replaces the older copy, keeping whether it is watched
+    pub fn add(&mut self, url: &str, title: &str, html: &str) -> io::Result<()> {
+        let saved_at = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+        let watch = self.get(url).is_some_and(|p| p.watch);
+        self.pages.retain(|p| p.url != url);
+        self.pages.push(SavedPage { url: url.to_string(), title: title.to_string(), html: html.to_string(), saved_at, watch, changed: false });
+        self.write()
+    }
+
+    // Returns the new watch state, or None when the page isn't on the list
+    pub fn toggle_watch(&mut self, url: &str) -> io::Result<Option<bool>> {
+        let Some(page) = self.pages.iter_mut().find(|p| p.url == url) else { return Ok(None) };
+        page.watch = !page.watch;
+        let watch = page.watch;
+        self.write().map(|_| Some(watch))
+    }
+
+    pub fn mark_changed(&mut self, url: &str) -> io::Result<()> {
+        if let Some(page) = self.pages.iter_mut().find(|p| p.url == url) {
+            page.changed = true;
+        }
+        self.write()
+    }
+
+    pub fn remove(&mut self, url: &str) -> io::Result<bool> {
+        let before = self.pages.len();
+        self.pages.retain(|p| p.url != url);
+        if self.pages.len() == before {
+            return Ok(false);
+        }
+        self.write().map(|_| true)
+    }
+
+    pub fn get(&self, url: &str) -> Option<&SavedPage> {
+        self.pages.iter().find(|p| p.url == url)
+    }
+
+    fn write(&self) -> io::Result<()> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let mut data = String::new();
+        for page in &self.pages {
+            data.push_str(&serde_json::to_string(page)?);
+            data.push('\n');
+        }
+        fs::write(&self.path, data)
+    }
+}
+
+pub fn reading_list_page(list: &ReadingList, sandbox: bool) -> String {
+    let mut html = format!("<title>Reading list</title><h1>Reading list</h1><p>{} saved page(s)", list.pages.len());
+    if sandbox {
+        html.push_str(" — network access is disabled, pages open from their saved copies");
+    }
+    html.push_str("</p><ol>");
+    for page in list.pages.iter().rev() {
+        let title = if page.title.is_empty() { &page.url } else { &page.title };
+        let flags = match (page.watch, page.changed) {
+            (_, true) => " <b>[changed]</b>",
+            (true, false) => " [watched]",
+            (false, false) => "",
+        };
+        html.push_str(&format!("<li><a href=\"{0}\">{1}</a>{2}<br>{0}</li>", escape_html(&page.url), escape_html(title), flags));
+    }
+    html.push_str("</ol>");
+    html
+}
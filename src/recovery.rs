@@ -0,0 +1,175 @@
+use crate::app::App;
+use crate::history::HistoryTree;
+use crate::i18n::tr;
+use crate::tabs::Tab;
+use crate::types::{HistoryEntry, PagePosition};
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fs;
+use std::path::PathBuf;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+const INTERVAL: Duration = Duration::from_secs(15);
+
+#[derive(Serialize, Deserialize, PartialEq)]
+struct SavedPage {
+    url: String,
+    title: Option<String>,
+    // Seconds since the Unix epoch
+    visited: u64,
+    scroll: u16,
+    link: usize,
+}
+
+// The back/forward path through a tab's history and where on it the tab was
+#[derive(Serialize, Deserialize, PartialEq)]
+struct SavedTab {
+    pages: Vec<SavedPage>,
+    current: usize,
+}
+
+impl SavedTab {
+    fn new(history: &HistoryTree) -> Self {
+        let path = history.path();
+        // A new tab's empty first entry has nothing to reopen
+        let path: Vec<usize> = path.into_iter().filter(|&idx| !history.node(idx).entry.url.is_empty()).collect();
+        let pages = path.iter().map(|&idx| {
+            let e = &history.node(idx).entry;
+            SavedPage {
+                url: e.url.clone(),
+                title: e.title.clone(),
+                visited: e.visited_at.duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs()),
+                scroll: e.position.scroll,
+                link: e.position.selected_link_idx,
+            }
+        }).collect();
+        let current = path.iter().position(|&idx| idx == history.current).unwrap_or(0);
+        Self { pages, current }
+    }
+
+    // The history rebuilt, and the entry to reopen
+    fn history(&self) -> (HistoryTree, HistoryEntry) {
+        let mut history = HistoryTree::new(&self.pages[0].url);
+        for (i, page) in self.pages.iter().enumerate() {
+            if i > 0 {
+                history.visit(&page.url);
+            }
+            history.save(HistoryEntry {
+                url: page.url.clone(),
+                title: page.title.clone(),
+                visited_at: UNIX_EPOCH + Duration::from_secs(page.visited),
+                position: PagePosition { scroll: page.scroll, selected_link_idx: page.link },
+            });
+        }
+        let current = self.current.min(self.pages.len() - 1);
+        let entry = history.jump(current).unwrap_or_else(|| HistoryEntry::new(&self.pages[0].url));
+        (history, entry)
+    }
+}
+
+// Every tab, in order, and which one was shown
+#[derive(Serialize, Deserialize, PartialEq)]
+pub struct Snapshot {
+    tabs: Vec<SavedTab>,
+    active: usize,
+}
+
+// The session is written to a recovery file every few seconds while it changes, and the
+// file is removed on a clean exit; one still there at startup means the last run crashed.
+pub struct Autosave {
+    path: PathBuf,
+    due: Instant,
+    written: Option<Snapshot>,
+    // Left behind by the previous run, until restored
+    pub recovered: Option<Snapshot>,
+}
+
+impl Autosave {
+    pub fn load(path: PathBuf) -> Self {
+        let recovered = fs::read_to_string(&path).ok()
+            .and_then(|data| serde_json::from_str::<Snapshot>(&data).ok())
+            .map(|mut s| {
+                // Tabs with nothing to reopen are dropped, keeping the shown one pointed at
+                let before = s.tabs.iter().take(s.active).filter(|t| t.pages.is_empty()).count();
+                s.tabs.retain(|t| !t.pages.is_empty());
+                s.active = s.active.saturating_sub(before).min(s.tabs.len().saturating_sub(1));
+                s
+            })
+            .filter(|s| !s.tabs.is_empty());
+        Self { path, due: Instant::now() + INTERVAL, written: None, recovered }
+    }
+
+    // Called on a clean exit
+    pub fn clear(&self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+impl App {
+    // The shown tab's history is in App's own fields, the others' in their slots
+    fn snapshot(&mut self) -> Snapshot {
+        self.history.save(self.current_entry());
+        let tabs = self.tabs.iter().enumerate().map(|(i, tab)| match i == self.active_tab {
+            true => SavedTab::new(&self.history),
+            false => SavedTab::new(tab.history()),
+        }).collect();
+        Snapshot { tabs, active: self.active_tab }
+    }
+
+    pub fn autosave_tick(&mut self) {
+        if Instant::now() < self.autosave.due {
+            return;
+        }
+        self.autosave.due = Instant::now() + INTERVAL;
+        let snapshot = self.snapshot();
+        if self.autosave.written.as_ref() == Some(&snapshot) {
+            return;
+        }
+        let written = self.autosave.path.parent().map_or(Ok(()), fs::create_dir_all)
+            .and_then(|_| fs::write(&self.autosave.path, serde_json::to_string(&snapshot)?));
+        match written {
+            Ok(()) => self.autosave.written = Some(snapshot),
+            Err(e) => self.status = tr!("Session autosave failed: {}", e),
+        }
+    }
+
+    pub fn offer_recovery(&mut self) {
+        let Some(ref snapshot) = self.autosave.recovered else { return };
+        let tab = &snapshot.tabs[snapshot.active];
+        let page = &tab.pages[tab.current.min(tab.pages.len() - 1)];
+        let shown = page.title.clone().unwrap_or_else(|| page.url.clone());
+        let pages: usize = snapshot.tabs.iter().map(|t| t.pages.len()).sum();
+        let message = tr!("Voyager didn't exit cleanly last time.\nRestore that session ({} tabs, {} pages of history, at {})?", snapshot.tabs.len(), pages, shown);
+        self.confirm("Restore Session", message, "restore-session".to_string());
+    }
+
+    // Rebuilds each tab's back/forward history from the recovery file and reopens its current
+    // page. The first goes into the shown tab, the rest into new tabs after it, loading in the
+    // background.
+    pub async fn restore_session(&mut self) -> Result<(), Box<dyn Error>> {
+        let Some(snapshot) = self.autosave.recovered.take() else {
+            self.status = tr!("restore-session: no session to restore");
+            return Ok(());
+        };
+        let first = self.active_tab;
+        for (i, tab) in snapshot.tabs.iter().enumerate() {
+            let (history, entry) = tab.history();
+            if i == 0 {
+                self.history = history;
+                self.visited_at = SystemTime::now();
+                self.revisit(entry).await?;
+                continue;
+            }
+            self.tabs.insert(first + i, Tab::new(""));
+            let peek = self.peek_tab(first + i);
+            self.history = history;
+            let reopened = self.revisit(entry).await;
+            self.unpeek_tab(peek);
+            reopened?;
+        }
+        self.switch_tab(first + snapshot.active);
+        let pages: usize = snapshot.tabs.iter().map(|t| t.pages.len()).sum();
+        self.status = tr!("Restored {} tabs, {} pages of history", snapshot.tabs.len(), pages);
+        Ok(())
+    }
+}
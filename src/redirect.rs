@@ -0,0 +1,123 @@
+use crate::headers;
+use crate::homograph;
+use reqwest::redirect::Policy;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use url::Url;
+
+// Chains nobody took are dropped past this many, e.g. those of prefetches and downloads
+const MAX_PENDING: usize = 64;
+
+// A redirect the client followed: the address it left, and the status that sent it on
+#[derive(Clone)]
+pub struct Hop {
+    pub from: String,
+    pub status: u16,
+}
+
+// Why the policy refused to follow a redirect
+#[derive(Clone)]
+pub enum Stop {
+    // Past the redirects setting, or back to an address already visited
+    Limit,
+    // To a lookalike domain, with homograph::check's description of it
+    Lookalike(String),
+    // To another host, with headers voyager.toml sets for this one only; the client can't take
+    // them off, so the page load sends a fresh request to `to` instead
+    SiteHeaders { to: String, status: u16 },
+}
+
+#[derive(Default)]
+struct Chains {
+    hops: HashMap<String, Vec<Hop>>,
+    // Addresses whose redirect the policy refused to follow
+    stopped: HashMap<String, Stop>,
+    // The domains with [site_headers] in voyager.toml
+    sites: Vec<String>,
+}
+
+// The hops behind each redirected request, keyed by the address they ended at. The client's
+// redirect policy writes them; the page load that receives the response takes its own.
+#[derive(Clone, Default)]
+pub struct RedirectLog(Arc<Mutex<Chains>>);
+
+impl RedirectLog {
+    // The hops from `requested` to `url`, the address the response came from
+    pub fn take(&self, requested: &str, url: &str) -> Vec<Hop> {
+        if requested == url {
+            return Vec::new();
+        }
+        let Ok(mut chains) = self.0.lock() else { return Vec::new() };
+        chains.hops.remove(url).filter(|hops| hops.first().is_some_and(|h| h.from == requested)).unwrap_or_default()
+    }
+
+    // Why the redirect response from `url` came back, when the policy stopped following; None
+    // for one the client never follows (300, 304, no Location)
+    pub fn take_stopped(&self, url: &str) -> Option<Stop> {
+        self.0.lock().ok()?.stopped.remove(url)
+    }
+
+    // Where the redirect from `url` was going, when it was stopped for its site headers; left in
+    // place for the status page in case the load doesn't go on
+    pub fn site_headers_stop(&self, url: &str) -> Option<(String, u16)> {
+        match self.0.lock().ok()?.stopped.get(url)? {
+            Stop::SiteHeaders { to, status } => Some((to.clone(), *status)),
+            _ => None,
+        }
+    }
+
+    pub fn set_sites(&self, sites: Vec<String>) {
+        if let Ok(mut chains) = self.0.lock() {
+            chains.sites = sites;
+        }
+    }
+
+    // Whether `from` gets site headers that `to` doesn't
+    fn leaves_site_headers(&self, from: &Url, to: &Url) -> bool {
+        let Ok(chains) = self.0.lock() else { return false };
+        let (from, to) = (from.host_str().unwrap_or_default(), to.host_str().unwrap_or_default());
+        chains.sites.iter().any(|d| headers::matches(from, d) && !headers::matches(to, d))
+    }
+}
+
+// Follows at most `limit` redirects, none back to an address already visited, none onto a
+// lookalike domain from another host, and none to a host that shouldn't see this one's site
+// headers. Past any of them, the redirect response itself comes back and is shown as a page with
+// a link on, which asks before opening a lookalike like any other.
+pub fn policy(limit: usize, log: RedirectLog) -> Policy {
+    Policy::custom(move |attempt| {
+        let previous = attempt.previous();
+        let same_host = previous.last().is_some_and(|u| u.host_str() == attempt.url().host_str());
+        let leaves_site = !same_host && previous.last().is_some_and(|from| log.leaves_site_headers(from, attempt.url()));
+        let stop = match homograph::check(attempt.url()) {
+            Some(warning) if !same_host => Some(Stop::Lookalike(warning)),
+            _ if previous.len() > limit || previous.contains(attempt.url()) => Some(Stop::Limit),
+            _ if leaves_site => Some(Stop::SiteHeaders { to: attempt.url().to_string(), status: attempt.status().as_u16() }),
+            _ => None,
+        };
+        let from = previous.last().map(|u| u.to_string());
+        let (to, status) = (attempt.url().to_string(), attempt.status().as_u16());
+        let (Some(from), Ok(mut chains)) = (from, log.0.lock()) else {
+            return if stop.is_some() { attempt.stop() } else { attempt.follow() };
+        };
+        if chains.hops.len() + chains.stopped.len() >= MAX_PENDING {
+            chains.hops.clear();
+            chains.stopped.clear();
+        }
+        if let Some(stop) = stop {
+            chains.stopped.insert(from, stop);
+            return attempt.stop();
+        }
+        let mut hops = chains.hops.get(&from).cloned().unwrap_or_default();
+        hops.push(Hop { from, status });
+        chains.hops.insert(to, hops);
+        attempt.follow()
+    })
+}
+
+// "http://a (301) → https://b (302) → https://b/home" for the status bar
+pub fn describe(hops: &[Hop], to: &str) -> String {
+    let mut out: Vec<String> = hops.iter().map(|h| format!("{} ({})", h.from, h.status)).collect();
+    out.push(to.to_string());
+    out.join(" → ")
+}
@@ -0,0 +1,69 @@
+use crate::app::App;
+use crate::clipboard;
+use crate::i18n::{self, tr};
+use ratatui::{style::{Color, Modifier, Style}, text::{Line, Span}};
+
+// The register a yank goes to without a "x prefix; it also copies to the clipboard
+pub const UNNAMED: char = '"';
+
+pub fn is_register(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == UNNAMED || c == '+'
+}
+
+impl App {
+    // After `"`: the register the next y, Y or p uses
+    pub fn select_register(&mut self, c: char) {
+        if is_register(c) {
+            self.register = Some(c);
+            self.status = tr!("Register \"{}: y yanks the link URL, Y the page URL, p opens its contents", c);
+        } else {
+            self.status = tr!("Not a register: {}", c);
+        }
+    }
+
+    // Stores `text` in the selected register (or the unnamed one, then also the clipboard).
+    // Named registers keep their contents to themselves; `+` is the clipboard.
+    pub fn yank(&mut self, text: String, what: &str) {
+        let register = self.register.take().unwrap_or(UNNAMED);
+        let chars = text.chars().count();
+        let via = match register {
+            UNNAMED | '+' => format!(", {}", clipboard::copy(&text)),
+            _ => String::new(),
+        };
+        if register != '+' {
+            self.registers.insert(register, text.clone());
+        }
+        self.registers.insert(UNNAMED, text);
+        self.status = tr!("Yanked {} ({} chars) into \"{}{}", i18n::lookup(what), chars, register, via);
+    }
+
+    pub async fn open_register(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        let register = self.register.take().unwrap_or(UNNAMED);
+        let Some(text) = self.registers.get(&register).cloned() else {
+            self.status = tr!("Register \"{} is empty", register);
+            return Ok(());
+        };
+        let text = text.trim().to_string();
+        if text.contains(char::is_whitespace) || !text.contains(['.', ':']) {
+            let url = self.search_url(&text);
+            return self.navigate(url).await;
+        }
+        self.navigate(text).await
+    }
+
+    pub fn show_registers(&mut self) {
+        if self.registers.is_empty() {
+            self.status = tr!("No registers set; yank with y, Y or caret-mode y, optionally after \"x");
+            return;
+        }
+        let lines = self.registers.iter().map(|(name, text)| {
+            let flat = text.split_whitespace().collect::<Vec<_>>().join(" ");
+            let shown: String = flat.chars().take(200).collect();
+            Line::from(vec![
+                Span::styled(format!("\"{}  ", name), Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
+                Span::raw(shown),
+            ])
+        }).collect();
+        self.open_popup("Registers", lines);
+    }
+}
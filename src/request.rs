@@ -0,0 +1,136 @@
+use crate::app::{address_to_url, App};
+use crate::jobs::{self, Job, Received};
+use crate::i18n::tr;
+use crate::types::Mode;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{style::{Color, Modifier, Style}, text::{Line, Span}};
+use std::error::Error;
+use std::time::Duration;
+use url::Url;
+
+// Headers worth picking out when checking a URL: type, size, caching, CORS and allowed methods
+fn is_notable(name: &str) -> bool {
+    matches!(name, "content-type" | "content-length" | "allow" | "location" | "cache-control" | "last-modified" | "etag")
+        || name.starts_with("access-control-")
+}
+
+pub struct ComposedRequest {
+    pub method: reqwest::Method,
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+// Raw-HTTP-like text: "METHOD URL", then "Name: value" header lines, a blank line, and the body
+pub fn parse(text: &str) -> Result<ComposedRequest, String> {
+    let mut lines = text.lines();
+    let first = lines.next().unwrap_or_default().trim();
+    let (method, url) = first.split_once(' ').ok_or("first line must be `METHOD URL`")?;
+    let method = reqwest::Method::from_bytes(method.to_ascii_uppercase().as_bytes()).map_err(|_| format!("bad method {:?}", method))?;
+    let mut headers = Vec::new();
+    for line in lines.by_ref() {
+        if line.trim().is_empty() {
+            break;
+        }
+        let (k, v) = line.split_once(':').ok_or_else(|| format!("bad header line {:?}", line))?;
+        headers.push((k.trim().to_string(), v.trim().to_string()));
+    }
+    let body = lines.collect::<Vec<_>>().join("\n");
+    Ok(ComposedRequest { method, url: url.trim().to_string(), headers, body })
+}
+
+impl App {
+    pub fn open_request_composer(&mut self, url: &str) {
+        if self.request_buffer.is_empty() || !url.is_empty() {
+            let target = if url.is_empty() { self.current_url.as_str() } else { url };
+            self.request_buffer = format!("GET {}\nAccept: */*\n\n", target);
+        }
+        self.mode = Mode::Request;
+        self.status = tr!("Compose request — Ctrl-S send, Esc cancel");
+    }
+
+    pub async fn handle_request_key(&mut self, key: KeyEvent) -> Result<(), Box<dyn Error>> {
+        match key.code {
+            KeyCode::Esc => {
+                self.mode = Mode::Normal;
+                self.status = tr!("Request cancelled");
+            }
+            KeyCode::Char('s') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.mode = Mode::Normal;
+                match parse(&self.request_buffer) {
+                    Ok(req) => self.send_composed(req).await?,
+                    Err(e) => {
+                        self.mode = Mode::Request;
+                        self.status = tr!("request: {}", e);
+                    }
+                }
+            }
+            KeyCode::Enter => self.request_buffer.push('\n'),
+            KeyCode::Tab => self.request_buffer.push_str("    "),
+            KeyCode::Backspace => { self.request_buffer.pop(); }
+            KeyCode::Char(c) => self.request_buffer.push(c),
+            _ => {}
+        }
+        Ok(())
+    }
+
+    async fn send_composed(&mut self, req: ComposedRequest) -> Result<(), Box<dyn Error>> {
+        let base_url = Url::parse(&req.url)?;
+        let mut builder = self.net.request(req.method.clone(), base_url.clone());
+        for (k, v) in &req.headers {
+            builder = builder.header(k, v);
+        }
+        if !req.body.is_empty() {
+            builder = builder.body(req.body.clone());
+        }
+        self.status = format!("{} {}...", req.method, req.url);
+        self.load_request(&req.url, builder)
+    }
+
+    // `:head` / `:options`: the status line and headers of `arg` (default: the current page),
+    // without reading or rendering any body
+    pub fn show_headers(&mut self, method: reqwest::Method, arg: &str) -> Result<(), Box<dyn Error>> {
+        let url = if arg.is_empty() { self.current_url.clone() } else { address_to_url(arg) };
+        let target = Url::parse(&url)?;
+        self.status = format!("{} {}...", method, url);
+        let net = self.network()?.clone();
+        self.spawn_job(async move {
+            let result = jobs::receive(&net, net.request(method.clone(), target), false).await;
+            Job::Headers { method, url, result }
+        });
+        Ok(())
+    }
+
+    pub fn show_header_lines(&mut self, method: reqwest::Method, url: &str, received: Received) {
+        let Received { outgoing, incoming, ttfb, .. } = received;
+        let status_color = match incoming.status {
+            200..=299 => Color::Green,
+            300..=399 => Color::Yellow,
+            _ => Color::Red,
+        };
+        let mut lines = vec![
+            Line::from(Span::styled(format!("{} {} {}", incoming.http_version, incoming.status, incoming.status_text), Style::default().fg(status_color).add_modifier(Modifier::BOLD))),
+            Line::from(""),
+        ];
+        for (name, value) in &incoming.headers {
+            let style = if is_notable(name) { Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD) } else { Style::default().add_modifier(Modifier::BOLD) };
+            lines.push(Line::from(vec![Span::styled(format!("{}: ", name), style), Span::raw(value.clone())]));
+        }
+        if method == reqwest::Method::OPTIONS {
+            let allowed: Vec<&str> = incoming.headers.iter()
+                .filter(|(n, _)| n == "allow" || n == "access-control-allow-methods")
+                .map(|(_, v)| v.as_str())
+                .collect();
+            lines.push(Line::from(""));
+            lines.push(Line::from(match allowed.as_slice() {
+                [] => "The server did not list its allowed methods".to_string(),
+                methods => format!("Allowed methods: {}", methods.join(", ")),
+            }));
+        }
+        let title = format!("{} {}", method, url);
+        let status = tr!("{} {} -> {} ({} headers)", method, url, incoming.status, incoming.headers.len());
+        self.log_exchange(outgoing, incoming, (ttfb, Duration::ZERO, Duration::ZERO), 0, false);
+        self.open_popup(&title, lines);
+        self.status = status;
+    }
+}
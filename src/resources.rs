@@ -0,0 +1,103 @@
+use crate::app::App;
+use crate::dialog::DialogKind;
+use crate::dom;
+use crate::i18n::{self, tr};
+use crate::net::Net;
+use crate::types::PopupAction;
+use crate::ui::format_size;
+use ratatui::{style::{Color, Style}, text::{Line, Span}};
+use std::error::Error;
+use std::time::Duration;
+use tokio::task::JoinSet;
+use url::Url;
+
+// HEAD requests for sizes are skipped past this many resources, and given up on after this long
+const MAX_SIZED: usize = 200;
+const HEAD_TIMEOUT: Duration = Duration::from_secs(5);
+
+// (kind, absolute URL) of everything the page declares it loads, in document order
+pub fn subresources(html: &str, base_url: &Url) -> Vec<(&'static str, String)> {
+    let document = dom::parse(html);
+    let mut out: Vec<(&'static str, String)> = Vec::new();
+    for node in dom::elements(&document.document) {
+        let rel = dom::attr(&node, "rel").unwrap_or_default().to_ascii_lowercase();
+        let rels: Vec<&str> = rel.split_whitespace().collect();
+        let found = match dom::tag_name(&node).as_deref() {
+            Some("link") if rels.contains(&"stylesheet") => Some(("stylesheet", dom::attr(&node, "href"))),
+            Some("link") if rels.contains(&"icon") => Some(("icon", dom::attr(&node, "href"))),
+            Some("link") if rels.iter().any(|r| matches!(*r, "preload" | "modulepreload")) => Some(("preload", dom::attr(&node, "href"))),
+            Some("link") if rels.iter().any(|r| matches!(*r, "prefetch" | "prerender")) => Some(("prefetch", dom::attr(&node, "href"))),
+            Some("script") => Some(("script", dom::attr(&node, "src"))),
+            Some("img") => Some(("image", dom::attr(&node, "src"))),
+            Some("source") => Some(("media", dom::attr(&node, "src"))),
+            Some("audio" | "video") => Some(("media", dom::attr(&node, "src"))),
+            Some("iframe" | "frame") => Some(("frame", dom::attr(&node, "src"))),
+            _ => None,
+        };
+        let Some((kind, Some(href))) = found else { continue };
+        let href = href.trim();
+        if href.is_empty() || href.starts_with("data:") {
+            continue;
+        }
+        let url = base_url.join(href).map(|u| u.to_string()).unwrap_or_else(|_| href.to_string());
+        if !out.iter().any(|(_, u)| *u == url) {
+            out.push((kind, url));
+        }
+    }
+    out
+}
+
+// Content-Length from a HEAD request; None when the server doesn't say or doesn't answer in time
+async fn head_size(net: Net, url: String) -> Option<u64> {
+    let res = net.send(net.head(&url).timeout(HEAD_TIMEOUT)).await.ok()?;
+    res.headers().get(reqwest::header::CONTENT_LENGTH)?.to_str().ok()?.parse().ok()
+}
+
+impl App {
+    // Lists the page's subresources with their sizes; the sizes are asked for only now
+    pub async fn show_resources(&mut self) -> Result<(), Box<dyn Error>> {
+        let base = Url::parse(&self.current_url)?;
+        let resources = subresources(&self.raw_html, &base);
+        if resources.is_empty() {
+            self.status = tr!("resources: the page declares no subresources");
+            return Ok(());
+        }
+        let mut sizes = vec![None; resources.len()];
+        if let Ok(net) = self.network().cloned() {
+            self.status = tr!("Checking the sizes of {} resources...", resources.len().min(MAX_SIZED));
+            let mut heads = JoinSet::new();
+            for (i, (_, url)) in resources.iter().enumerate().take(MAX_SIZED) {
+                let net = net.clone();
+                let url = url.clone();
+                heads.spawn(async move { (i, head_size(net, url).await) });
+            }
+            while let Some(Ok((i, size))) = heads.join_next().await {
+                sizes[i] = size;
+            }
+        }
+        let total: u64 = sizes.iter().flatten().sum();
+        let dim = Style::default().fg(Color::DarkGray);
+        let entries = resources.into_iter().zip(&sizes).map(|((kind, url), size)| {
+            let size = size.map_or("?".to_string(), |s| format_size(s as usize));
+            let line = Line::from(vec![
+                Span::raw(i18n::pad(i18n::lookup(kind), 11)),
+                Span::styled(format!("{:>10}  ", size), dim),
+                Span::raw(url.clone()),
+            ]);
+            (line, PopupAction::Resource(url))
+        }).collect::<Vec<_>>();
+        self.status = tr!("{} resources, {} known in total — Enter to open, inspect or save one", entries.len(), format_size(total as usize));
+        self.open_menu(&tr!("Resources ({})", entries.len()), entries);
+        Ok(())
+    }
+
+    pub fn resource_menu(&mut self, url: &str) {
+        let options = vec![
+            (tr!("Open"), format!("url {}", url)),
+            (tr!("Show headers (HEAD)"), format!("head {}", url)),
+            (tr!("Save to disk"), format!("save-url {}", url)),
+            (tr!("Cancel"), String::new()),
+        ];
+        self.open_dialog("Resource", url.to_string(), DialogKind::Choice { options, selected: 0 });
+    }
+}
@@ -0,0 +1,75 @@
+use crate::app::App;
+use crossterm::event::{MouseEvent, MouseEventKind};
+use std::time::Duration;
+
+// A smooth scroll is spread over this many redraws, this far apart
+const FRAMES: u16 = 4;
+pub const FRAME: Duration = Duration::from_millis(16);
+// Lines of the last screen still shown after a full-page scroll
+const PAGE_OVERLAP: i32 = 2;
+
+pub struct SmoothScroll {
+    target: u16,
+    step: u16,
+    // Where the last frame left the page; anything else means something else moved it
+    last: u16,
+}
+
+impl App {
+    // Scrolls by `delta` lines, animated when smooth scrolling is on. Presses during an
+    // animation add on to where it is heading rather than where it has got to.
+    pub fn scroll_by(&mut self, delta: i32) {
+        let from = self.heading();
+        let target = (from as i32 + delta).clamp(0, self.max_scroll() as i32) as u16;
+        let distance = target.abs_diff(self.scroll);
+        if !self.settings.smooth_scroll || distance <= 1 {
+            self.smooth = None;
+            self.scroll = target;
+            return;
+        }
+        self.smooth = Some(SmoothScroll { target, step: distance.div_ceil(FRAMES), last: self.scroll });
+    }
+
+    // Where the page is scrolling to, or where it is when it isn't moving
+    fn heading(&self) -> u16 {
+        self.smooth.as_ref().filter(|s| s.last == self.scroll).map_or(self.scroll, |s| s.target)
+    }
+
+    // gg / G and Home / End; `line` is clamped like any other scroll
+    pub fn scroll_to(&mut self, line: u16) {
+        self.scroll_by(line as i32 - self.heading() as i32);
+    }
+
+    // Ctrl-D / Ctrl-U move half a screen; Ctrl-F / Ctrl-B a whole one less two lines, which stay
+    // on screen for context, as in less and vim
+    pub fn scroll_screen(&mut self, half: bool, down: bool) {
+        let height = self.viewport_height.max(1) as i32;
+        let lines = if half { (height / 2).max(1) } else { (height - PAGE_OVERLAP).max(1) };
+        self.scroll_by(if down { lines } else { -lines });
+    }
+
+    // Advances a smooth scroll by one frame; true while frames are left to draw
+    pub fn animate_scroll(&mut self) -> bool {
+        let Some(s) = self.smooth.as_mut() else { return false };
+        if self.scroll != s.last {
+            self.smooth = None;
+            return false;
+        }
+        self.scroll = if s.target > self.scroll { (self.scroll + s.step).min(s.target) } else { self.scroll.saturating_sub(s.step).max(s.target) };
+        s.last = self.scroll;
+        if self.scroll == s.target {
+            self.smooth = None;
+        }
+        self.smooth.is_some()
+    }
+
+    // The wheel scrolls the page by its own step, separate from j / k
+    pub fn handle_mouse(&mut self, mouse: MouseEvent) {
+        let step = self.settings.wheel_step as i32;
+        match mouse.kind {
+            MouseEventKind::ScrollDown => self.scroll_by(step),
+            MouseEventKind::ScrollUp => self.scroll_by(-step),
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,103 @@
+use crate::app::App;
+use crate::caret::restyle_range;
+use crate::i18n::tr;
+use ratatui::{style::{Modifier, Style}, text::Line};
+
+// A `/` search over the text in the content pane. Matches are found again from the
+// lines on every use, so they follow the pane through alternate views and re-layouts.
+pub struct Search {
+    pub query: String,
+    case_sensitive: bool,
+    // (line, column) of the match last jumped to
+    current: Option<(usize, usize)>,
+}
+
+fn fold(c: char, case_sensitive: bool) -> char {
+    if case_sensitive { c } else { c.to_lowercase().next().unwrap_or(c) }
+}
+
+// Start columns of the non-overlapping occurrences of `query` in `line`
+fn find_in_line(line: &Line, query: &[char], case_sensitive: bool) -> Vec<usize> {
+    let chars: Vec<char> = line.spans.iter().flat_map(|s| s.content.chars()).map(|c| fold(c, case_sensitive)).collect();
+    let mut found = Vec::new();
+    let mut col = 0;
+    while !query.is_empty() && col + query.len() <= chars.len() {
+        if chars[col..col + query.len()] == *query {
+            found.push(col);
+            col += query.len();
+        } else {
+            col += 1;
+        }
+    }
+    found
+}
+
+impl Search {
+    fn folded_query(&self) -> Vec<char> {
+        self.query.chars().map(|c| fold(c, self.case_sensitive)).collect()
+    }
+}
+
+impl App {
+    // Case-insensitive, unless smartcase is on and the query has a capital letter
+    pub fn start_search(&mut self, query: &str) {
+        if query.is_empty() {
+            self.search = None;
+            return;
+        }
+        let case_sensitive = self.settings.smartcase && query.chars().any(char::is_uppercase);
+        self.search = Some(Search { query: query.to_string(), case_sensitive, current: None });
+        self.next_match(true);
+    }
+
+    fn search_matches(&self) -> Vec<(usize, usize)> {
+        let Some(ref search) = self.search else { return Vec::new() };
+        let query = search.folded_query();
+        self.content_lines.iter().enumerate()
+            .flat_map(|(row, line)| find_in_line(line, &query, search.case_sensitive).into_iter().map(move |col| (row, col)))
+            .collect()
+    }
+
+    // n / N. The first jump goes to the first match from the top of the screen down.
+    pub fn next_match(&mut self, forward: bool) {
+        let matches = self.search_matches();
+        let Some(search) = self.search.as_mut() else { return };
+        if matches.is_empty() {
+            self.status = tr!("Pattern not found: {}", search.query);
+            return;
+        }
+        let from = search.current.unwrap_or((self.scroll as usize, 0));
+        let (idx, wrapped) = match (forward, search.current) {
+            (true, None) => matches.iter().position(|&m| m >= from).map_or((0, true), |i| (i, false)),
+            (true, Some(_)) => matches.iter().position(|&m| m > from).map_or((0, true), |i| (i, false)),
+            (false, _) => matches.iter().rposition(|&m| m < from).map_or((matches.len() - 1, true), |i| (i, false)),
+        };
+        search.current = Some(matches[idx]);
+        let query = search.query.clone();
+        let line = matches[idx].0;
+        let viewport = self.viewport_height.max(1) as usize;
+        if line < self.scroll as usize || line >= self.scroll as usize + viewport {
+            self.scroll = (line.saturating_sub(viewport / 3) as u16).min(self.max_scroll());
+        }
+        self.status = match wrapped {
+            true if forward => tr!("/{}: match {} of {} (wrapped to the top)", query, idx + 1, matches.len()),
+            true => tr!("/{}: match {} of {} (wrapped to the bottom)", query, idx + 1, matches.len()),
+            false => tr!("/{}: match {} of {}", query, idx + 1, matches.len()),
+        };
+    }
+
+    pub fn search_overlay(&self, row: usize, line: Line<'static>) -> Line<'static> {
+        let Some(ref search) = self.search else { return line };
+        let query = search.folded_query();
+        let mut line = line;
+        for col in find_in_line(&line, &query, search.case_sensitive) {
+            let style = if search.current == Some((row, col)) {
+                self.palette().highlight().add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().add_modifier(Modifier::REVERSED)
+            };
+            line = restyle_range(line, col, col + query.len(), style);
+        }
+        line
+    }
+}
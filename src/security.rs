@@ -0,0 +1,58 @@
+use crate::app::App;
+use ratatui::style::Color;
+
+// What the URL bar shows about how the page reached us
+#[derive(Clone, Copy, PartialEq)]
+pub enum Security {
+    // about:, file: and other pages that never crossed the network
+    Local,
+    // Plain http://
+    Insecure,
+    // https:// with a certificate rustls verified during this fetch
+    Verified,
+    // https:// shown without a verified connection behind it, e.g. a saved copy
+    Unverified,
+}
+
+impl Security {
+    pub fn glyph(self, ascii: bool) -> &'static str {
+        match (self, ascii) {
+            (Security::Local, _) => "",
+            (Security::Insecure, false) => "⚠ Not secure ",
+            (Security::Insecure, true) => "! Not secure ",
+            (Security::Verified, false) => "🔒 ",
+            (Security::Verified, true) => "[TLS] ",
+            (Security::Unverified, false) => "◌ Not verified ",
+            (Security::Unverified, true) => "? Not verified ",
+        }
+    }
+
+    pub fn describe(self) -> &'static str {
+        match self {
+            Security::Local => "Local page, no network connection",
+            Security::Insecure => "Unencrypted HTTP",
+            Security::Verified => "HTTPS with a verified certificate",
+            Security::Unverified => "HTTPS, but not fetched over a verified connection (saved copy)",
+        }
+    }
+
+    pub fn color(self) -> Color {
+        match self {
+            Security::Local => Color::Reset,
+            Security::Insecure => Color::Red,
+            Security::Verified => Color::Green,
+            Security::Unverified => Color::Yellow,
+        }
+    }
+}
+
+impl App {
+    pub fn security(&self) -> Security {
+        match self.current_url.split(':').next() {
+            Some("https") if self.tls_verified => Security::Verified,
+            Some("https") => Security::Unverified,
+            Some("http") => Security::Insecure,
+            _ => Security::Local,
+        }
+    }
+}
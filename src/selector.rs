@@ -0,0 +1,205 @@
+use crate::dom;
+use markup5ever_rcdom::Handle;
+
+// A practical CSS subset: type/universal, #id, .class, [attr], [attr=v|^=|$=|*=|~=],
+// :first-child/:last-child, descendant and child combinators, and comma-separated groups.
+enum AttrOp {
+    Exists,
+    Equals(String),
+    Prefix(String),
+    Suffix(String),
+    Contains(String),
+    Word(String),
+}
+
+#[derive(Default)]
+struct Compound {
+    tag: Option<String>,
+    id: Option<String>,
+    classes: Vec<String>,
+    attrs: Vec<(String, AttrOp)>,
+    first_child: bool,
+    last_child: bool,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Combinator {
+    Descendant,
+    Child,
+}
+
+// Stored right-to-left: the first compound is the subject, each followed by the
+// combinator linking it to the next compound towards the root
+struct Complex(Vec<(Compound, Combinator)>);
+
+pub struct Selector(Vec<Complex>);
+
+fn ident(chars: &[char], i: &mut usize) -> String {
+    let start = *i;
+    while *i < chars.len() && (chars[*i].is_alphanumeric() || chars[*i] == '-' || chars[*i] == '_') {
+        *i += 1;
+    }
+    chars[start..*i].iter().collect()
+}
+
+fn parse_attr(chars: &[char], i: &mut usize) -> Result<(String, AttrOp), String> {
+    *i += 1;
+    let name = ident(chars, i).to_lowercase();
+    let mut op = String::new();
+    while *i < chars.len() && "=^$*~".contains(chars[*i]) {
+        op.push(chars[*i]);
+        *i += 1;
+    }
+    if op.is_empty() {
+        if chars.get(*i) != Some(&']') {
+            return Err("expected ] in attribute selector".to_string());
+        }
+        *i += 1;
+        return Ok((name, AttrOp::Exists));
+    }
+    let value = match chars.get(*i) {
+        Some(&q) if q == '"' || q == '\'' => {
+            *i += 1;
+            let start = *i;
+            while *i < chars.len() && chars[*i] != q { *i += 1; }
+            let v: String = chars[start..*i].iter().collect();
+            *i += 1;
+            v
+        }
+        _ => ident(chars, i),
+    };
+    if chars.get(*i) != Some(&']') {
+        return Err("expected ] in attribute selector".to_string());
+    }
+    *i += 1;
+    let op = match op.as_str() {
+        "=" => AttrOp::Equals(value),
+        "^=" => AttrOp::Prefix(value),
+        "$=" => AttrOp::Suffix(value),
+        "*=" => AttrOp::Contains(value),
+        "~=" => AttrOp::Word(value),
+        other => return Err(format!("unsupported attribute operator {}", other)),
+    };
+    Ok((name, op))
+}
+
+fn parse_compound(chars: &[char], i: &mut usize) -> Result<Compound, String> {
+    let mut c = Compound::default();
+    let start = *i;
+    while *i < chars.len() {
+        match chars[*i] {
+            '*' => *i += 1,
+            '#' => { *i += 1; c.id = Some(ident(chars, i)); }
+            '.' => { *i += 1; c.classes.push(ident(chars, i)); }
+            '[' => c.attrs.push(parse_attr(chars, i)?),
+            ':' => {
+                *i += 1;
+                match ident(chars, i).as_str() {
+                    "first-child" => c.first_child = true,
+                    "last-child" => c.last_child = true,
+                    other => return Err(format!("unsupported pseudo-class :{}", other)),
+                }
+            }
+            ch if ch.is_alphanumeric() => c.tag = Some(ident(chars, i).to_lowercase()),
+            _ => break,
+        }
+    }
+    if *i == start {
+        return Err(format!("unexpected character {:?}", chars.get(*i).copied().unwrap_or(' ')));
+    }
+    Ok(c)
+}
+
+impl Selector {
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let mut groups = Vec::new();
+        for group in input.split(',') {
+            let chars: Vec<char> = group.trim().chars().collect();
+            if chars.is_empty() {
+                return Err("empty selector".to_string());
+            }
+            let mut parts = Vec::new();
+            let mut i = 0;
+            let mut combinator = Combinator::Descendant;
+            while i < chars.len() {
+                parts.push((parse_compound(&chars, &mut i)?, combinator));
+                combinator = Combinator::Descendant;
+                while i < chars.len() && (chars[i].is_whitespace() || chars[i] == '>') {
+                    if chars[i] == '>' { combinator = Combinator::Child; }
+                    i += 1;
+                }
+            }
+            // Shift combinators so each compound carries the link to its left neighbour
+            let combinators: Vec<Combinator> = parts.iter().map(|(_, c)| *c).collect();
+            let mut complex: Vec<(Compound, Combinator)> = parts.into_iter().map(|(c, _)| (c, Combinator::Descendant)).collect();
+            for (idx, comb) in combinators.iter().enumerate().skip(1) {
+                complex[idx].1 = *comb;
+            }
+            complex.reverse();
+            groups.push(Complex(complex));
+        }
+        Ok(Selector(groups))
+    }
+
+    pub fn matches(&self, node: &Handle) -> bool {
+        self.0.iter().any(|c| matches_complex(&c.0, node))
+    }
+
+    // Matching elements in document order, skipping descendants of an already matched element
+    pub fn select(&self, root: &Handle) -> Vec<Handle> {
+        let mut out: Vec<Handle> = Vec::new();
+        for node in dom::elements(root) {
+            if self.matches(&node) && !out.iter().any(|m| dom::is_ancestor(m, &node)) {
+                out.push(node);
+            }
+        }
+        out
+    }
+}
+
+fn matches_compound(c: &Compound, node: &Handle) -> bool {
+    let Some(tag) = dom::tag_name(node) else { return false };
+    if c.tag.as_ref().is_some_and(|t| *t != tag) { return false; }
+    if c.id.as_ref().is_some_and(|id| dom::attr(node, "id").as_ref() != Some(id)) { return false; }
+    let classes = dom::attr(node, "class").unwrap_or_default();
+    if !c.classes.iter().all(|cl| classes.split_whitespace().any(|x| x == cl)) { return false; }
+    for (name, op) in &c.attrs {
+        let Some(v) = dom::attr(node, name) else { return false };
+        let ok = match op {
+            AttrOp::Exists => true,
+            AttrOp::Equals(x) => v == *x,
+            AttrOp::Prefix(x) => v.starts_with(x.as_str()),
+            AttrOp::Suffix(x) => v.ends_with(x.as_str()),
+            AttrOp::Contains(x) => v.contains(x.as_str()),
+            AttrOp::Word(x) => v.split_whitespace().any(|w| w == x),
+        };
+        if !ok { return false; }
+    }
+    if c.first_child || c.last_child {
+        let siblings = dom::parent(node).map(|p| dom::child_elements(&p)).unwrap_or_default();
+        if c.first_child && !siblings.first().is_some_and(|s| std::rc::Rc::ptr_eq(s, node)) { return false; }
+        if c.last_child && !siblings.last().is_some_and(|s| std::rc::Rc::ptr_eq(s, node)) { return false; }
+    }
+    true
+}
+
+fn matches_complex(parts: &[(Compound, Combinator)], node: &Handle) -> bool {
+    let Some(((compound, combinator), rest)) = parts.split_first() else { return true };
+    if !matches_compound(compound, node) {
+        return false;
+    }
+    if rest.is_empty() {
+        return true;
+    }
+    let mut ancestor = dom::parent(node);
+    while let Some(a) = ancestor {
+        if matches_complex(rest, &a) {
+            return true;
+        }
+        if *combinator == Combinator::Child {
+            return false;
+        }
+        ancestor = dom::parent(&a);
+    }
+    false
+}
@@ -0,0 +1,74 @@
+use crate::app::App;
+use crate::homograph;
+use crate::i18n::tr;
+use crate::jobs::Job;
+use crate::net::Net;
+use crate::proxy;
+use std::error::Error;
+use url::Url;
+
+const SHORTENERS: &[&str] = &[
+    "bit.ly", "t.co", "tinyurl.com", "goo.gl", "ow.ly", "is.gd", "buff.ly", "rebrand.ly", "t.ly",
+    "cutt.ly", "shorturl.at", "lnkd.in", "tiny.cc", "rb.gy", "bit.do", "s.id", "v.gd", "dlvr.it",
+];
+const MAX_HOPS: usize = 5;
+
+pub fn is_shortened(url: &str) -> bool {
+    Url::parse(url).ok()
+        .and_then(|u| u.host_str().map(|h| h.trim_start_matches("www.").to_string()))
+        .is_some_and(|host| SHORTENERS.contains(&host.as_str()))
+}
+
+// Follows the shortener's redirects without fetching the destination. HEAD first; some
+// shorteners only redirect a GET, whose body is then never read.
+async fn resolve(net: &Net, client: &reqwest::Client, url: &str) -> Result<Option<String>, Box<dyn Error>> {
+    let mut current = Url::parse(url)?;
+    for _ in 0..MAX_HOPS {
+        let mut res = net.send(client.head(current.clone())).await?;
+        if !res.status().is_redirection() {
+            res = net.send(client.get(current.clone())).await?;
+        }
+        let location = res.headers().get(reqwest::header::LOCATION).and_then(|v| v.to_str().ok()).map(str::to_string);
+        let Some(location) = location.filter(|_| res.status().is_redirection()) else { break };
+        current = current.join(&location)?;
+        if !is_shortened(current.as_str()) {
+            break;
+        }
+    }
+    Ok((current.as_str() != url).then(|| current.to_string()))
+}
+
+impl App {
+    // Shows where a short link leads and navigates there once confirmed
+    pub fn preview_short_url(&mut self, url: String) -> Result<(), Box<dyn Error>> {
+        let net = self.network()?.clone();
+        let builder = reqwest::Client::builder()
+            .redirect(reqwest::redirect::Policy::none())
+            .user_agent(self.settings.user_agent.as_str());
+        let client = proxy::apply(builder, &self.settings.proxy).build()?;
+        self.status = tr!("Resolving {}...", url);
+        self.spawn_job(async move {
+            let result = resolve(&net, &client, &url).await.map_err(|e| e.to_string());
+            Job::ShortLink { url, result }
+        });
+        Ok(())
+    }
+
+    pub async fn show_short_link(&mut self, url: String, target: Option<String>) -> Result<(), Box<dyn Error>> {
+        match target {
+            // `url!` skips the lookalike check too, so it is made here
+            Some(target) => match Url::parse(&target).ok().as_ref().and_then(homograph::check) {
+                Some(warning) => {
+                    let message = tr!("{}\nleads to\n{}\n\n{}\n\nThis may be a phishing site. Open it anyway?", url, target, warning);
+                    self.confirm("Lookalike Domain", message, format!("url! {}", target));
+                }
+                None => self.confirm("Short Link", tr!("{}\nleads to\n{}\n\nOpen it?", url, target), format!("url! {}", target)),
+            },
+            None => {
+                self.status = tr!("{} doesn't redirect anywhere, opening it", url);
+                self.go_to(url).await?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,163 @@
+use crate::about::escape_html;
+use crate::dom;
+use crate::net::Net;
+use std::error::Error;
+use url::Url;
+
+// One User-agent group of robots.txt: the agents it names and its (directive, value) rules
+struct RobotsGroup {
+    agents: Vec<String>,
+    rules: Vec<(String, String)>,
+}
+
+struct Robots {
+    groups: Vec<RobotsGroup>,
+    sitemaps: Vec<String>,
+}
+
+fn parse_robots(text: &str) -> Robots {
+    let mut robots = Robots { groups: Vec::new(), sitemaps: Vec::new() };
+    // Consecutive User-agent lines share the rules that follow them
+    let mut in_agents = false;
+    for line in text.lines() {
+        let line = line.split('#').next().unwrap_or("").trim();
+        let Some((key, value)) = line.split_once(':') else { continue };
+        let (key, value) = (key.trim().to_ascii_lowercase(), value.trim().to_string());
+        match key.as_str() {
+            "user-agent" => {
+                match robots.groups.last_mut() {
+                    Some(group) if in_agents => group.agents.push(value),
+                    _ => robots.groups.push(RobotsGroup { agents: vec![value], rules: Vec::new() }),
+                }
+                in_agents = true;
+            }
+            "sitemap" => robots.sitemaps.push(value),
+            _ => {
+                in_agents = false;
+                if let Some(group) = robots.groups.last_mut() {
+                    group.rules.push((key, value));
+                }
+            }
+        }
+    }
+    robots
+}
+
+async fn fetch_text(net: &Net, url: &Url) -> Result<String, Box<dyn Error>> {
+    let res = net.send(net.get(url.clone())).await?.error_for_status()?;
+    Ok(res.text().await?)
+}
+
+pub fn origin(url: &Url) -> Option<Url> {
+    url.join("/").ok().filter(|u| matches!(u.scheme(), "http" | "https"))
+}
+
+fn sitemap_link(url: &str) -> String {
+    let about = format!("about:sitemap?url={}", url::form_urlencoded::byte_serialize(url.as_bytes()).collect::<String>());
+    format!("<a href=\"{}\">{}</a>", escape_html(&about), escape_html(url))
+}
+
+pub async fn robots_page(net: &Net, site: &Url) -> String {
+    let url = site.join("/robots.txt").unwrap_or_else(|_| site.clone());
+    let text = match fetch_text(net, &url).await {
+        Ok(text) => text,
+        Err(e) => return format!("<title>robots.txt</title><h1>robots.txt</h1><p>{}: {}</p>", escape_html(url.as_str()), escape_html(&e.to_string())),
+    };
+    let robots = parse_robots(&text);
+    let mut html = format!("<title>robots.txt</title><h1>robots.txt for {}</h1><p>{} group(s)</p>", escape_html(site.as_str()), robots.groups.len());
+    for group in &robots.groups {
+        html.push_str(&format!("<h2>User-agent: {}</h2>", escape_html(&group.agents.join(", "))));
+        if group.rules.is_empty() {
+            html.push_str("<p>No rules: everything is allowed.</p>");
+            continue;
+        }
+        html.push_str("<table>");
+        for (directive, value) in &group.rules {
+            // Plain paths link to the page they allow or forbid; patterns are shown as written
+            let shown = match site.join(value) {
+                Ok(target) if value.starts_with('/') && !value.contains(['*', '$']) => {
+                    format!("<a href=\"{}\">{}</a>", escape_html(target.as_str()), escape_html(value))
+                }
+                _ => escape_html(value),
+            };
+            html.push_str(&format!("<tr><td><b>{}</b></td><td>{}</td></tr>", escape_html(directive), shown));
+        }
+        html.push_str("</table>");
+    }
+    if !robots.sitemaps.is_empty() {
+        html.push_str("<h2>Sitemaps</h2><ul>");
+        for sitemap in &robots.sitemaps {
+            html.push_str(&format!("<li>{}</li>", sitemap_link(sitemap)));
+        }
+        html.push_str("</ul>");
+    }
+    html
+}
+
+// `<loc>` (and `<lastmod>`) of each `<url>` or nested `<sitemap>` entry
+fn sitemap_entries(xml: &str, entry: &str) -> Vec<(String, String)> {
+    let document = dom::parse(xml);
+    dom::elements(&document.document).iter()
+        .filter(|e| dom::tag_name(e).as_deref() == Some(entry))
+        .filter_map(|e| {
+            let children = dom::child_elements(e);
+            let text = |tag: &str| children.iter().find(|c| dom::tag_name(c).as_deref() == Some(tag)).map(|c| dom::text_content(c).trim().to_string());
+            Some((text("loc")?, text("lastmod").unwrap_or_default()))
+        })
+        .collect()
+}
+
+// `target` is a sitemap URL, or a site whose sitemaps are found through robots.txt
+pub async fn sitemap_page(net: &Net, target: &Url) -> String {
+    let sitemaps: Vec<String> = if target.path().ends_with(".xml") {
+        vec![target.to_string()]
+    } else {
+        let robots = match target.join("/robots.txt") {
+            Ok(url) => fetch_text(net, &url).await.map(|t| parse_robots(&t).sitemaps).unwrap_or_default(),
+            Err(_) => Vec::new(),
+        };
+        if robots.is_empty() {
+            target.join("/sitemap.xml").map(|u| vec![u.to_string()]).unwrap_or_default()
+        } else {
+            robots
+        }
+    };
+    if let [only] = sitemaps.as_slice() {
+        return single_sitemap(net, only).await;
+    }
+    let mut html = format!("<title>Sitemaps</title><h1>Sitemaps for {}</h1><p>Listed in robots.txt:</p><ul>", escape_html(target.as_str()));
+    for sitemap in &sitemaps {
+        html.push_str(&format!("<li>{}</li>", sitemap_link(sitemap)));
+    }
+    html.push_str("</ul>");
+    html
+}
+
+async fn single_sitemap(net: &Net, url: &str) -> String {
+    let xml = match Url::parse(url) {
+        Ok(u) => fetch_text(net, &u).await,
+        Err(e) => Err(e.into()),
+    };
+    let xml = match xml {
+        Ok(xml) => xml,
+        Err(e) => return format!("<title>Sitemap</title><h1>Sitemap</h1><p>{}: {}</p>", escape_html(url), escape_html(&e.to_string())),
+    };
+    let children = sitemap_entries(&xml, "sitemap");
+    let pages = sitemap_entries(&xml, "url");
+    let mut html = format!("<title>Sitemap</title><h1>Sitemap</h1><p>{}: {} page(s), {} nested sitemap(s)</p>", escape_html(url), pages.len(), children.len());
+    if !children.is_empty() {
+        html.push_str("<h2>Sitemaps</h2><ul>");
+        for (loc, lastmod) in &children {
+            html.push_str(&format!("<li>{} {}</li>", sitemap_link(loc), escape_html(lastmod)));
+        }
+        html.push_str("</ul>");
+    }
+    if !pages.is_empty() {
+        html.push_str("<h2>Pages</h2><ul>");
+        for (loc, lastmod) in &pages {
+            html.push_str(&format!("<li><a href=\"{0}\">{0}</a> {1}</li>", escape_html(loc), escape_html(lastmod)));
+        }
+        html.push_str("</ul>");
+    }
+    html
+}
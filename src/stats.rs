@@ -0,0 +1,72 @@
+use crate::about::escape_html;
+use crate::har::iso8601;
+use crate::types::HistoryEntry;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::time::{Duration, SystemTime};
+
+// Longer gaps between visits are time away from the browser, not reading
+const IDLE: Duration = Duration::from_secs(30 * 60);
+const TOP_DOMAINS: usize = 10;
+
+fn minutes(d: Duration) -> String {
+    match d.as_secs() {
+        s if s < 60 => format!("{}s", s),
+        s if s < 3600 => format!("{}m {}s", s / 60, s % 60),
+        s => format!("{}h {}m", s / 3600, s % 3600 / 60),
+    }
+}
+
+// Built from the visit log, so it covers every tab and past sessions: every visit counts, and
+// the time on a page is the time until the next visit (or now), ignoring idle gaps
+pub fn stats_page(log: &[HistoryEntry]) -> String {
+    let mut visits: Vec<(SystemTime, &str)> = log.iter()
+        .filter(|e| e.url.starts_with("http://") || e.url.starts_with("https://"))
+        .map(|e| (e.visited_at, e.url.as_str()))
+        .collect();
+    visits.sort();
+    let mut html = String::from("<title>Statistics</title><h1>Browsing statistics</h1>");
+    if visits.is_empty() {
+        html.push_str("<p>No web pages visited yet.</p>");
+        return html;
+    }
+
+    let mut domains: HashMap<String, usize> = HashMap::new();
+    let mut per_day: BTreeMap<String, usize> = BTreeMap::new();
+    let mut reading = Duration::ZERO;
+    let mut timed = 0;
+    for (i, &(at, url)) in visits.iter().enumerate() {
+        if let Some(host) = url::Url::parse(url).ok().and_then(|u| u.host_str().map(|h| h.trim_start_matches("www.").to_string())) {
+            *domains.entry(host).or_default() += 1;
+        }
+        *per_day.entry(iso8601(at)[..10].to_string()).or_default() += 1;
+        let until = visits.get(i + 1).map_or(SystemTime::now(), |&(next, _)| next);
+        if let Ok(spent) = until.duration_since(at)
+            && spent < IDLE
+        {
+            reading += spent;
+            timed += 1;
+        }
+    }
+    let distinct = visits.iter().map(|&(_, url)| url).collect::<HashSet<_>>().len();
+    let average = if timed > 0 { reading / timed } else { Duration::ZERO };
+    html.push_str(&format!(
+        "<table><tr><td>Pages visited</td><td>{}</td></tr><tr><td>Distinct pages</td><td>{}</td></tr><tr><td>Sites</td><td>{}</td></tr>\
+         <tr><td>Time reading</td><td>{}</td></tr><tr><td>Average per page</td><td>{}</td></tr></table>",
+        visits.len(), distinct, domains.len(), minutes(reading), minutes(average)
+    ));
+
+    let mut ranked: Vec<(String, usize)> = domains.into_iter().collect();
+    ranked.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+    html.push_str("<h2>Most visited sites</h2><table>");
+    for (host, count) in ranked.iter().take(TOP_DOMAINS) {
+        html.push_str(&format!("<tr><td><a href=\"https://{0}/\">{0}</a></td><td>{1}</td></tr>", escape_html(host), count));
+    }
+    html.push_str("</table><h2>Pages per day</h2><table>");
+    let busiest = per_day.values().copied().max().unwrap_or(1);
+    for (day, count) in per_day.iter().rev() {
+        let bar = "#".repeat((count * 40).div_ceil(busiest));
+        html.push_str(&format!("<tr><td>{}</td><td>{}</td><td>{}</td></tr>", day, count, bar));
+    }
+    html.push_str("</table>");
+    html
+}
@@ -0,0 +1,120 @@
+use crate::app::App;
+use crate::i18n::tr;
+use crate::page::TextStats;
+use ratatui::text::Line;
+use tokio::sync::mpsc::{self, UnboundedReceiver};
+use url::Url;
+
+// A text response still arriving. The reader task stops once this is dropped, since its
+// sends start failing when the page is left.
+pub struct LiveStream {
+    url: String,
+    rx: UnboundedReceiver<Result<Vec<u8>, String>>,
+    // Bytes of a character split across chunks
+    carry: Vec<u8>,
+    // The last, unterminated line, shown as the final content line until it is completed
+    pending: Option<String>,
+    pub received: usize,
+}
+
+// Text without a Content-Length that isn't markup: logs, event streams, long polls
+pub fn is_streaming(res: &reqwest::Response, mime_type: &str) -> bool {
+    let mime = mime_type.to_ascii_lowercase();
+    res.content_length().is_none()
+        && !mime.contains("html")
+        && !mime.contains("xml")
+        && (mime.starts_with("text/") || mime.contains("json") || mime.contains("event-stream"))
+}
+
+impl LiveStream {
+    fn decode(&mut self, chunk: &[u8]) -> String {
+        self.carry.extend_from_slice(chunk);
+        let valid = match std::str::from_utf8(&self.carry) {
+            Ok(_) => self.carry.len(),
+            Err(e) if e.error_len().is_none() => e.valid_up_to(),
+            Err(_) => self.carry.len(),
+        };
+        let text = String::from_utf8_lossy(&self.carry[..valid]).into_owned();
+        self.carry.drain(..valid);
+        text
+    }
+}
+
+impl App {
+    pub fn start_stream(&mut self, mut res: reqwest::Response, base_url: &Url) {
+        self.render_html("", base_url);
+        let (tx, rx) = mpsc::unbounded_channel();
+        tokio::spawn(async move {
+            loop {
+                let chunk = match res.chunk().await {
+                    Ok(Some(chunk)) => Ok(chunk.to_vec()),
+                    Ok(None) => break,
+                    Err(e) => Err(e.to_string()),
+                };
+                let failed = chunk.is_err();
+                if tx.send(chunk).is_err() || failed {
+                    break;
+                }
+            }
+        });
+        self.stream = Some(LiveStream { url: self.current_url.clone(), rx, carry: Vec::new(), pending: None, received: 0 });
+        self.status = tr!("Streaming {}...", self.current_url);
+    }
+
+    // Appends whatever has arrived since the last pass, following the end when scrolled there
+    pub fn drain_stream(&mut self) {
+        let Some(mut stream) = self.stream.take() else { return };
+        if stream.url != self.current_url || self.saved_view.is_some() {
+            self.stream = Some(stream);
+            return;
+        }
+        let at_bottom = self.scroll >= self.max_scroll();
+        let mut ended = false;
+        loop {
+            match stream.rx.try_recv() {
+                Ok(Ok(chunk)) => {
+                    stream.received += chunk.len();
+                    let mut text = match stream.pending.take() {
+                        Some(pending) => {
+                            self.content_lines.pop();
+                            pending
+                        }
+                        None => String::new(),
+                    };
+                    text.push_str(&stream.decode(&chunk));
+                    let mut lines: Vec<&str> = text.split('\n').collect();
+                    let last = lines.pop().unwrap_or_default();
+                    self.content_lines.extend(lines.into_iter().map(|l| Line::from(l.trim_end_matches('\r').to_string())));
+                    if !last.is_empty() {
+                        self.content_lines.push(Line::from(last.to_string()));
+                        stream.pending = Some(last.to_string());
+                    }
+                    self.status = tr!("Streaming {} ({} bytes)", self.current_url, stream.received);
+                }
+                Ok(Err(e)) => {
+                    self.status = tr!("Stream interrupted after {} bytes: {}", stream.received, e);
+                    ended = true;
+                    break;
+                }
+                Err(mpsc::error::TryRecvError::Empty) => break,
+                Err(mpsc::error::TryRecvError::Disconnected) => {
+                    self.status = tr!("Stream ended: {} bytes from {}", stream.received, self.current_url);
+                    ended = true;
+                    break;
+                }
+            }
+        }
+        if ended {
+            let text = self.page_text();
+            self.stats = TextStats::from_text(&text);
+            let title = self.page_info.title.clone().unwrap_or_default();
+            self.index.add_page(&self.current_url, &title, &text);
+        }
+        if at_bottom {
+            self.scroll = self.max_scroll();
+        }
+        if !ended {
+            self.stream = Some(stream);
+        }
+    }
+}
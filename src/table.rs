@@ -0,0 +1,518 @@
+use crate::dom;
+use crate::types::Settings;
+use markup5ever_rcdom::{Handle, NodeData};
+use ratatui::text::Span;
+
+pub fn tables(root: &Handle) -> Vec<Handle> {
+    dom::elements(root).into_iter().filter(|n| dom::tag_name(n).as_deref() == Some("table")).collect()
+}
+
+// Rows of the table itself (through thead/tbody/tfoot), not those of nested tables
+fn rows(table: &Handle) -> Vec<Handle> {
+    let mut out = Vec::new();
+    for child in dom::child_elements(table) {
+        match dom::tag_name(&child).as_deref() {
+            Some("tr") => out.push(child),
+            Some("thead" | "tbody" | "tfoot") => {
+                out.extend(dom::child_elements(&child).into_iter().filter(|r| dom::tag_name(r).as_deref() == Some("tr")));
+            }
+            _ => {}
+        }
+    }
+    out
+}
+
+fn span_attr(cell: &Handle, name: &str) -> usize {
+    dom::attr(cell, name).and_then(|v| v.trim().parse().ok()).unwrap_or(1).clamp(1, 1000)
+}
+
+fn fill_carried(out: &mut Vec<String>, col: &mut usize, carry: &mut [(usize, String)]) {
+    while *col < carry.len() && carry[*col].0 > 0 {
+        carry[*col].0 -= 1;
+        out.push(carry[*col].1.clone());
+        *col += 1;
+    }
+}
+
+// Rowspans repeat the cell's text into the rows below so columns stay aligned;
+// colspans leave the extra columns empty.
+pub fn flatten(table: &Handle) -> Vec<Vec<String>> {
+    let mut grid: Vec<Vec<String>> = Vec::new();
+    // Per column: (rows still covered, text)
+    let mut carry: Vec<(usize, String)> = Vec::new();
+    for row in rows(table) {
+        let mut out: Vec<String> = Vec::new();
+        let mut col = 0;
+        for cell in dom::child_elements(&row).into_iter().filter(|c| matches!(dom::tag_name(c).as_deref(), Some("td" | "th"))) {
+            fill_carried(&mut out, &mut col, &mut carry);
+            let text = dom::text_content(&cell).split_whitespace().collect::<Vec<_>>().join(" ");
+            let (colspan, rowspan) = (span_attr(&cell, "colspan"), span_attr(&cell, "rowspan"));
+            for i in 0..colspan {
+                if carry.len() <= col {
+                    carry.resize(col + 1, (0, String::new()));
+                }
+                let value = if i == 0 { text.clone() } else { String::new() };
+                carry[col] = (rowspan - 1, value.clone());
+                out.push(value);
+                col += 1;
+            }
+        }
+        fill_carried(&mut out, &mut col, &mut carry);
+        grid.push(out);
+    }
+    let width = grid.iter().map(Vec::len).max().unwrap_or(0);
+    for row in &mut grid {
+        row.resize(width, String::new());
+    }
+    grid
+}
+
+pub fn to_delimited(grid: &[Vec<String>], tsv: bool) -> String {
+    let mut out = String::new();
+    for row in grid {
+        let cells: Vec<String> = row.iter().map(|c| {
+            if tsv {
+                c.replace(['\t', '\n'], " ")
+            } else if c.contains([',', '"', '\n']) {
+                format!("\"{}\"", c.replace('"', "\"\""))
+            } else {
+                c.clone()
+            }
+        }).collect();
+        out.push_str(&cells.join(if tsv { "\t" } else { "," }));
+        out.push('\n');
+    }
+    out
+}
+
+// Drawing: before html2text lays a page out, each table without tables inside is replaced by a
+// <pre> of box-drawing characters. Links in the cells stay <a>, so they are numbered and followed
+// like any other.
+
+// Columns a table needs for each column, besides the room for a link label; one that can't
+// get this many is left to html2text
+const MIN_COLUMN: usize = 3;
+// Drawn after each link in a cell, as room for the link's label (see make_room)
+const FILLER: char = '\u{2007}';
+
+struct Word {
+    text: String,
+    href: Option<String>,
+}
+
+// A run of a cell's line: plain text, or the text of one link
+struct Piece {
+    text: String,
+    href: Option<String>,
+}
+
+struct Cell {
+    row: usize,
+    col: usize,
+    rowspan: usize,
+    colspan: usize,
+    header: bool,
+    // Right-aligned, so the digits line up
+    numeric: bool,
+    // Words of each line the markup breaks (<br>, <p>, list items, ...)
+    paras: Vec<Vec<Word>>,
+}
+
+#[derive(Default)]
+struct Words {
+    paras: Vec<Vec<Word>>,
+    // The last text ended inside a word, as in `<b>1</b>st`
+    glued: bool,
+}
+
+impl Words {
+    fn text(&mut self, text: &str, href: Option<&str>) {
+        if self.paras.is_empty() {
+            self.paras.push(Vec::new());
+        }
+        let Some(para) = self.paras.last_mut() else { return };
+        let continues = self.glued && !text.starts_with(char::is_whitespace);
+        for (i, word) in text.split_whitespace().enumerate() {
+            match para.last_mut() {
+                Some(last) if i == 0 && continues && last.href.as_deref() == href => last.text.push_str(word),
+                _ => para.push(Word { text: word.to_string(), href: href.map(str::to_string) }),
+            }
+        }
+        if !text.is_empty() {
+            self.glued = !text.ends_with(char::is_whitespace);
+        }
+    }
+
+    fn line_break(&mut self) {
+        if self.paras.last().is_none_or(|p| !p.is_empty()) {
+            self.paras.push(Vec::new());
+        }
+        self.glued = false;
+    }
+}
+
+fn collect_words(node: &Handle, href: Option<&str>, words: &mut Words) {
+    let children = |words: &mut Words, href: Option<&str>| {
+        for child in node.children.borrow().iter() {
+            collect_words(child, href, words);
+        }
+    };
+    match node.data {
+        NodeData::Text { ref contents } => words.text(&contents.borrow(), href),
+        NodeData::Element { .. } => match dom::tag_name(node).unwrap_or_default().as_str() {
+            "script" | "style" | "template" => {}
+            "br" => words.line_break(),
+            "img" => words.text(&format!(" {} ", dom::attr(node, "alt").unwrap_or_default()), href),
+            "a" => {
+                let link = dom::attr(node, "href").filter(|h| !h.trim().is_empty());
+                children(words, link.as_deref().or(href));
+            }
+            "p" | "div" | "li" | "ul" | "ol" | "dl" | "dt" | "dd" | "blockquote" | "pre" | "hr"
+            | "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+                words.line_break();
+                children(words, href);
+                words.line_break();
+            }
+            _ => children(words, href),
+        },
+        _ => {}
+    }
+}
+
+fn is_numeric(text: &str) -> bool {
+    text.chars().any(|c| c.is_ascii_digit()) && text.chars().all(|c| c.is_ascii_digit() || " ,.%+-−$€£¥".contains(c))
+}
+
+fn text_width(s: &str) -> usize {
+    Span::raw(s).width()
+}
+
+fn line_width(line: &[Piece], reserve: usize) -> usize {
+    line.iter().map(|p| text_width(&p.text) + if p.href.is_some() { reserve } else { 0 }).sum()
+}
+
+// A word wider than its column is cut into column-wide parts
+fn split_word(word: &str, max: usize) -> Vec<String> {
+    let mut parts = vec![String::new()];
+    let mut used = 0;
+    for c in word.chars() {
+        let w = text_width(c.encode_utf8(&mut [0; 4]));
+        if used > 0 && used + w > max {
+            parts.push(String::new());
+            used = 0;
+        }
+        if let Some(part) = parts.last_mut() {
+            part.push(c);
+        }
+        used += w;
+    }
+    parts
+}
+
+// Adds a word to a line, after a space unless it starts the line
+fn append(line: &mut Vec<Piece>, text: String, href: &Option<String>) {
+    if !line.is_empty() {
+        match line.last_mut() {
+            Some(last) if last.href.is_none() || last.href == *href => last.text.push(' '),
+            _ => line.push(Piece { text: " ".to_string(), href: None }),
+        }
+    }
+    match line.last_mut() {
+        Some(last) if last.href == *href => last.text.push_str(&text),
+        _ => line.push(Piece { text, href: href.clone() }),
+    }
+}
+
+fn wrap(paras: &[Vec<Word>], max: usize, reserve: usize) -> Vec<Vec<Piece>> {
+    let mut lines = Vec::new();
+    for para in paras {
+        let mut line: Vec<Piece> = Vec::new();
+        for word in para {
+            let reserved = if word.href.is_some() { reserve } else { 0 };
+            for part in split_word(&word.text, max.saturating_sub(reserved).max(1)) {
+                let joins = line.last().is_some_and(|p| p.href == word.href);
+                let needed = usize::from(!line.is_empty()) + text_width(&part) + if joins { 0 } else { reserved };
+                if !line.is_empty() && line_width(&line, reserve) + needed > max {
+                    lines.push(std::mem::take(&mut line));
+                }
+                append(&mut line, part, &word.href);
+            }
+        }
+        if !line.is_empty() {
+            lines.push(line);
+        }
+    }
+    lines
+}
+
+fn junction(up: bool, down: bool, left: bool, right: bool) -> char {
+    match (up, down, left, right) {
+        (false, true, false, true) => '┌',
+        (false, true, true, false) => '┐',
+        (true, false, false, true) => '└',
+        (true, false, true, false) => '┘',
+        (true, true, false, true) => '├',
+        (true, true, true, false) => '┤',
+        (false, true, true, true) => '┬',
+        (true, false, true, true) => '┴',
+        (true, true, true, true) => '┼',
+        (true, true, false, false) => '│',
+        (false, false, _, _) => '─',
+        (true, false, false, false) => '╵',
+        (false, true, false, false) => '╷',
+    }
+}
+
+// The <pre> being drawn, with plain text gathered into as few text nodes as it can
+struct Pre {
+    node: Handle,
+    text: String,
+}
+
+impl Pre {
+    fn text(&mut self, s: &str) {
+        self.text.push_str(s);
+    }
+
+    fn flush(&mut self) {
+        if !self.text.is_empty() {
+            dom::append(&self.node, dom::new_text(&std::mem::take(&mut self.text)));
+        }
+    }
+
+    fn element(&mut self, tag: &str, attrs: &[(&str, &str)], text: &str) {
+        self.flush();
+        dom::append(&self.node, dom::new_element(tag, attrs, text));
+    }
+
+    fn finish(mut self) -> Handle {
+        self.flush();
+        self.node
+    }
+
+    // One line of a cell, padded to `w` with a space either side
+    fn cell_line(&mut self, cell: Option<&Cell>, line: Option<&Vec<Piece>>, w: usize, reserve: usize) {
+        let line = line.map_or(&[][..], Vec::as_slice);
+        let pad = w.saturating_sub(line_width(line, reserve));
+        let (left, right) = if cell.is_some_and(|c| c.numeric) { (pad, 0) } else { (0, pad) };
+        self.text(&" ".repeat(left + 1));
+        for piece in line {
+            match piece.href {
+                Some(ref href) => {
+                    self.element("a", &[("href", href)], &piece.text);
+                    self.text(&FILLER.to_string().repeat(reserve));
+                }
+                None if cell.is_some_and(|c| c.header) => self.element("strong", &[], &piece.text),
+                None => self.text(&piece.text),
+            }
+        }
+        self.text(&" ".repeat(right + 1));
+    }
+}
+
+// Shares out `avail` columns: every column gets what its longest line needs if they all fit,
+// otherwise at least its longest word, and the rest goes to the columns that would wrap most
+fn column_widths(natural: &[usize], least: &[usize], avail: usize) -> Vec<usize> {
+    if natural.iter().sum::<usize>() <= avail {
+        return natural.to_vec();
+    }
+    let mut widths = least.to_vec();
+    while widths.iter().sum::<usize>() > avail {
+        let widest = (0..widths.len()).max_by_key(|&c| widths[c]).unwrap_or(0);
+        widths[widest] -= 1;
+    }
+    let extra = avail - widths.iter().sum::<usize>();
+    let slack: Vec<usize> = natural.iter().zip(&widths).map(|(n, w)| n.saturating_sub(*w)).collect();
+    let total: usize = slack.iter().sum();
+    for (w, s) in widths.iter_mut().zip(&slack) {
+        *w += (extra * s).checked_div(total).unwrap_or(0);
+    }
+    widths
+}
+
+// Rowspans and colspans merge cells as they do in a browser. None when the table has fewer than
+// two columns, or too many to fit in `width` columns.
+fn draw(table: &Handle, width: usize, reserve: usize) -> Option<Handle> {
+    let rows = rows(table);
+    let mut cells: Vec<Cell> = Vec::new();
+    // Which cell covers each slot; None where a row is short
+    let mut grid: Vec<Vec<Option<usize>>> = vec![Vec::new(); rows.len()];
+    for (r, row) in rows.iter().enumerate() {
+        let mut col = 0;
+        for node in dom::child_elements(row).into_iter().filter(|c| matches!(dom::tag_name(c).as_deref(), Some("td" | "th"))) {
+            while grid[r].get(col).is_some_and(Option::is_some) {
+                col += 1;
+            }
+            let (colspan, rowspan) = (span_attr(&node, "colspan"), span_attr(&node, "rowspan").min(rows.len() - r));
+            for slots in &mut grid[r..r + rowspan] {
+                if slots.len() < col + colspan {
+                    slots.resize(col + colspan, None);
+                }
+                slots[col..col + colspan].fill(Some(cells.len()));
+            }
+            let mut words = Words::default();
+            collect_words(&node, None, &mut words);
+            words.paras.retain(|p| !p.is_empty());
+            let text: String = dom::text_content(&node).split_whitespace().collect();
+            cells.push(Cell {
+                row: r,
+                col,
+                rowspan,
+                colspan,
+                header: dom::tag_name(&node).as_deref() == Some("th"),
+                numeric: is_numeric(&text),
+                paras: words.paras,
+            });
+            col += colspan;
+        }
+    }
+    let (nrows, ncols) = (grid.len(), grid.iter().map(Vec::len).max().unwrap_or(0));
+    if ncols < 2 {
+        return None;
+    }
+    for slots in &mut grid {
+        slots.resize(ncols, None);
+    }
+
+    // A border before each column and after the last, and a space either side of each cell
+    let avail = width.checked_sub(3 * ncols + 1)?;
+    if avail < (MIN_COLUMN + reserve) * ncols {
+        return None;
+    }
+    let natural_of = |cell: &Cell| wrap(&cell.paras, usize::MAX, reserve).iter().map(|l| line_width(l, reserve)).max().unwrap_or(0);
+    let mut natural = vec![1; ncols];
+    let mut least = vec![1; ncols];
+    for cell in cells.iter().filter(|c| c.colspan == 1) {
+        natural[cell.col] = natural[cell.col].max(natural_of(cell));
+        let longest = cell.paras.iter().flatten().map(|w| text_width(&w.text) + if w.href.is_some() { reserve } else { 0 }).max().unwrap_or(0);
+        least[cell.col] = least[cell.col].max(longest);
+    }
+    let mut widths = column_widths(&natural, &least, avail);
+    // Cells over several columns widen the last of them when there is room left
+    for cell in cells.iter().filter(|c| c.colspan > 1) {
+        let spanned = widths[cell.col..cell.col + cell.colspan].iter().sum::<usize>() + 3 * (cell.colspan - 1);
+        let room = avail - widths.iter().sum::<usize>();
+        widths[cell.col + cell.colspan - 1] += natural_of(cell).saturating_sub(spanned).min(room);
+    }
+    let cell_width = |cell: &Cell| widths[cell.col..cell.col + cell.colspan].iter().sum::<usize>() + 3 * (cell.colspan - 1);
+    let laid: Vec<Vec<Vec<Piece>>> = cells.iter().map(|c| wrap(&c.paras, cell_width(c), reserve)).collect();
+
+    // A cell over several rows runs on through the borders between them
+    let mut heights = vec![1; nrows];
+    for (cell, lines) in cells.iter().zip(&laid).filter(|(c, _)| c.rowspan == 1) {
+        heights[cell.row] = heights[cell.row].max(lines.len());
+    }
+    for (cell, lines) in cells.iter().zip(&laid).filter(|(c, _)| c.rowspan > 1) {
+        let have = heights[cell.row..cell.row + cell.rowspan].iter().sum::<usize>() + cell.rowspan - 1;
+        heights[cell.row + cell.rowspan - 1] += lines.len().saturating_sub(have);
+    }
+    // The first text line of each row, counting the top border as line 0
+    let mut top = vec![1];
+    for h in &heights {
+        top.push(top[top.len() - 1] + h + 1);
+    }
+
+    let same = |a: Option<usize>, b: Option<usize>| a.is_some() && a == b;
+    let horizontal = |r: usize, c: usize| r == 0 || r == nrows || !same(grid[r - 1][c], grid[r][c]);
+    let vertical = |r: usize, b: usize| b == 0 || b == ncols || !same(grid[r][b - 1], grid[r][b]);
+    // The region starting at column `c` of row `r`: the covering cell, and how many columns it takes
+    let region = |r: usize, c: usize| {
+        let cell = grid[r][c].map(|i| &cells[i]);
+        (grid[r][c], cell.map_or(1, |cell| cell.col + cell.colspan - c))
+    };
+    let line_of = |id: Option<usize>, y: usize| id.and_then(|i| laid[i].get(y - top[cells[i].row]));
+    let span_width = |c: usize, span: usize| widths[c..c + span].iter().sum::<usize>() + 3 * (span - 1);
+
+    let mut pre = Pre { node: dom::new_element("pre", &[], ""), text: String::new() };
+    for r in 0..=nrows {
+        // The border above row r
+        let y = top[r] - 1;
+        let mut c = 0;
+        loop {
+            let up = r > 0 && vertical(r - 1, c);
+            let down = r < nrows && vertical(r, c);
+            let left = c > 0 && horizontal(r, c - 1);
+            let right = c < ncols && horizontal(r, c);
+            pre.text(&junction(up, down, left, right).to_string());
+            if c == ncols {
+                break;
+            }
+            if right {
+                pre.text(&"─".repeat(widths[c] + 2));
+                c += 1;
+            } else {
+                let (id, span) = region(r, c);
+                pre.cell_line(id.map(|i| &cells[i]), line_of(id, y), span_width(c, span), reserve);
+                c += span;
+            }
+        }
+        pre.text("\n");
+        if r == nrows {
+            break;
+        }
+        for y in top[r]..top[r] + heights[r] {
+            let mut c = 0;
+            while c < ncols {
+                let (id, span) = region(r, c);
+                pre.text("│");
+                pre.cell_line(id.map(|i| &cells[i]), line_of(id, y), span_width(c, span), reserve);
+                c += span;
+            }
+            pre.text("│\n");
+        }
+    }
+
+    let caption = dom::child_elements(table).into_iter()
+        .find(|n| dom::tag_name(n).as_deref() == Some("caption"))
+        .map(|n| dom::text_content(&n).split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|c| !c.is_empty());
+    let drawing = dom::new_element("div", &[], "");
+    if let Some(caption) = caption {
+        let p = dom::new_element("p", &[], "");
+        dom::append(&p, dom::new_element("strong", &[], &caption));
+        dom::append(&drawing, p);
+    }
+    dom::append(&drawing, pre.finish());
+    Some(drawing)
+}
+
+// Replaces each table that has no tables inside (those are usually layout) with a drawing that
+// fits in `width` columns. `reserve` is the room kept after each link for its label. None when
+// no table was drawn.
+pub fn draw_tables(html: &str, width: usize, reserve: usize) -> Option<String> {
+    if !html.contains("<table") && !html.contains("<TABLE") {
+        return None;
+    }
+    let document = dom::parse(html);
+    let mut drawn = false;
+    for table in tables(&document.document) {
+        let nested = dom::elements(&table).iter().any(|n| dom::tag_name(n).as_deref() == Some("table"));
+        if nested || dom::attr(&table, "role").is_some_and(|r| r == "presentation") {
+            continue;
+        }
+        if let Some(drawing) = draw(&table, width, reserve) {
+            dom::replace(&table, drawing);
+            drawn = true;
+        }
+    }
+    drawn.then(|| dom::serialize(&document))
+}
+
+// Widest label the page's links can get, counting each link twice for those a cell cuts in two
+pub fn label_width(html: &str, settings: &Settings, first_link: usize) -> usize {
+    let links = first_link + 2 * (html.matches("<a ").count() + html.matches("<img").count());
+    let digits = links.to_string().len();
+    match (settings.footnotes, settings.link_numbers) {
+        (true, _) => digits,
+        (false, true) => digits + 2,
+        (false, false) => 0,
+    }
+}
+
+// Called with the spans after a link's label once the label is known: takes the label's width
+// out of the filler drawn after a link in a table, so the table's columns stay aligned
+pub fn make_room(after: &mut [Span<'static>], label: usize) {
+    let Some(span) = after.iter_mut().take(2).find(|s| s.content.starts_with(FILLER)) else { return };
+    let taken = span.content.chars().take_while(|&c| c == FILLER).count().min(label);
+    span.content = span.content.chars().skip(taken).collect::<String>().into();
+}
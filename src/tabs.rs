@@ -0,0 +1,255 @@
+use crate::app::App;
+use crate::caret::Caret;
+use crate::chunked::PartialBody;
+use crate::download::{Download, HeldResponse};
+use crate::graphics::ImagePreview;
+use crate::hints::Hints;
+use crate::history::HistoryTree;
+use crate::i18n::tr;
+use crate::inspector::Inspector;
+use crate::loader::Loading;
+use crate::page::{PageInfo, TextStats};
+use crate::search::Search;
+use crate::stream::LiveStream;
+use crate::types::{LinkData, Mode, PagePosition, SavedView, Translation};
+use crate::watch::Watch;
+use ratatui::text::Line;
+use std::error::Error;
+use std::time::{Instant, SystemTime};
+
+// Everything that belongs to one tab's page. The shown tab's state lives in App's own
+// fields; the slot in `App::tabs` for the shown tab holds whatever was swapped out last.
+pub struct Tab {
+    current_url: String,
+    shown: (String, usize),
+    history: HistoryTree,
+    visited_at: SystemTime,
+    content_lines: Vec<Line<'static>>,
+    links: Vec<LinkData>,
+    selected_link_idx: usize,
+    scroll: u16,
+    page_info: PageInfo,
+    raw_html: String,
+    fetched_source: Option<String>,
+    stats: TextStats,
+    saved_view: Option<SavedView>,
+    translation: Option<Translation>,
+    appended_pages: Vec<String>,
+    previous_text: Option<String>,
+    tls_verified: bool,
+    pending_refresh: Option<(Instant, String)>,
+    partial: Option<PartialBody>,
+    download: Option<Download>,
+    held: Option<HeldResponse>,
+    stream: Option<LiveStream>,
+    // A tab keeps loading in the background; its result is rendered into it when it comes in
+    loading: Option<Loading>,
+    load_chain: Vec<String>,
+    restore_on_load: Option<PagePosition>,
+    image_preview: Option<ImagePreview>,
+    caret: Caret,
+    editing_field: Option<(usize, usize)>,
+    // A watched tab keeps being re-fetched while another is shown
+    watch: Option<Watch>,
+}
+
+impl Tab {
+    pub fn new(url: &str) -> Self {
+        Self {
+            current_url: url.to_string(),
+            shown: (url.to_string(), 0),
+            history: HistoryTree::new(url),
+            visited_at: SystemTime::now(),
+            content_lines: Vec::new(),
+            links: Vec::new(),
+            selected_link_idx: 0,
+            scroll: 0,
+            page_info: PageInfo::default(),
+            raw_html: String::new(),
+            fetched_source: None,
+            stats: TextStats::default(),
+            saved_view: None,
+            translation: None,
+            appended_pages: Vec::new(),
+            previous_text: None,
+            tls_verified: false,
+            pending_refresh: None,
+            partial: None,
+            download: None,
+            held: None,
+            stream: None,
+            loading: None,
+            load_chain: Vec::new(),
+            restore_on_load: None,
+            image_preview: None,
+            caret: Caret::default(),
+            editing_field: None,
+            watch: None,
+        }
+    }
+
+    // Title for the tab bar
+    pub fn label(&self) -> &str {
+        self.page_info.title.as_deref().unwrap_or(&self.current_url)
+    }
+
+    pub fn url(&self) -> &str {
+        &self.current_url
+    }
+
+    pub fn history(&self) -> &HistoryTree {
+        &self.history
+    }
+
+    pub fn watching(&self) -> bool {
+        self.watch.is_some()
+    }
+}
+
+// What a background tab borrows App's fields for, and what it must leave as it was: the
+// status line and whatever is open over the shown page
+pub struct Peek {
+    idx: usize,
+    status: String,
+    inspector: Option<Inspector>,
+    search: Option<Search>,
+    hints: Option<Hints>,
+    mode: Mode,
+}
+
+impl App {
+    fn swap_tab(&mut self, idx: usize) {
+        let tab = &mut self.tabs[idx];
+        std::mem::swap(&mut self.current_url, &mut tab.current_url);
+        std::mem::swap(&mut self.shown, &mut tab.shown);
+        std::mem::swap(&mut self.history, &mut tab.history);
+        std::mem::swap(&mut self.visited_at, &mut tab.visited_at);
+        std::mem::swap(&mut self.content_lines, &mut tab.content_lines);
+        std::mem::swap(&mut self.links, &mut tab.links);
+        std::mem::swap(&mut self.selected_link_idx, &mut tab.selected_link_idx);
+        std::mem::swap(&mut self.scroll, &mut tab.scroll);
+        std::mem::swap(&mut self.page_info, &mut tab.page_info);
+        std::mem::swap(&mut self.raw_html, &mut tab.raw_html);
+        std::mem::swap(&mut self.fetched_source, &mut tab.fetched_source);
+        std::mem::swap(&mut self.stats, &mut tab.stats);
+        std::mem::swap(&mut self.saved_view, &mut tab.saved_view);
+        std::mem::swap(&mut self.translation, &mut tab.translation);
+        std::mem::swap(&mut self.appended_pages, &mut tab.appended_pages);
+        std::mem::swap(&mut self.previous_text, &mut tab.previous_text);
+        std::mem::swap(&mut self.tls_verified, &mut tab.tls_verified);
+        std::mem::swap(&mut self.pending_refresh, &mut tab.pending_refresh);
+        std::mem::swap(&mut self.partial, &mut tab.partial);
+        std::mem::swap(&mut self.download, &mut tab.download);
+        std::mem::swap(&mut self.held, &mut tab.held);
+        std::mem::swap(&mut self.stream, &mut tab.stream);
+        std::mem::swap(&mut self.loading, &mut tab.loading);
+        std::mem::swap(&mut self.load_chain, &mut tab.load_chain);
+        std::mem::swap(&mut self.restore_on_load, &mut tab.restore_on_load);
+        std::mem::swap(&mut self.image_preview, &mut tab.image_preview);
+        std::mem::swap(&mut self.caret, &mut tab.caret);
+        std::mem::swap(&mut self.editing_field, &mut tab.editing_field);
+        std::mem::swap(&mut self.watch, &mut tab.watch);
+    }
+
+    // The tab whose background load has this id
+    pub fn tab_loading(&self, id: u64) -> Option<usize> {
+        self.tabs.iter().position(|tab| tab.loading.as_ref().is_some_and(|l| l.id() == id))
+    }
+
+    // Brings a background tab's state into App's fields for a moment, to load or render into
+    // it; `unpeek_tab` puts everything back
+    pub fn peek_tab(&mut self, idx: usize) -> Peek {
+        let peek = Peek {
+            idx,
+            status: std::mem::take(&mut self.status),
+            inspector: self.inspector.take(),
+            search: self.search.take(),
+            hints: self.hints.take(),
+            mode: self.mode.clone(),
+        };
+        self.swap_tab(self.active_tab);
+        self.swap_tab(idx);
+        peek
+    }
+
+    pub fn unpeek_tab(&mut self, peek: Peek) {
+        self.swap_tab(peek.idx);
+        self.swap_tab(self.active_tab);
+        self.status = peek.status;
+        (self.inspector, self.search, self.hints, self.mode) = (peek.inspector, peek.search, peek.hints, peek.mode);
+    }
+
+    // Opens `url` in a new tab at `idx`, after the shown one, and loads it without switching to it
+    pub async fn background_tab(&mut self, idx: usize, url: &str) -> Result<(), Box<dyn Error>> {
+        self.tabs.insert(idx, Tab::new(""));
+        let peek = self.peek_tab(idx);
+        let loaded = self.go_to(url.to_string()).await;
+        self.unpeek_tab(peek);
+        loaded
+    }
+
+    pub fn switch_tab(&mut self, idx: usize) {
+        if idx >= self.tabs.len() || idx == self.active_tab {
+            return;
+        }
+        // Kept for the session file; a page still loading hasn't replaced the one on screen yet
+        if self.loading.is_none() {
+            self.history.save(self.current_entry());
+        }
+        self.swap_tab(self.active_tab);
+        self.swap_tab(idx);
+        self.active_tab = idx;
+        self.smooth = None;
+        self.inspector = None;
+        self.search = None;
+        self.hints = None;
+        self.status = tr!("Tab {}/{}: {}", idx + 1, self.tabs.len(), self.current_url);
+    }
+
+    // gt / gT: wraps around at either end
+    pub fn cycle_tab(&mut self, forward: bool) {
+        let n = self.tabs.len();
+        let idx = if forward { (self.active_tab + 1) % n } else { (self.active_tab + n - 1) % n };
+        self.switch_tab(idx);
+    }
+
+    // Opens `url` in a new tab after the current one and switches to it. It goes through the
+    // same checks as a typed address; the tab's empty first entry is taken over by the page.
+    pub async fn new_tab(&mut self, url: &str) -> Result<(), Box<dyn Error>> {
+        let url = if url.is_empty() { self.current_url.clone() } else { url.to_string() };
+        self.tabs.insert(self.active_tab + 1, Tab::new(""));
+        self.switch_tab(self.active_tab + 1);
+        self.navigate(url).await
+    }
+
+    pub fn close_tab(&mut self) {
+        if self.tabs.len() == 1 {
+            self.status = tr!("tabclose: this is the last tab (:q quits)");
+            return;
+        }
+        if let Some(loading) = self.loading.take() {
+            loading.abort();
+        }
+        let closed = self.active_tab;
+        self.swap_tab(closed);
+        self.tabs.remove(closed);
+        self.active_tab = closed.min(self.tabs.len() - 1);
+        self.swap_tab(self.active_tab);
+        self.smooth = None;
+        self.inspector = None;
+        self.search = None;
+        self.hints = None;
+        self.status = tr!("Tab closed; {} left", self.tabs.len());
+    }
+
+    // (label, is the shown tab) for each tab in order
+    pub fn tab_labels(&self) -> Vec<(String, bool)> {
+        self.tabs.iter().enumerate().map(|(i, tab)| match i == self.active_tab {
+            true => (self.page_info.title.clone().unwrap_or_else(|| self.current_url.clone()), true),
+            false => (tab.label().to_string(), false),
+        }).map(|(label, shown)| match label.is_empty() {
+            true => (tr!("New tab"), shown),
+            false => (label, shown),
+        }).collect()
+    }
+}
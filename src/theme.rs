@@ -0,0 +1,222 @@
+use crate::app::App;
+use crate::colors::{self, ColorDepth};
+use crate::i18n::tr;
+use crate::storage;
+use crate::types::ToastKind;
+use crossterm::event::{self, Event, KeyCode, KeyModifiers};
+use ratatui::style::{Color, Modifier, Style};
+use std::io::Write;
+use std::time::{Duration, Instant};
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Background {
+    Dark,
+    Light,
+}
+
+impl Background {
+    pub fn parse(value: &str) -> Result<Option<Self>, String> {
+        match value {
+            "auto" => Ok(None),
+            "dark" => Ok(Some(Background::Dark)),
+            "light" => Ok(Some(Background::Light)),
+            _ => Err(format!("expected auto, dark or light, got {:?}", value)),
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Background::Dark => "dark",
+            Background::Light => "light",
+        }
+    }
+}
+
+// Colors of the browser's own chrome, picked to stay readable on the terminal's background
+#[derive(Clone)]
+pub struct Palette {
+    pub status_fg: Color,
+    pub status_bg: Color,
+    // The selected link
+    pub highlight_fg: Color,
+    pub highlight_bg: Color,
+    pub accent: Color,
+    pub dim: Color,
+}
+
+impl Palette {
+    // Without colors, the status bar and the selected link are shown in reverse video
+    pub fn status_bar(&self) -> Style {
+        match self.status_bg {
+            Color::Reset => Style::default().add_modifier(Modifier::REVERSED),
+            bg => Style::default().fg(self.status_fg).bg(bg),
+        }
+    }
+
+    pub fn highlight(&self) -> Style {
+        match self.highlight_bg {
+            Color::Reset => Style::default().add_modifier(Modifier::REVERSED),
+            bg => Style::default().fg(self.highlight_fg).bg(bg),
+        }
+    }
+
+    fn fit(self, depth: ColorDepth) -> Self {
+        let f = |c| colors::fit(c, depth);
+        Palette {
+            status_fg: f(self.status_fg), status_bg: f(self.status_bg), highlight_fg: f(self.highlight_fg),
+            highlight_bg: f(self.highlight_bg), accent: f(self.accent), dim: f(self.dim),
+        }
+    }
+}
+
+pub fn palette(background: Background) -> Palette {
+    match background {
+        Background::Dark => Palette { status_fg: Color::Black, status_bg: Color::White, highlight_fg: Color::Black, highlight_bg: Color::Yellow, accent: Color::Cyan, dim: Color::DarkGray },
+        Background::Light => Palette { status_fg: Color::White, status_bg: Color::Blue, highlight_fg: Color::Black, highlight_bg: Color::Yellow, accent: Color::Blue, dim: Color::Gray },
+    }
+}
+
+// An ANSI color name ("red", "bright-blue"), an ANSI index 0-15, or #rrggbb
+pub fn parse_color(value: &str) -> Option<Color> {
+    const ANSI: [Color; 16] = [
+        Color::Black, Color::Red, Color::Green, Color::Yellow, Color::Blue, Color::Magenta, Color::Cyan, Color::Gray,
+        Color::DarkGray, Color::LightRed, Color::LightGreen, Color::LightYellow, Color::LightBlue, Color::LightMagenta, Color::LightCyan, Color::White,
+    ];
+    const NAMES: [&str; 8] = ["black", "red", "green", "yellow", "blue", "magenta", "cyan", "white"];
+    let value = value.trim().trim_matches(['"', '\'']).to_ascii_lowercase();
+    if value.len() <= 2 && let Ok(n) = value.parse::<usize>() {
+        return ANSI.get(n).copied();
+    }
+    if let Some(i) = NAMES.iter().position(|&n| n == value) {
+        return Some(ANSI[i]);
+    }
+    if let Some(i) = value.strip_prefix("bright-").and_then(|v| NAMES.iter().position(|&n| n == v)) {
+        return Some(ANSI[i + 8]);
+    }
+    let hex = value.strip_prefix('#').unwrap_or(&value);
+    if hex.len() != 6 {
+        return None;
+    }
+    let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+    Some(Color::Rgb(channel(0)?, channel(2)?, channel(4)?))
+}
+
+// A theme file holds flat `key: value` lines. base16 schemes (base00 to base0F) are mapped
+// onto the chrome; otherwise the keys are the roles themselves (status-fg, highlight-bg, ...)
+// and anything left out keeps the built-in color.
+pub fn parse_theme(text: &str, fallback: Palette) -> Result<Palette, String> {
+    let mut values = std::collections::HashMap::new();
+    for line in text.lines().map(str::trim).filter(|l| !l.starts_with('#')) {
+        let Some((key, value)) = line.split_once(':') else { continue };
+        // A value may itself start with # (#rrggbb); a comment needs a space before it
+        let value = value.trim();
+        let value = match value.get(1..).and_then(|rest| rest.find(" #")) {
+            Some(i) => &value[..i + 1],
+            None => value,
+        };
+        values.insert(key.trim().to_ascii_lowercase(), value.trim().to_string());
+    }
+    let color = |key: &str| -> Result<Option<Color>, String> {
+        match values.get(key) {
+            Some(v) => parse_color(v).map(Some).ok_or_else(|| format!("{}: not a color: {}", key, v)),
+            None => Ok(None),
+        }
+    };
+    let mut p = fallback;
+    if values.contains_key("base00") {
+        let base = |n: &str| color(&format!("base{}", n))?.ok_or_else(|| format!("base16 scheme is missing base{}", n));
+        p.status_bg = base("01")?;
+        p.status_fg = base("05")?;
+        p.highlight_bg = base("0a")?;
+        p.highlight_fg = base("00")?;
+        p.accent = base("0d")?;
+        p.dim = base("03")?;
+        return Ok(p);
+    }
+    for (key, slot) in [("status-fg", &mut p.status_fg), ("status-bg", &mut p.status_bg), ("highlight-fg", &mut p.highlight_fg),
+                        ("highlight-bg", &mut p.highlight_bg), ("accent", &mut p.accent), ("dim", &mut p.dim)] {
+        if let Some(c) = color(key)? {
+            *slot = c;
+        }
+    }
+    Ok(p)
+}
+
+// "rgb:RRRR/GGGG/BBBB" (1 to 4 hex digits per channel) from an OSC 11 reply
+fn parse_rgb(reply: &str) -> Option<Background> {
+    let rgb = &reply[reply.find("rgb:")? + 4..];
+    let channels: Vec<f64> = rgb.split('/').take(3).map(|c| {
+        let hex: String = c.chars().take_while(char::is_ascii_hexdigit).collect();
+        let max = (16f64).powi(hex.len() as i32) - 1.0;
+        u32::from_str_radix(&hex, 16).ok().map(|v| v as f64 / max)
+    }).collect::<Option<_>>()?;
+    let [r, g, b] = channels[..] else { return None };
+    // Relative luminance, ITU-R BT.709 weights
+    let luminance = 0.2126 * r + 0.7152 * g + 0.0722 * b;
+    Some(if luminance > 0.5 { Background::Light } else { Background::Dark })
+}
+
+// rxvt, Konsole and others export "fg;bg" as palette indices; 7 and 9-15 are light
+fn from_colorfgbg() -> Option<Background> {
+    let value = std::env::var("COLORFGBG").ok()?;
+    let bg: u8 = value.rsplit(';').next()?.parse().ok()?;
+    Some(if bg == 7 || bg > 8 { Background::Light } else { Background::Dark })
+}
+
+// Asks the terminal for its background color (OSC 11). Must run in raw mode. crossterm
+// has no event for the reply, so it arrives as key presses and is pieced back together.
+pub fn detect_background() -> Background {
+    let mut out = std::io::stdout();
+    if write!(out, "\x1b]11;?\x07").and_then(|_| out.flush()).is_err() {
+        return from_colorfgbg().unwrap_or(Background::Dark);
+    }
+    let deadline = Instant::now() + Duration::from_millis(150);
+    let mut reply = String::new();
+    while let Some(left) = deadline.checked_duration_since(Instant::now()) {
+        if !event::poll(left).unwrap_or(false) {
+            break;
+        }
+        let Ok(Event::Key(key)) = event::read() else { continue };
+        match key.code {
+            // BEL arrives as Ctrl-G and ST as Alt-\
+            KeyCode::Char('g') if key.modifiers.contains(KeyModifiers::CONTROL) => break,
+            KeyCode::Char('\\') if key.modifiers.contains(KeyModifiers::ALT) => break,
+            KeyCode::Char(c) => reply.push(c),
+            _ => {}
+        }
+    }
+    parse_rgb(&reply).or_else(from_colorfgbg).unwrap_or(Background::Dark)
+}
+
+impl App {
+    pub fn background(&self) -> Background {
+        self.settings.background.unwrap_or(self.terminal_background)
+    }
+
+    pub fn palette(&self) -> Palette {
+        let depth = self.settings.color_depth.unwrap_or(self.terminal_colors);
+        self.theme.clone().unwrap_or_else(|| palette(self.background())).fit(depth)
+    }
+
+    // Loads the `theme` setting from themes/<name>-<dark|light>.yaml or themes/<name>.yaml in
+    // the config directory, so a theme can ship a variant for each background
+    pub fn load_theme(&mut self) {
+        let name = self.settings.theme.clone();
+        if name == "default" {
+            self.theme = None;
+            return;
+        }
+        let background = self.background();
+        let variant = storage::config_file(&format!("themes/{}-{}.yaml", name, background.name()));
+        let path = if variant.exists() { variant } else { storage::config_file(&format!("themes/{}.yaml", name)) };
+        let loaded = std::fs::read_to_string(&path).map_err(|e| format!("{}: {}", path.display(), e))
+            .and_then(|text| parse_theme(&text, palette(background)));
+        match loaded {
+            Ok(p) => self.theme = Some(p),
+            Err(e) => {
+                self.theme = None;
+                self.notify(ToastKind::Error, tr!("theme {}: {}", name, e));
+            }
+        }
+    }
+}
@@ -0,0 +1,200 @@
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+use rustls::client::{ClientSessionMemoryCache, ClientSessionStore, Tls12ClientSessionValue, Tls13ClientSessionValue};
+use rustls::NamedGroup;
+use rustls::pki_types::ServerName;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+use tower_layer::Layer;
+use tower_service::Service;
+
+// Connection phases observed by the client's resolver, connector and TLS session hooks.
+// All stay None when the request reused a pooled connection; `connect` covers the whole
+// connection, DNS and TLS included, and `tls` stays None for plain HTTP.
+#[derive(Default, Clone, Copy)]
+pub struct Phases {
+    pub dns: Option<Duration>,
+    pub connect: Option<Duration>,
+    pub tls: Option<Duration>,
+}
+
+#[derive(Default)]
+struct Recording {
+    phases: Phases,
+    // When the TLS handshake on the connection being made started
+    tls_start: Option<Instant>,
+}
+
+// One per request, so fetches running at the same time don't record into each other
+#[derive(Clone, Default)]
+pub struct Probe(Arc<Mutex<Recording>>);
+
+tokio::task_local! {
+    // The probe of the request being sent on this task, for the hooks to record into
+    static CURRENT: Probe;
+}
+
+impl Probe {
+    pub fn take(&self) -> Phases {
+        std::mem::take(&mut *self.0.lock().unwrap()).phases
+    }
+
+    // Runs `fut`, a request being sent, with the hooks recording into this probe
+    pub async fn watch<F: Future>(&self, fut: F) -> F::Output {
+        CURRENT.scope(self.clone(), fut).await
+    }
+
+    // None for a connection made outside `watch`, like a download's
+    fn current() -> Option<Probe> {
+        CURRENT.try_with(Probe::clone).ok()
+    }
+
+    fn record(&self, f: impl FnOnce(&mut Recording)) {
+        f(&mut self.0.lock().unwrap());
+    }
+}
+
+pub struct FetchTiming {
+    pub url: String,
+    pub phases: Phases,
+    pub ttfb: Duration,
+    pub body: Duration,
+    pub render: Duration,
+    pub bytes: usize,
+}
+
+impl FetchTiming {
+    pub fn report(&self) -> Vec<String> {
+        let ms = |d: Duration| format!("{:>8.1} ms", d.as_secs_f64() * 1000.0);
+        let phase = |d: Option<Duration>| d.map(ms).unwrap_or_else(|| "  (reused)".to_string());
+        // The connector also drives name resolution and the handshake, so both are taken out of its total
+        let Phases { dns, connect, tls } = self.phases;
+        let tcp = connect.map(|c| c.saturating_sub(dns.unwrap_or_default() + tls.unwrap_or_default()));
+        let tls = match (connect, tls) {
+            (Some(_), None) => "       -".to_string(),
+            _ => phase(tls),
+        };
+        let network = self.ttfb + self.body;
+        vec![
+            self.url.clone(),
+            String::new(),
+            format!("DNS          {}", phase(dns)),
+            format!("Connect      {}", phase(tcp)),
+            format!("TLS          {}", tls),
+            format!("TTFB         {}", ms(self.ttfb)),
+            format!("Body         {}  ({} bytes)", ms(self.body), self.bytes),
+            format!("Render       {}", ms(self.render)),
+            String::new(),
+            format!("Network      {}", ms(network)),
+            format!("Total        {}", ms(network + self.render)),
+        ]
+    }
+}
+
+pub struct TimedResolver;
+
+impl Resolve for TimedResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        let probe = Probe::current();
+        Box::pin(async move {
+            let start = Instant::now();
+            let addrs: Vec<_> = tokio::net::lookup_host((name.as_str(), 0)).await?.collect();
+            if let Some(probe) = probe {
+                probe.record(|r| r.phases.dns = Some(start.elapsed()));
+            }
+            Ok(Box::new(addrs.into_iter()) as Addrs)
+        })
+    }
+}
+
+#[derive(Clone)]
+pub struct ConnectTiming;
+
+impl<S> Layer<S> for ConnectTiming {
+    type Service = TimedConnector<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        TimedConnector { inner }
+    }
+}
+
+#[derive(Clone)]
+pub struct TimedConnector<S> {
+    inner: S,
+}
+
+impl<S, R> Service<R> for TimedConnector<S>
+where
+    S: Service<R>,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<S::Response, S::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: R) -> Self::Future {
+        let start = Instant::now();
+        let fut = self.inner.call(req);
+        let probe = Probe::current();
+        Box::pin(async move {
+            let res = fut.await;
+            if let Some(probe) = probe {
+                probe.record(|r| {
+                    r.phases.connect = Some(start.elapsed());
+                    r.phases.tls = r.tls_start.take().map(|t| t.elapsed());
+                });
+            }
+            res
+        })
+    }
+}
+
+// rustls's session cache, which it asks for a key exchange hint as it writes the ClientHello:
+// the moment the TCP connection is up and the TLS handshake starts
+#[derive(Debug)]
+pub struct HandshakeTiming(ClientSessionMemoryCache);
+
+impl Default for HandshakeTiming {
+    fn default() -> Self {
+        Self(ClientSessionMemoryCache::new(256))
+    }
+}
+
+impl ClientSessionStore for HandshakeTiming {
+    fn set_kx_hint(&self, server_name: ServerName<'static>, group: NamedGroup) {
+        self.0.set_kx_hint(server_name, group);
+    }
+
+    fn kx_hint(&self, server_name: &ServerName<'_>) -> Option<NamedGroup> {
+        if let Some(probe) = Probe::current() {
+            probe.record(|r| r.tls_start = Some(Instant::now()));
+        }
+        self.0.kx_hint(server_name)
+    }
+
+    fn set_tls12_session(&self, server_name: ServerName<'static>, value: Tls12ClientSessionValue) {
+        self.0.set_tls12_session(server_name, value);
+    }
+
+    fn tls12_session(&self, server_name: &ServerName<'_>) -> Option<Tls12ClientSessionValue> {
+        self.0.tls12_session(server_name)
+    }
+
+    fn remove_tls12_session(&self, server_name: &ServerName<'static>) {
+        self.0.remove_tls12_session(server_name);
+    }
+
+    fn insert_tls13_ticket(&self, server_name: ServerName<'static>, value: Tls13ClientSessionValue) {
+        self.0.insert_tls13_ticket(server_name, value);
+    }
+
+    fn take_tls13_ticket(&self, server_name: &ServerName<'static>) -> Option<Tls13ClientSessionValue> {
+        self.0.take_tls13_ticket(server_name)
+    }
+}
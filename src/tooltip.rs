@@ -0,0 +1,33 @@
+use crate::app::App;
+use crate::i18n::tr;
+
+impl App {
+    // The selected link's title, else the expansions of abbreviations on screen
+    pub fn show_titles(&mut self) {
+        if let Some(link) = self.links.get(self.selected_link_idx)
+            && let Some((_, title)) = self.page_info.link_titles.iter().find(|(href, _)| *href == link.url)
+        {
+            self.status = tr!("Link title: {}", title);
+            return;
+        }
+        let visible: Vec<String> = self.content_lines.iter()
+            .skip(self.scroll as usize)
+            .take(self.viewport_height.max(1) as usize)
+            .map(|l| l.spans.iter().map(|s| s.content.as_ref()).collect())
+            .collect();
+        let shown: Vec<String> = self.page_info.abbreviations.iter()
+            .filter(|(text, _)| visible.iter().any(|line| contains_word(line, text)))
+            .map(|(text, title)| format!("{}: {}", text, title))
+            .collect();
+        self.status = if shown.is_empty() { tr!("No titles on screen") } else { shown.join("; ") };
+    }
+}
+
+// `word` in `line` with no letter or digit either side, so "CSS" doesn't match in "CSSOM"
+fn contains_word(line: &str, word: &str) -> bool {
+    line.match_indices(word).any(|(i, _)| {
+        let before = line[..i].chars().next_back();
+        let after = line[i + word.len()..].chars().next();
+        !before.is_some_and(char::is_alphanumeric) && !after.is_some_and(char::is_alphanumeric)
+    })
+}
@@ -0,0 +1,76 @@
+use crate::net::Net;
+use crate::types::Translator;
+use std::error::Error;
+use std::process::Stdio;
+use tokio::io::AsyncWriteExt;
+
+const CHUNK_CHARS: usize = 4000;
+
+// Split on line boundaries so each request stays under typical backend size limits
+fn chunks(text: &str) -> Vec<String> {
+    let mut out = vec![String::new()];
+    for line in text.lines() {
+        let current = out.last_mut().unwrap();
+        if !current.is_empty() && current.len() + line.len() > CHUNK_CHARS {
+            out.push(String::new());
+        }
+        let current = out.last_mut().unwrap();
+        current.push_str(line);
+        current.push('\n');
+    }
+    out
+}
+
+async fn translate_chunk(net: &Net, backend: &Translator, text: &str, lang: &str) -> Result<String, Box<dyn Error>> {
+    match backend {
+        Translator::LibreTranslate { url, api_key } => {
+            let body = serde_json::json!({
+                "q": text, "source": "auto", "target": lang, "format": "text", "api_key": api_key,
+            });
+            let res = net.send(net.post(url).header("Content-Type", "application/json").body(body.to_string())).await?.text().await?;
+            let value: serde_json::Value = serde_json::from_str(&res)?;
+            value["translatedText"].as_str()
+                .map(str::to_string)
+                .ok_or_else(|| format!("libretranslate: {}", value["error"].as_str().unwrap_or("unexpected response")).into())
+        }
+        Translator::DeepL { url, api_key } => {
+            let request = net.post(url)
+                .header("Authorization", format!("DeepL-Auth-Key {}", api_key))
+                .form(&[("text", text), ("target_lang", &lang.to_uppercase())]);
+            let res = net.send(request).await?.text().await?;
+            let value: serde_json::Value = serde_json::from_str(&res)?;
+            value["translations"][0]["text"].as_str()
+                .map(str::to_string)
+                .ok_or_else(|| format!("deepl: {}", value["message"].as_str().unwrap_or("unexpected response")).into())
+        }
+        Translator::Command(cmd) => {
+            let mut child = tokio::process::Command::new("sh")
+                .arg("-c").arg(cmd.replace("{lang}", lang))
+                .stdin(Stdio::piped()).stdout(Stdio::piped()).stderr(Stdio::null())
+                .spawn()?;
+            if let Some(mut stdin) = child.stdin.take() {
+                stdin.write_all(text.as_bytes()).await?;
+            }
+            let out = child.wait_with_output().await?;
+            if !out.status.success() {
+                return Err(format!("translate command exited with {}", out.status).into());
+            }
+            Ok(String::from_utf8_lossy(&out.stdout).into_owned())
+        }
+    }
+}
+
+pub async fn translate(net: &Net, backend: &Translator, text: &str, lang: &str) -> Result<String, Box<dyn Error>> {
+    let mut out = String::new();
+    for chunk in chunks(text) {
+        if chunk.trim().is_empty() {
+            out.push_str(&chunk);
+            continue;
+        }
+        out.push_str(&translate_chunk(net, backend, &chunk, lang).await?);
+        if !out.ends_with('\n') {
+            out.push('\n');
+        }
+    }
+    Ok(out)
+}
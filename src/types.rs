@@ -1,15 +1,37 @@
-use ratatui::style::Color;
+use crate::app::USER_AGENT;
+use crate::colors::ColorDepth;
+use crate::graphics::Graphics;
+use crate::i18n::{self, Locale};
+use crate::proxy;
+use crate::theme::Background;
+use ratatui::{style::{Color, Style}, text::Line};
+use std::time::{Instant, SystemTime};
 
 #[derive(Debug, PartialEq, Clone)]
 pub enum Mode {
     Normal,
+    Picker,
+    Dialog,
     Command,
+    Caret,
+    Request,
+    Search,
+    // Typing into a form field (see forms.rs)
+    Insert,
+    // Typing a link label (see hints.rs)
+    Hint,
+    // Editing an address (see urlbar.rs)
+    Url,
 }
 
 #[derive(Debug, PartialEq, Clone)]
 pub enum LinkType {
     Web,
     Image,
+    // The source of an <audio> or <video>
+    Media,
+    // A form control; following it edits, toggles or submits
+    Field,
 }
 
 #[derive(Clone)]
@@ -18,5 +40,420 @@ pub struct LinkData {
     pub link_type: LinkType,
 }
 
+// Reading position remembered with a history entry
+#[derive(Clone, Copy, Default)]
+pub struct PagePosition {
+    pub scroll: u16,
+    pub selected_link_idx: usize,
+}
+
+#[derive(Clone)]
+pub struct HistoryEntry {
+    pub url: String,
+    pub title: Option<String>,
+    pub visited_at: SystemTime,
+    pub position: PagePosition,
+}
+
+#[derive(Clone)]
+pub enum PopupAction {
+    JumpToLine(u16),
+    JumpHistory(usize),
+    // Offers to open, inspect or save a subresource (see resources.rs)
+    Resource(String),
+    // Picks option n of a <select>: (form, field, option)
+    FormOption(usize, usize, usize),
+}
+
+// When `actions` is non-empty each line is an entry: j/k move the selection and Enter runs it
+pub struct Popup {
+    pub title: String,
+    pub lines: Vec<Line<'static>>,
+    pub scroll: u16,
+    pub actions: Vec<PopupAction>,
+    pub selected: usize,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum ToastKind {
+    Info,
+    Error,
+}
+
+pub struct Toast {
+    pub kind: ToastKind,
+    pub message: String,
+    pub expires: Instant,
+}
+
 pub const LINK_COLOR_WEB: Color = Color::Blue;
 pub const LINK_COLOR_IMG: Color = Color::Magenta;
+pub const LINK_COLOR_MEDIA: Color = Color::LightBlue;
+pub const LINK_COLOR_FIELD: Color = Color::LightGreen;
+
+// Rendered links are told apart from the rest of the text by their color
+pub fn is_link_style(style: Style) -> bool {
+    style.fg.is_some_and(|fg| [LINK_COLOR_WEB, LINK_COLOR_IMG, LINK_COLOR_MEDIA, LINK_COLOR_FIELD].contains(&fg))
+}
+
+#[derive(Clone)]
+pub enum Translator {
+    LibreTranslate { url: String, api_key: Option<String> },
+    DeepL { url: String, api_key: String },
+    // Shell command reading text on stdin; `{lang}` is replaced by the target language
+    Command(String),
+}
+
+impl Translator {
+    // "libretranslate:<url>", "deepl:<auth key>" or "cmd:<shell command>"
+    pub fn parse(spec: &str) -> Option<Self> {
+        let (kind, value) = spec.split_once(':')?;
+        match kind {
+            "libretranslate" => {
+                let (url, key) = value.split_once('#').map(|(u, k)| (u, Some(k.to_string()))).unwrap_or((value, None));
+                Some(Translator::LibreTranslate { url: url.to_string(), api_key: key })
+            }
+            "deepl" => {
+                // Free-tier keys end in ":fx" and use a separate host
+                let host = if value.ends_with(":fx") { "api-free.deepl.com" } else { "api.deepl.com" };
+                Some(Translator::DeepL { url: format!("https://{}/v2/translate", host), api_key: value.to_string() })
+            }
+            "cmd" => Some(Translator::Command(value.to_string())),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub enum Dictionary {
+    // dict protocol server, e.g. "dict.org"
+    Dict(String),
+    // URL template with `{}` for the word (dictionaryapi.dev JSON is formatted)
+    Web(String),
+    // Command that receives the word as its last argument, e.g. "sdcv -n --utf8-output"
+    Command(String),
+}
+
+impl Dictionary {
+    // "dict:<host>", "web:<url template>" or "cmd:<command>"
+    pub fn parse(spec: &str) -> Option<Self> {
+        let (kind, value) = spec.split_once(':')?;
+        match kind {
+            "dict" => Some(Dictionary::Dict(value.to_string())),
+            "web" => Some(Dictionary::Web(value.to_string())),
+            "cmd" => Some(Dictionary::Command(value.to_string())),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum ViewKind {
+    Source,
+    Extract,
+    Diff,
+}
+
+// The rendered page stashed while an alternate view (e.g. page source) occupies the content pane
+pub struct SavedView {
+    pub kind: ViewKind,
+    pub lines: Vec<Line<'static>>,
+    pub links: Vec<LinkData>,
+    pub scroll: u16,
+    pub selected_link_idx: usize,
+}
+
+pub struct Translation {
+    pub lang: String,
+    pub showing_translated: bool,
+    pub other_lines: Vec<Line<'static>>,
+    pub other_links: Vec<LinkData>,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum HttpVersion {
+    // Negotiated via ALPN, which prefers HTTP/2 when the server offers it
+    Auto,
+    Http1,
+    Http2,
+    Http3,
+}
+
+impl HttpVersion {
+    pub fn parse(value: &str) -> Result<Self, String> {
+        match value {
+            "auto" => Ok(HttpVersion::Auto),
+            "1" | "1.1" | "http1" => Ok(HttpVersion::Http1),
+            "2" | "h2" | "http2" => Ok(HttpVersion::Http2),
+            "3" | "h3" | "http3" if cfg!(feature = "http3") => Ok(HttpVersion::Http3),
+            "3" | "h3" | "http3" => Err("built without the http3 feature".to_string()),
+            _ => Err(format!("expected auto, 1.1, 2 or 3, got {:?}", value)),
+        }
+    }
+}
+
+pub struct Settings {
+    pub search_engine: String,
+    pub meta_engines: Vec<(String, String)>,
+    pub hyperlinks: bool,
+    pub translator: Translator,
+    pub translate_lang: String,
+    pub dictionary: Dictionary,
+    // Command <audio>/<video> sources are played with, given the URL; empty for none
+    pub player: String,
+    pub link_numbers: bool,
+    // `/` searches are case-sensitive when the pattern has a capital letter
+    pub smartcase: bool,
+    // The terminal background the colors are picked for; None uses the one detected at startup
+    pub background: Option<Background>,
+    // "default", or a theme file in the config directory's themes/ (see theme.rs)
+    pub theme: String,
+    // Language of messages and help; English where a catalog has no translation
+    pub locale: Locale,
+    // How many colors the terminal shows; None uses what was detected at startup
+    pub color_depth: Option<ColorDepth>,
+    // Mark links with a superscript number and list their URLs at the end of the page
+    pub footnotes: bool,
+    // Show each link's URL, dimmed, after its text
+    pub show_urls: bool,
+    pub images: bool,
+    // How image previews are drawn; None uses what was detected at startup (see graphics.rs)
+    pub graphics: Option<Graphics>,
+    // Lay out only the main article of each page (see reader.rs)
+    pub reader: bool,
+    // Plain ASCII and the least terminal output, for slow SSH links and serial consoles
+    pub ascii: bool,
+    pub http_version: HttpVersion,
+    pub auto_paginate: bool,
+    // Lines moved per j / k press and per mouse wheel notch
+    pub scroll_step: usize,
+    pub wheel_step: usize,
+    // Animate scrolls over a few redraws instead of jumping
+    pub smooth_scroll: bool,
+    // Pages kept laid out in memory for back / forward; 0 keeps none
+    pub page_cache: usize,
+    // Bodies over this many KB load in chunks of this size; 0 loads everything at once
+    pub chunk_kb: usize,
+    // Bodies announced larger than this many MB ask before downloading; 0 never asks
+    pub size_limit_mb: u64,
+    // Redirects followed per load; the one past the limit is shown as a page with a link on
+    pub redirects: usize,
+    // "auto", "none" or a proxy URL (see proxy.rs)
+    pub proxy: String,
+    // Sent with every request, unless voyager.toml has a User-Agent header for the site
+    pub user_agent: String,
+    // Minutes between re-fetches of watched reading list pages and bookmarks; 0 turns the watcher off
+    pub monitor_mins: u64,
+    // (site, alternative front end host) offered for pages that need JavaScript
+    pub frontends: Vec<(String, String)>,
+    // Follow AMP pages through to the publisher's own URL, except on the listed sites
+    pub deamp: bool,
+    // Resolve known URL shorteners and confirm the destination before going there
+    pub expand_short: bool,
+    pub deamp_skip: Vec<String>,
+    // Blank columns either side of the text, in addition to any zoom
+    pub margin_left: usize,
+    pub margin_right: usize,
+    // Blank lines between paragraphs; None keeps the spacing the page was laid out with
+    pub paragraph_spacing: Option<usize>,
+    // Columns the first line of each paragraph is indented by
+    pub indent: usize,
+    // Skip the confirmation before writing files / running external commands
+    pub allow_write: bool,
+    pub allow_exec: bool,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        let margins = std::env::var("VOYAGER_MARGIN").ok().and_then(|v| parse_margins(&v).ok()).unwrap_or((0, 0));
+        Self {
+            search_engine: String::from("https://html.duckduckgo.com/html/?q={}"),
+            meta_engines: vec![
+                (String::from("ddg"), String::from("https://html.duckduckgo.com/html/?q={}")),
+                (String::from("bing"), String::from("https://www.bing.com/search?q={}")),
+                (String::from("mojeek"), String::from("https://www.mojeek.com/search?q={}")),
+            ],
+            hyperlinks: terminal_supports_osc8(),
+            translator: std::env::var("VOYAGER_TRANSLATOR").ok()
+                .and_then(|spec| Translator::parse(&spec))
+                .unwrap_or(Translator::LibreTranslate {
+                    url: String::from("https://libretranslate.com/translate"),
+                    api_key: None,
+                }),
+            translate_lang: String::from("en"),
+            dictionary: std::env::var("VOYAGER_DICTIONARY").ok()
+                .and_then(|spec| Dictionary::parse(&spec))
+                .unwrap_or(Dictionary::Dict(String::from("dict.org"))),
+            player: std::env::var("VOYAGER_PLAYER").unwrap_or_default(),
+            link_numbers: true,
+            smartcase: false,
+            color_depth: std::env::var("VOYAGER_COLORS").ok().and_then(|v| ColorDepth::parse(&v).ok()).flatten(),
+            graphics: std::env::var("VOYAGER_GRAPHICS").ok().and_then(|v| Graphics::parse(&v).ok()).flatten(),
+            theme: std::env::var("VOYAGER_THEME").unwrap_or_else(|_| "default".to_string()),
+            locale: Locale::detect(),
+            background: std::env::var("VOYAGER_BACKGROUND").ok().and_then(|v| Background::parse(&v).ok()).flatten(),
+            footnotes: false,
+            show_urls: false,
+            images: true,
+            reader: std::env::var("VOYAGER_READER").is_ok_and(|v| parse_bool(&v).unwrap_or(false)),
+            ascii: std::env::var("VOYAGER_ASCII").is_ok_and(|v| parse_bool(&v).unwrap_or(false)),
+            http_version: std::env::var("VOYAGER_HTTP").ok()
+                .and_then(|v| HttpVersion::parse(&v).ok())
+                .unwrap_or(HttpVersion::Auto),
+            auto_paginate: false,
+            scroll_step: std::env::var("VOYAGER_SCROLL").ok().and_then(|v| v.parse().ok()).unwrap_or(1),
+            wheel_step: std::env::var("VOYAGER_WHEEL").ok().and_then(|v| v.parse().ok()).unwrap_or(3),
+            smooth_scroll: std::env::var("VOYAGER_SMOOTH").is_ok_and(|v| parse_bool(&v).unwrap_or(false)),
+            page_cache: 20,
+            chunk_kb: 1024,
+            size_limit_mb: 20,
+            redirects: std::env::var("VOYAGER_REDIRECTS").ok().and_then(|v| v.parse().ok()).unwrap_or(10),
+            proxy: std::env::var("VOYAGER_PROXY").ok().and_then(|v| proxy::parse(&v).ok()).unwrap_or_else(|| "auto".to_string()),
+            user_agent: std::env::var("VOYAGER_USER_AGENT").ok().and_then(|v| parse_user_agent(&v).ok()).unwrap_or_else(|| USER_AGENT.to_string()),
+            monitor_mins: 0,
+            frontends: Vec::new(),
+            deamp: true,
+            expand_short: true,
+            deamp_skip: Vec::new(),
+            margin_left: margins.0,
+            margin_right: margins.1,
+            paragraph_spacing: std::env::var("VOYAGER_SPACING").ok().and_then(|v| parse_spacing(&v).ok()).flatten(),
+            indent: std::env::var("VOYAGER_INDENT").ok().and_then(|v| v.parse().ok()).unwrap_or(0),
+            allow_write: false,
+            allow_exec: false,
+        }
+    }
+}
+
+// "4" for both sides, or "4,2" for left and right
+fn parse_margins(value: &str) -> Result<(usize, usize), String> {
+    let err = || format!("expected a margin in columns, or <left>,<right>, got {:?}", value);
+    let (left, right) = value.split_once(',').unwrap_or((value, value));
+    Ok((left.trim().parse().map_err(|_| err())?, right.trim().parse().map_err(|_| err())?))
+}
+
+fn parse_spacing(value: &str) -> Result<Option<usize>, String> {
+    match value {
+        "auto" => Ok(None),
+        n => n.parse().map(Some).map_err(|_| format!("expected auto or a number of lines, got {:?}", value)),
+    }
+}
+
+fn parse_bool(value: &str) -> Result<bool, String> {
+    match value {
+        "on" | "true" | "yes" | "1" => Ok(true),
+        "off" | "false" | "no" | "0" => Ok(false),
+        _ => Err(format!("expected on/off, got {:?}", value)),
+    }
+}
+
+fn parse_user_agent(value: &str) -> Result<String, String> {
+    match value {
+        "default" => Ok(USER_AGENT.to_string()),
+        _ if !value.is_empty() && reqwest::header::HeaderValue::from_str(value).is_ok() => Ok(value.to_string()),
+        _ => Err(format!("expected default or a User-Agent string, got {:?}", value)),
+    }
+}
+
+fn parse_lines(value: &str) -> Result<usize, String> {
+    value.parse().ok().filter(|&n| n > 0).ok_or_else(|| format!("expected a number of lines, got {:?}", value))
+}
+
+pub enum SetEffect {
+    None,
+    Rerender,
+    RebuildClient,
+    Restyle,
+}
+
+impl Settings {
+    // Applies a `:set key=value` assignment
+    pub fn set(&mut self, key: &str, value: &str) -> Result<SetEffect, String> {
+        match key {
+            "linknums" => { self.link_numbers = parse_bool(value)?; Ok(SetEffect::Rerender) }
+            "linkstyle" => {
+                self.footnotes = match value {
+                    "inline" => false,
+                    "footnote" => true,
+                    _ => return Err(format!("expected inline or footnote, got {:?}", value)),
+                };
+                Ok(SetEffect::Rerender)
+            }
+            "background" => { self.background = Background::parse(value)?; Ok(SetEffect::Restyle) }
+            "theme" => { self.theme = value.to_string(); Ok(SetEffect::Restyle) }
+            "locale" => {
+                self.locale = Locale::parse(value)?;
+                i18n::set_locale(self.locale);
+                Ok(SetEffect::Rerender)
+            }
+            "colors" => { self.color_depth = ColorDepth::parse(value)?; Ok(SetEffect::None) }
+            "showurls" => { self.show_urls = parse_bool(value)?; Ok(SetEffect::Rerender) }
+            "images" => { self.images = parse_bool(value)?; Ok(SetEffect::Rerender) }
+            "graphics" => { self.graphics = Graphics::parse(value)?; Ok(SetEffect::None) }
+            "reader" => { self.reader = parse_bool(value)?; Ok(SetEffect::Rerender) }
+            "ascii" => { self.ascii = parse_bool(value)?; Ok(SetEffect::Rerender) }
+            "allow-write" => { self.allow_write = parse_bool(value)?; Ok(SetEffect::None) }
+            "allow-exec" => { self.allow_exec = parse_bool(value)?; Ok(SetEffect::None) }
+            "autopage" => { self.auto_paginate = parse_bool(value)?; Ok(SetEffect::None) }
+            "hyperlinks" => { self.hyperlinks = parse_bool(value)?; Ok(SetEffect::None) }
+            "scroll" => { self.scroll_step = parse_lines(value)?; Ok(SetEffect::None) }
+            "wheel" => { self.wheel_step = parse_lines(value)?; Ok(SetEffect::None) }
+            "smartcase" => { self.smartcase = parse_bool(value)?; Ok(SetEffect::None) }
+            "smooth" => { self.smooth_scroll = parse_bool(value)?; Ok(SetEffect::None) }
+            "pagecache" => { self.page_cache = value.parse().map_err(|_| format!("expected a number of pages, got {:?}", value))?; Ok(SetEffect::None) }
+            "chunk" => { self.chunk_kb = value.parse().map_err(|_| format!("expected a size in KB, got {:?}", value))?; Ok(SetEffect::None) }
+            "redirects" => { self.redirects = value.parse().map_err(|_| format!("expected a number of redirects, got {:?}", value))?; Ok(SetEffect::RebuildClient) }
+            "proxy" => { self.proxy = proxy::parse(value)?; Ok(SetEffect::RebuildClient) }
+            "useragent" => { self.user_agent = parse_user_agent(value)?; Ok(SetEffect::RebuildClient) }
+            "sizelimit" => { self.size_limit_mb = value.parse().map_err(|_| format!("expected a size in MB, got {:?}", value))?; Ok(SetEffect::None) }
+            "monitor" => { self.monitor_mins = value.parse().map_err(|_| format!("expected minutes, got {:?}", value))?; Ok(SetEffect::None) }
+            "expand" => { self.expand_short = parse_bool(value)?; Ok(SetEffect::None) }
+            "deamp" => { self.deamp = parse_bool(value)?; Ok(SetEffect::None) }
+            "deamp-skip" => {
+                self.deamp_skip = value.split(',').map(|s| s.trim().trim_start_matches("www.").to_string()).filter(|s| !s.is_empty()).collect();
+                Ok(SetEffect::None)
+            }
+            "frontend" => match value.split_once('=') {
+                Some((site, "")) => { self.frontends.retain(|(s, _)| s != site); Ok(SetEffect::None) }
+                Some((site, alt)) => {
+                    self.frontends.retain(|(s, _)| s != site);
+                    self.frontends.push((site.trim_start_matches("www.").to_string(), alt.to_string()));
+                    Ok(SetEffect::None)
+                }
+                None => Err(format!("expected frontend=<site>=<host>, got {:?}", value)),
+            },
+            "margin" => {
+                (self.margin_left, self.margin_right) = parse_margins(value)?;
+                Ok(SetEffect::Rerender)
+            }
+            "spacing" => { self.paragraph_spacing = parse_spacing(value)?; Ok(SetEffect::Rerender) }
+            "indent" => { self.indent = value.parse().map_err(|_| format!("expected a number of columns, got {:?}", value))?; Ok(SetEffect::Rerender) }
+            "player" => { self.player = value.to_string(); Ok(SetEffect::None) }
+            "engine" => { self.search_engine = value.to_string(); Ok(SetEffect::None) }
+            "lang" => { self.translate_lang = value.to_string(); Ok(SetEffect::None) }
+            "translator" => {
+                self.translator = Translator::parse(value).ok_or_else(|| format!("bad translator spec {:?}", value))?;
+                Ok(SetEffect::None)
+            }
+            "http" => { self.http_version = HttpVersion::parse(value)?; Ok(SetEffect::RebuildClient) }
+            "dictionary" => {
+                self.dictionary = Dictionary::parse(value).ok_or_else(|| format!("bad dictionary spec {:?}", value))?;
+                Ok(SetEffect::None)
+            }
+            _ => Err(format!("unknown setting {:?}", key)),
+        }
+    }
+}
+
+fn terminal_supports_osc8() -> bool {
+    let env = |k: &str| std::env::var(k).unwrap_or_default();
+    if let Ok(v) = std::env::var("VOYAGER_HYPERLINKS") {
+        return v == "1" || v == "on";
+    }
+    matches!(env("TERM_PROGRAM").as_str(), "iTerm.app" | "WezTerm" | "vscode" | "Hyper" | "ghostty")
+        || std::env::var_os("KITTY_WINDOW_ID").is_some()
+        || std::env::var_os("WT_SESSION").is_some()
+        || env("VTE_VERSION").parse::<u32>().is_ok_and(|v| v >= 5000)
+        || env("TERM").starts_with("foot")
+        || env("TERM") == "alacritty"
+}
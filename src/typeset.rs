@@ -0,0 +1,48 @@
+use crate::types::Settings;
+use ratatui::text::{Line, Span};
+
+fn is_blank(line: &Line) -> bool {
+    line.spans.iter().all(|s| s.content.trim().is_empty())
+}
+
+// List items, quotes, drawn tables and preformatted or already indented text keep their own layout
+fn is_prose(line: &Line) -> bool {
+    let text: String = line.spans.iter().map(|s| s.content.as_ref()).collect();
+    let starts = |p: &str| text.starts_with(p);
+    let boxed = text.starts_with(|c| ('\u{2500}'..='\u{257F}').contains(&c));
+    !(text.starts_with(char::is_whitespace) || boxed || starts("* ") || starts("- ") || starts("> ") || starts("#")
+        || text.split_once(". ").is_some_and(|(n, _)| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit())))
+}
+
+// Columns taken from the layout width by the margins and the first-line indent
+pub fn reserved_width(settings: &Settings) -> usize {
+    settings.margin_left + settings.margin_right + settings.indent
+}
+
+// Book-style paragraphs: blank lines between paragraphs collapsed to the configured
+// spacing, and the first line of each paragraph of running text indented
+pub fn apply(lines: Vec<Line<'static>>, settings: &Settings) -> Vec<Line<'static>> {
+    if settings.paragraph_spacing.is_none() && settings.indent == 0 {
+        return lines;
+    }
+    let mut out = Vec::with_capacity(lines.len());
+    let mut blanks = 0;
+    let mut paragraph_start = true;
+    for mut line in lines {
+        if is_blank(&line) {
+            blanks += 1;
+            paragraph_start = true;
+            continue;
+        }
+        let keep = if out.is_empty() { blanks } else { settings.paragraph_spacing.unwrap_or(blanks) };
+        out.extend(std::iter::repeat_n(Line::from(""), keep));
+        blanks = 0;
+        if paragraph_start && settings.indent > 0 && is_prose(&line) {
+            line.spans.insert(0, Span::raw(" ".repeat(settings.indent)));
+        }
+        paragraph_start = false;
+        out.push(line);
+    }
+    out.extend(std::iter::repeat_n(Line::from(""), blanks));
+    out
+}
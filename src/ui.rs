@@ -1,61 +1,466 @@
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
-    style::{Color, Style},
-    text::Line,
-    widgets::{Block, Borders, Clear, Paragraph},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph, Wrap},
     Frame,
 };
 use crate::app::App;
-use crate::types::Mode;
+use crate::dialog::{Dialog, DialogKind};
+use crate::graphics::Graphics;
+use crate::har::Exchange;
+use crate::help;
+use crate::i18n::{self, tr};
+use crate::inspector::Inspector;
+use crate::picker::Picker;
+use crate::types::{is_link_style, LinkType, Mode, Popup, Toast, ToastKind};
+use std::rc::Rc;
 
-pub fn draw(f: &mut Frame, app: &App) {
-    let chunks = Layout::default()
+// Tab bar (only with more than one tab), URL bar, content, status line
+fn main_layout(area: Rect, app: &App) -> Rc<[Rect]> {
+    let tab_bar = if app.tabs.len() > 1 { 1 } else { 0 };
+    Layout::default()
         .direction(Direction::Vertical)
-        .constraints([Constraint::Length(3), Constraint::Min(0), Constraint::Length(1)])
-        .split(f.size());
+        .constraints([Constraint::Length(tab_bar), Constraint::Length(3), Constraint::Min(0), Constraint::Length(1)])
+        .split(area)
+}
+
+// The content chunk shared between the page and the panes open beside it: the network pane
+// takes the bottom 30%, then the inspector the right 40% of what is left
+struct ContentPanes {
+    page: Rect,
+    network: Option<Rect>,
+    inspector: Option<Rect>,
+}
+
+fn content_panes(area: Rect, app: &App) -> ContentPanes {
+    let mut panes = ContentPanes { page: main_layout(area, app)[2], network: None, inspector: None };
+    if app.network_pane {
+        let split = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Percentage(70), Constraint::Percentage(30)])
+            .split(panes.page);
+        (panes.page, panes.network) = (split[0], Some(split[1]));
+    }
+    if app.inspector.is_some() {
+        let split = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
+            .split(panes.page);
+        (panes.page, panes.inspector) = (split[0], Some(split[1]));
+    }
+    panes
+}
+
+fn content_block() -> Block<'static> {
+    Block::default().borders(Borders::LEFT | Borders::RIGHT)
+}
+
+// Where the page text is drawn on a screen of `area`. Scrolling and the position shown in the
+// status bar go by its height.
+pub fn page_area(area: Rect, app: &App) -> Rect {
+    page_column(content_block().inner(content_panes(area, app).page), app)
+}
+
+// A zoomed page is laid out narrower or wider than usual; a narrow one is centered.
+// The margins are then taken from either side of the column.
+fn page_column(area: Rect, app: &App) -> Rect {
+    let column = match app.zoomed_width() {
+        Some(width) if (width as u16) < area.width => {
+            let width = width as u16;
+            Rect { x: area.x + (area.width - width) / 2, width, ..area }
+        }
+        _ => area,
+    };
+    let left = (app.settings.margin_left as u16).min(column.width);
+    let right = (app.settings.margin_right as u16).min(column.width - left);
+    Rect { x: column.x + left, width: column.width - left - right, ..column }
+}
+
+pub fn draw(f: &mut Frame, app: &App) {
+    let chunks = main_layout(f.size(), app);
+
+    if app.tabs.len() > 1 {
+        draw_tab_bar(f, app, chunks[0]);
+    }
 
     // URL Bar
+    let url_title = match app.page_info.title {
+        Some(ref t) => format!(" {} ", t),
+        None => format!(" {} ", i18n::lookup("Voyager URL")),
+    };
+    let url_title = if app.sandbox { format!("{}[sandbox] ", url_title) } else { url_title };
+    let security = app.security();
+    // A lookalike host is shown in its punycode form, in red, with the reason
+    let (url_text, url_style) = match url::Url::parse(&app.current_url).ok().as_ref().and_then(crate::homograph::check) {
+        Some(warning) => (format!("⚠ {}  —  {}", app.current_url, warning), Style::default().fg(Color::White).bg(Color::Red)),
+        None => (format!("{}{}", security.glyph(app.settings.ascii), app.current_url), Style::default()),
+    };
     f.render_widget(
-        Paragraph::new(app.current_url.as_str())
-            .block(Block::default().borders(Borders::ALL).title(" Voyager URL ")),
-        chunks[0]
+        Paragraph::new(url_text).style(url_style)
+            .block(Block::default().borders(Borders::ALL).border_style(Style::default().fg(security.color())).title(url_title)),
+        chunks[1]
     );
 
     // Main Content
-    f.render_widget(
-        Paragraph::new(app.render_content())
-            .block(Block::default().borders(Borders::LEFT | Borders::RIGHT))
-            .scroll((app.scroll, 0)),
-        chunks[1]
-    );
+    let panes = content_panes(f.size(), app);
+    if let Some(area) = panes.network {
+        draw_network(f, &app.session_log, area);
+    }
+    if let (Some(inspector), Some(area)) = (&app.inspector, panes.inspector) {
+        draw_inspector(f, inspector, area);
+    }
+    f.render_widget(content_block(), panes.page);
+    let page = page_area(f.size(), app);
+    f.render_widget(Paragraph::new(app.render_content()).scroll((app.scroll, 0)), page);
 
     // Status Bar
     let status_text = match app.mode {
         Mode::Command => format!(":{}", app.command_buffer),
-        Mode::Normal => format!(
-            " {} | Link [{}]: {}",
+        Mode::Search => format!("/{}", app.command_buffer),
+        Mode::Insert => format!(" {}", app.insert_prompt()),
+        Mode::Hint => format!(" {}", app.hint_prompt()),
+        Mode::Url => app.url_line(chunks[3].width as usize).0,
+        Mode::Request | Mode::Picker | Mode::Dialog => format!(" {}", app.status),
+        Mode::Normal | Mode::Caret if app.loading.is_some() => format!(" {}", app.load_progress()),
+        Mode::Normal | Mode::Caret if app.settings.ascii => format!("{} {}", app.position_summary(page.height), app.status),
+        Mode::Normal | Mode::Caret => format!(
+            " {} | {} | {} | {} [{}]: {}",
             app.status,
+            app.position_summary(page.height),
+            app.stats.summary(),
+            i18n::lookup("Link"),
             app.selected_link_idx,
             if app.links.is_empty() { "" } else { &app.links[app.selected_link_idx].url }
         ),
     };
     f.render_widget(
-        Paragraph::new(status_text).style(Style::default().bg(Color::White).fg(Color::Black)),
-        chunks[2]
+        Paragraph::new(status_text).style(app.palette().status_bar()),
+        chunks[3]
     );
+    if app.mode == Mode::Url {
+        let (_, column) = app.url_line(chunks[3].width as usize);
+        f.set_cursor(chunks[3].x + column as u16, chunks[3].y);
+    }
 
     // Image Popup
-    if let Some(ref aa) = app.image_preview {
+    if let Some(ref preview) = app.image_preview {
         let area = centered_rect(80, 80, f.size());
         f.render_widget(Clear, area);
-        let aa_lines: Vec<Line> = aa.iter().map(|s| Line::from(s.clone())).collect();
+        // With a graphics protocol the popup is left empty, and the event loop draws the image into it
+        let (title, aa_lines): (_, Vec<Line>) = match app.graphics() {
+            Graphics::Text => ("Image AA Preview", preview.aa.iter().map(|s| Line::from(s.clone())).collect()),
+            _ => ("Image Preview", Vec::new()),
+        };
         f.render_widget(
             Paragraph::new(aa_lines)
-                .block(Block::default().borders(Borders::ALL).title(" Image AA Preview "))
+                .block(Block::default().borders(Borders::ALL).title(format!(" {} ", i18n::lookup(title))))
                 .style(Style::default().bg(Color::Black)),
             area
         );
     }
+
+    if let Some(ref popup) = app.popup {
+        draw_popup(f, popup);
+    }
+
+    if let Some(ref picker) = app.picker {
+        draw_picker(f, picker);
+    }
+
+    if let Some(ref dialog) = app.dialog {
+        draw_dialog(f, dialog);
+    }
+
+    if let Some(prefix) = app.pending_key {
+        draw_which_key(f, app, prefix, chunks[2]);
+    }
+
+    draw_toasts(f, &app.toasts, chunks[2]);
+
+    // Request Composer
+    if app.mode == Mode::Request {
+        let area = centered_rect(70, 60, f.size());
+        f.render_widget(Clear, area);
+        let mut text = app.request_buffer.clone();
+        text.push('█');
+        f.render_widget(
+            Paragraph::new(text)
+                .block(Block::default().borders(Borders::ALL).title(format!(" {} ", i18n::lookup("Request (METHOD URL / headers / blank line / body)"))))
+                .wrap(Wrap { trim: false }),
+            area
+        );
+    }
+
+    if app.settings.ascii {
+        asciify(f);
+    }
+}
+
+// One cell per tab, numbered for :tab N, the shown tab highlighted; labels are cut to fit
+fn draw_tab_bar(f: &mut Frame, app: &App, area: Rect) {
+    let palette = app.palette();
+    let labels = app.tab_labels();
+    let width = (area.width as usize / labels.len()).clamp(8, 30);
+    let spans: Vec<Span> = labels.into_iter().enumerate().map(|(i, (label, active))| {
+        let text = format!(" {}: {}", i + 1, label);
+        let text: String = if text.chars().count() > width - 1 { text.chars().take(width - 2).chain(['…']).collect() } else { text };
+        let style = if active { palette.highlight().add_modifier(Modifier::BOLD) } else { palette.status_bar() };
+        Span::styled(format!("{} ", i18n::pad(&text, width - 1)), style)
+    }).collect();
+    f.render_widget(Paragraph::new(Line::from(spans)).style(palette.status_bar()), area);
+}
+
+// Stand-in for a glyph that a plain ASCII terminal can't show
+fn ascii_fallback(c: char) -> Option<char> {
+    Some(match c {
+        '─' | '━' | '┄' | '┅' | '┈' | '┉' | '╌' | '╍' | '═' | '—' | '–' => '-',
+        '│' | '┃' | '┆' | '┇' | '┊' | '┋' | '╎' | '╏' | '║' => '|',
+        '\u{2500}'..='\u{257F}' => '+',
+        '\u{2580}'..='\u{259F}' => '#',
+        '·' | '…' => '.',
+        '•' | '●' => '*',
+        '○' | '◌' => 'o',
+        '▾' => 'v',
+        '▸' | '›' | '»' | '→' => '>',
+        '‹' | '«' | '←' => '<',
+        '‘' | '’' => '\'',
+        '“' | '”' => '"',
+        '⚠' => '!',
+        // Emoji and pictographs
+        '\u{2600}'..='\u{27BF}' | '\u{1F300}'..='\u{1FAFF}' => '?',
+        _ => return None,
+    })
+}
+
+// Rewrites the finished frame so borders, cursors, markers and emoji go out as ASCII.
+// Wide emoji leave a blank cell behind them, so the layout doesn't shift.
+fn asciify(f: &mut Frame) {
+    for cell in &mut f.buffer_mut().content {
+        let mut chars = cell.symbol().chars();
+        if let (Some(c), None) = (chars.next(), chars.next()) && let Some(plain) = ascii_fallback(c) {
+            cell.set_char(plain);
+        }
+    }
+}
+
+fn draw_inspector(f: &mut Frame, inspector: &Inspector, area: Rect) {
+    let panes = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
+        .split(area);
+    let items: Vec<ListItem> = inspector.visible().into_iter().map(|i| {
+        let node = &inspector.nodes[i];
+        let marker = if node.subtree_end == i + 1 { "·" } else if node.expanded { "▾" } else { "▸" };
+        ListItem::new(format!("{}{} {}", "  ".repeat(node.depth), marker, node.label))
+    }).collect();
+    let mut state = ListState::default().with_selected(Some(inspector.selected));
+    f.render_stateful_widget(
+        List::new(items)
+            .block(Block::default().borders(Borders::ALL).title(" DOM "))
+            .highlight_style(Style::default().bg(Color::Yellow).fg(Color::Black)),
+        panes[0],
+        &mut state
+    );
+
+    let mut details = Vec::new();
+    if let Some(node) = inspector.selected_node() {
+        details.push(Line::from(Span::styled(node.label.clone(), Style::default().fg(Color::Cyan))));
+        for (k, v) in &node.attrs {
+            details.push(Line::from(vec![Span::styled(format!("{}=", k), Style::default().fg(Color::Yellow)), Span::raw(v.clone())]));
+        }
+        details.push(Line::from(""));
+        details.push(Line::from(node.text.chars().take(500).collect::<String>()));
+    }
+    f.render_widget(
+        Paragraph::new(details).block(Block::default().borders(Borders::ALL).title(" Node ")).wrap(Wrap { trim: true }),
+        panes[1]
+    );
+}
+
+// Most recent requests last, trimmed from the top so the newest entry stays visible
+fn draw_network(f: &mut Frame, log: &[Exchange], area: Rect) {
+    let rows = area.height.saturating_sub(2) as usize;
+    let items: Vec<ListItem> = log[log.len().saturating_sub(rows)..].iter().map(|e| {
+        let status_color = match e.response.status {
+            200..=299 => Color::Green,
+            300..=399 => Color::Cyan,
+            _ => Color::Red,
+        };
+        let ms = (e.timing.ttfb + e.timing.body).as_millis();
+        ListItem::new(Line::from(vec![
+            Span::raw(format!("{:<7}", e.request.method)),
+            Span::styled(format!("{:<4}", e.response.status), Style::default().fg(status_color)),
+            Span::raw(format!("{:>9} {:>6}ms ", format_size(e.timing.bytes), ms)),
+            Span::styled(if e.from_cache { "hit  " } else { "miss " }, Style::default().fg(Color::DarkGray)),
+            Span::raw(e.request.url.clone()),
+        ]))
+    }).collect();
+    f.render_widget(
+        List::new(items).block(Block::default().borders(Borders::ALL).title(format!(" {} ", tr!("Network ({} requests)", log.len())))),
+        area
+    );
+}
+
+fn draw_dialog(f: &mut Frame, dialog: &Dialog) {
+    let mut lines: Vec<Line> = dialog.message.lines().map(|l| Line::from(l.to_string())).collect();
+    lines.push(Line::from(""));
+    let hint = match dialog.kind {
+        DialogKind::Confirm { .. } => "y: yes   n / Enter / Esc: no",
+        DialogKind::Prompt { ref input, secret, .. } => {
+            let shown = if secret { "*".repeat(input.chars().count()) } else { input.clone() };
+            lines.push(Line::from(format!("> {}█", shown)));
+            "Enter: submit   Esc: cancel"
+        }
+        DialogKind::Choice { ref options, selected, .. } => {
+            for (i, (label, _)) in options.iter().enumerate() {
+                let style = if i == selected { Style::default().bg(Color::Yellow).fg(Color::Black) } else { Style::default() };
+                lines.push(Line::from(Span::styled(format!("{}. {}", i + 1, label), style)));
+            }
+            "j / k or 1-9: choose   Enter: confirm   Esc: cancel"
+        }
+    };
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(i18n::lookup(hint), Style::default().fg(Color::DarkGray))));
+    let area = centered_rect(60, 40, f.size());
+    let height = (lines.len() as u16 + 2).min(area.height);
+    let area = Rect { y: area.y + (area.height - height) / 2, height, ..area };
+    f.render_widget(Clear, area);
+    f.render_widget(
+        Paragraph::new(lines)
+            .block(Block::default().borders(Borders::ALL).title(dialog.title.as_str()).border_style(Style::default().fg(Color::Yellow)))
+            .wrap(Wrap { trim: false }),
+        area
+    );
+}
+
+fn draw_picker(f: &mut Frame, picker: &Picker) {
+    let area = centered_rect(70, 60, f.size());
+    f.render_widget(Clear, area);
+    let panes = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(3), Constraint::Min(0)])
+        .split(area);
+    f.render_widget(
+        Paragraph::new(format!("> {}█", picker.query))
+            .block(Block::default().borders(Borders::ALL).title(picker.title.as_str())),
+        panes[0]
+    );
+    let items: Vec<ListItem> = picker.matches.iter().map(|&i| {
+        let item = &picker.items[i];
+        ListItem::new(Line::from(vec![
+            Span::raw(item.label.clone()),
+            Span::styled(format!("  {}", item.detail), Style::default().fg(Color::DarkGray)),
+        ]))
+    }).collect();
+    let mut state = ListState::default().with_selected(Some(picker.selected));
+    f.render_stateful_widget(
+        List::new(items)
+            .block(Block::default().borders(Borders::ALL).title(format!(" {}/{} ", picker.matches.len(), picker.items.len())))
+            .highlight_style(Style::default().bg(Color::Yellow).fg(Color::Black)),
+        panes[1],
+        &mut state
+    );
+}
+
+// The continuations of a pending prefix key, in the content area's bottom-right corner
+fn draw_which_key(f: &mut Frame, app: &App, prefix: char, area: Rect) {
+    let palette = app.palette();
+    let mut lines: Vec<Line> = help::PREFIX_KEYS.iter()
+        .filter(|(p, _, _)| *p == prefix)
+        .map(|(p, key, desc)| Line::from(vec![Span::styled(format!(" {}{}  ", p, key), Style::default().fg(palette.accent).add_modifier(Modifier::BOLD)), Span::raw(i18n::lookup(desc))]))
+        .collect();
+    lines.push(Line::from(Span::styled(format!(" {}", i18n::lookup("any other key cancels")), Style::default().fg(palette.dim))));
+    let width = (lines.iter().map(|l| l.width()).max().unwrap_or(0) as u16 + 3).min(area.width);
+    let height = (lines.len() as u16 + 2).min(area.height);
+    let rect = Rect { x: area.x + area.width - width, y: area.y + area.height - height, width, height };
+    f.render_widget(Clear, rect);
+    f.render_widget(Paragraph::new(lines).block(Block::default().borders(Borders::ALL).title(format!(" {} ", prefix))), rect);
+}
+
+// Newest at the top of a stack in the content area's top-right corner
+fn draw_toasts(f: &mut Frame, toasts: &[Toast], area: Rect) {
+    let width = (area.width / 3).clamp(20, 60).min(area.width);
+    let mut y = area.y;
+    for toast in toasts.iter().rev() {
+        let inner_width = width.saturating_sub(2).max(1) as usize;
+        let height = (toast.message.chars().count().div_ceil(inner_width) as u16 + 2).min(6);
+        if y + height > area.y + area.height {
+            break;
+        }
+        let rect = Rect { x: area.x + area.width - width, y, width, height };
+        let color = match toast.kind {
+            ToastKind::Info => Color::Green,
+            ToastKind::Error => Color::Red,
+        };
+        f.render_widget(Clear, rect);
+        f.render_widget(
+            Paragraph::new(toast.message.as_str())
+                .block(Block::default().borders(Borders::ALL).border_style(Style::default().fg(color)))
+                .wrap(Wrap { trim: true }),
+            rect
+        );
+        y += height;
+    }
+}
+
+pub fn format_size(bytes: usize) -> String {
+    match bytes {
+        b if b >= 1 << 20 => format!("{:.1} MiB", b as f64 / (1 << 20) as f64),
+        b if b >= 1 << 10 => format!("{:.1} KiB", b as f64 / (1 << 10) as f64),
+        b => format!("{} B", b),
+    }
+}
+
+pub fn draw_popup(f: &mut Frame, popup: &Popup) {
+    let area = centered_rect(80, 70, f.size());
+    f.render_widget(Clear, area);
+    let block = Block::default().borders(Borders::ALL).title(popup.title.as_str());
+    if popup.actions.is_empty() {
+        f.render_widget(
+            Paragraph::new(popup.lines.clone()).block(block).wrap(Wrap { trim: false }).scroll((popup.scroll, 0)),
+            area
+        );
+        return;
+    }
+    let items: Vec<ListItem> = popup.lines.iter().map(|l| ListItem::new(l.clone())).collect();
+    let mut state = ListState::default().with_selected(Some(popup.selected));
+    f.render_stateful_widget(
+        List::new(items).block(block).highlight_style(Style::default().bg(Color::Yellow).fg(Color::Black)),
+        area,
+        &mut state
+    );
+}
+
+// Screen position of each fully visible link span, for overlaying OSC 8 hyperlinks after a draw
+pub fn hyperlink_regions(app: &App, screen: Rect) -> Vec<(u16, u16, Span<'static>, String)> {
+    let area = page_area(screen, app);
+    let mut regions = Vec::new();
+    let mut link_idx = 0;
+    for (row, (raw, line)) in app.content_lines.iter().zip(app.render_content()).enumerate() {
+        let visible_row = (row as u16).checked_sub(app.scroll).filter(|r| *r < area.height);
+        let mut x = 0u16;
+        for (raw_span, span) in raw.spans.iter().zip(line.spans) {
+            let width = span.width() as u16;
+            if is_link_style(raw_span.style) {
+                // Form controls link to nothing outside the browser
+                if let (Some(y), Some(link)) = (visible_row, app.links.get(link_idx))
+                    && x + width <= area.width
+                    && link.link_type != LinkType::Field
+                {
+                    regions.push((area.x + x, area.y + y, span.clone(), link.url.clone()));
+                }
+                link_idx += 1;
+            }
+            x = x.saturating_add(width);
+        }
+    }
+    regions
+}
+
+// Inside the image preview's border, where a graphics protocol puts the image
+pub fn image_area(screen: Rect) -> Rect {
+    Block::default().borders(Borders::ALL).inner(centered_rect(80, 80, screen))
 }
 
 fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
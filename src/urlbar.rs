@@ -0,0 +1,257 @@
+use crate::app::App;
+use crate::i18n::{self, tr};
+use crate::types::Mode;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::text::Span;
+use std::collections::HashSet;
+use std::error::Error;
+
+// Tab cycles through at most this many
+const MAX_COMPLETIONS: usize = 50;
+
+// `o` / `O`: an address line in the status bar, empty or holding the current URL, edited
+// with readline keys and completed with Tab from bookmarks and history
+#[derive(Default)]
+pub struct UrlBar {
+    input: Vec<char>,
+    // In chars, 0..=input.len()
+    cursor: usize,
+    // The last Ctrl-U / Ctrl-K / Ctrl-W cut, for Ctrl-Y
+    killed: Vec<char>,
+    // What was typed before the first Tab, and what Tab cycles through; any edit drops them
+    typed: String,
+    completions: Vec<String>,
+    completion: Option<usize>,
+}
+
+fn is_word(c: char) -> bool {
+    c.is_alphanumeric()
+}
+
+// "https://www.example.com/a" -> "example.com/a", so typing "exa" matches it
+fn bare(url: &str) -> &str {
+    let rest = url.split_once("://").map_or(url, |(_, rest)| rest);
+    rest.strip_prefix("www.").unwrap_or(rest)
+}
+
+impl UrlBar {
+    fn new(text: &str) -> Self {
+        let input: Vec<char> = text.chars().collect();
+        Self { cursor: input.len(), input, ..Self::default() }
+    }
+
+    pub fn text(&self) -> String {
+        self.input.iter().collect()
+    }
+
+    fn set_text(&mut self, text: &str) {
+        self.input = text.chars().collect();
+        self.cursor = self.input.len();
+    }
+
+    fn edited(&mut self) {
+        self.completions.clear();
+        self.completion = None;
+    }
+
+    // Start of the word before the cursor, skipping the punctuation in between
+    fn word_back(&self) -> usize {
+        let mut i = self.cursor;
+        while i > 0 && !is_word(self.input[i - 1]) {
+            i -= 1;
+        }
+        while i > 0 && is_word(self.input[i - 1]) {
+            i -= 1;
+        }
+        i
+    }
+
+    fn word_forward(&self) -> usize {
+        let mut i = self.cursor;
+        while i < self.input.len() && !is_word(self.input[i]) {
+            i += 1;
+        }
+        while i < self.input.len() && is_word(self.input[i]) {
+            i += 1;
+        }
+        i
+    }
+
+    fn kill(&mut self, from: usize, to: usize) {
+        self.killed = self.input.drain(from..to).collect();
+        self.cursor = from;
+        self.edited();
+    }
+
+    // Single chars go without touching the kill buffer
+    fn delete_back(&mut self) {
+        if self.cursor > 0 {
+            self.cursor -= 1;
+            self.input.remove(self.cursor);
+            self.edited();
+        }
+    }
+
+    fn delete_forward(&mut self) {
+        if self.cursor < self.input.len() {
+            self.input.remove(self.cursor);
+            self.edited();
+        }
+    }
+
+    fn insert(&mut self, chars: &[char]) {
+        self.input.splice(self.cursor..self.cursor, chars.iter().copied());
+        self.cursor += chars.len();
+        self.edited();
+    }
+
+    // Past either end of the list comes back to what was typed
+    fn cycle(&mut self, forward: bool) {
+        let n = self.completions.len();
+        self.completion = match (self.completion, forward) {
+            (None, true) => Some(0),
+            (None, false) => n.checked_sub(1),
+            (Some(i), true) => (i + 1 < n).then_some(i + 1),
+            (Some(i), false) => i.checked_sub(1),
+        };
+        let text = self.completion.map_or_else(|| self.typed.clone(), |i| self.completions[i].clone());
+        self.set_text(&text);
+    }
+
+    fn edit(&mut self, key: KeyEvent) {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        let alt = key.modifiers.contains(KeyModifiers::ALT);
+        let len = self.input.len();
+        match key.code {
+            KeyCode::Left => self.cursor = self.cursor.saturating_sub(1),
+            KeyCode::Char('b') if ctrl => self.cursor = self.cursor.saturating_sub(1),
+            KeyCode::Right => self.cursor = (self.cursor + 1).min(len),
+            KeyCode::Char('f') if ctrl => self.cursor = (self.cursor + 1).min(len),
+            KeyCode::Char('b') if alt => self.cursor = self.word_back(),
+            KeyCode::Char('f') if alt => self.cursor = self.word_forward(),
+            KeyCode::Home => self.cursor = 0,
+            KeyCode::Char('a') if ctrl => self.cursor = 0,
+            KeyCode::End => self.cursor = len,
+            KeyCode::Char('e') if ctrl => self.cursor = len,
+            KeyCode::Backspace if alt => self.kill(self.word_back(), self.cursor),
+            KeyCode::Char('w') if ctrl => self.kill(self.word_back(), self.cursor),
+            KeyCode::Char('d') if alt => self.kill(self.cursor, self.word_forward()),
+            KeyCode::Char('u') if ctrl => self.kill(0, self.cursor),
+            KeyCode::Char('k') if ctrl => self.kill(self.cursor, len),
+            KeyCode::Char('y') if ctrl => {
+                let killed = self.killed.clone();
+                self.insert(&killed);
+            }
+            KeyCode::Backspace => self.delete_back(),
+            KeyCode::Char('h') if ctrl => self.delete_back(),
+            KeyCode::Delete => self.delete_forward(),
+            KeyCode::Char('d') if ctrl => self.delete_forward(),
+            KeyCode::Char(c) if !ctrl && !alt => self.insert(&[c]),
+            _ => {}
+        }
+    }
+}
+
+impl App {
+    // `o` opens it empty, `O` with the current address to edit
+    pub fn open_url_bar(&mut self, current: bool) {
+        let text = if current { self.current_url.clone() } else { String::new() };
+        self.url_bar = Some(UrlBar::new(&text));
+        self.mode = Mode::Url;
+    }
+
+    fn close_url_bar(&mut self) {
+        self.url_bar = None;
+        self.mode = Mode::Normal;
+    }
+
+    // Bookmarks, then this session's history by frecency, then the visit log newest first;
+    // addresses starting with `typed` before those that merely contain it
+    fn url_completions(&self, typed: &str) -> Vec<String> {
+        let typed = bare(typed.trim()).to_lowercase();
+        let urls = self.bookmarks.entries.iter().map(|b| b.url.clone())
+            .chain(self.history.frecent().into_iter().map(|(url, _)| url))
+            .chain(self.visits.visits.iter().rev().map(|v| v.url.clone()));
+        let mut seen = HashSet::new();
+        let (mut starting, mut containing) = (Vec::new(), Vec::new());
+        for url in urls {
+            let lower = bare(&url).to_lowercase();
+            if !lower.contains(&typed) || !seen.insert(url.clone()) {
+                continue;
+            }
+            match lower.starts_with(&typed) {
+                true => starting.push(url),
+                false => containing.push(url),
+            }
+            if starting.len() >= MAX_COMPLETIONS {
+                break;
+            }
+        }
+        starting.extend(containing);
+        starting.truncate(MAX_COMPLETIONS);
+        starting
+    }
+
+    fn complete_url(&mut self, forward: bool) {
+        let Some(bar) = self.url_bar.as_ref() else { return };
+        if bar.completions.is_empty() {
+            let typed = bar.text();
+            let found = self.url_completions(&typed);
+            if found.is_empty() {
+                self.status = tr!("No bookmarks or history match {}", typed);
+                return;
+            }
+            let Some(bar) = self.url_bar.as_mut() else { return };
+            bar.typed = typed;
+            bar.completions = found;
+        }
+        if let Some(bar) = self.url_bar.as_mut() {
+            bar.cycle(forward);
+        }
+    }
+
+    pub async fn handle_url_key(&mut self, key: KeyEvent) -> Result<(), Box<dyn Error>> {
+        let Some(bar) = self.url_bar.as_mut() else {
+            self.mode = Mode::Normal;
+            return Ok(());
+        };
+        match key.code {
+            KeyCode::Esc => self.close_url_bar(),
+            KeyCode::Enter => {
+                let text = bar.text();
+                self.close_url_bar();
+                if !text.trim().is_empty() {
+                    self.open_typed(text.trim().to_string()).await?;
+                }
+            }
+            KeyCode::Tab => self.complete_url(true),
+            KeyCode::BackTab => self.complete_url(false),
+            // Like readline's reverse search: the fuzzy picker over tabs, bookmarks and history,
+            // starting from the text
+            KeyCode::Char('r') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                let text = bar.text();
+                self.close_url_bar();
+                self.open_omnibar(&text);
+            }
+            _ => bar.edit(key),
+        }
+        Ok(())
+    }
+
+    // The status line while editing, scrolled sideways to keep the cursor in view, and the
+    // cursor's column in it
+    pub fn url_line(&self, width: usize) -> (String, usize) {
+        let Some(bar) = self.url_bar.as_ref() else { return (String::new(), 0) };
+        let prompt = format!(" {}: ", i18n::lookup("Go to"));
+        let count = match bar.completion {
+            Some(i) => format!("  ({}/{})", i + 1, bar.completions.len()),
+            None => String::new(),
+        };
+        let room = width.saturating_sub(prompt.chars().count() + count.chars().count() + 1).max(1);
+        let start = bar.cursor.saturating_sub(room);
+        let shown: String = bar.input.iter().skip(start).take(room).collect();
+        let before: String = bar.input[start..bar.cursor].iter().collect();
+        let column = Span::raw(prompt.as_str()).width() + Span::raw(before).width();
+        (format!("{}{}{}", prompt, shown, count), column)
+    }
+}
@@ -0,0 +1,123 @@
+use crate::about::escape_html;
+use crate::har::iso8601;
+use crate::types::HistoryEntry;
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+// The file is cut back to the newest visits once it grows past this many
+const MAX_VISITS: usize = 10_000;
+const MAX_SHOWN: usize = 1_000;
+
+// One line of the file; in memory a visit is a HistoryEntry like any other
+#[derive(Serialize, Deserialize)]
+struct Record {
+    url: String,
+    #[serde(default)]
+    title: String,
+    // Seconds since the Unix epoch
+    visited_at: u64,
+}
+
+impl From<Record> for HistoryEntry {
+    fn from(r: Record) -> Self {
+        let title = (!r.title.is_empty()).then_some(r.title);
+        HistoryEntry { title, visited_at: UNIX_EPOCH + Duration::from_secs(r.visited_at), ..HistoryEntry::new(&r.url) }
+    }
+}
+
+impl From<&HistoryEntry> for Record {
+    fn from(e: &HistoryEntry) -> Self {
+        let visited_at = e.visited_at.duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs());
+        Record { url: e.url.clone(), title: e.title.clone().unwrap_or_default(), visited_at }
+    }
+}
+
+// Every page loaded, across sessions, as an append-only JSON lines file. The back/forward
+// tree (history.rs) is per tab and gone on quit; this is what about:history and about:stats
+// are built from.
+pub struct VisitLog {
+    path: PathBuf,
+    pub visits: Vec<HistoryEntry>,
+}
+
+impl VisitLog {
+    pub fn load(path: PathBuf) -> Self {
+        let visits: Vec<HistoryEntry> = fs::read_to_string(&path)
+            .map(|data| data.lines().filter_map(|l| serde_json::from_str::<Record>(l).ok()).map(HistoryEntry::from).collect())
+            .unwrap_or_default();
+        let mut log = Self { path, visits };
+        if log.visits.len() > MAX_VISITS {
+            log.visits.drain(..log.visits.len() - MAX_VISITS);
+            log.compact();
+        }
+        log
+    }
+
+    // A reload of the page visited last isn't another visit
+    pub fn record(&mut self, url: &str, title: &str) {
+        if self.visits.last().is_some_and(|v| v.url == url) {
+            return;
+        }
+        let title = (!title.is_empty()).then(|| title.to_string());
+        let visit = HistoryEntry { title, visited_at: SystemTime::now(), ..HistoryEntry::new(url) };
+        if let Some(dir) = self.path.parent() {
+            let _ = fs::create_dir_all(dir);
+        }
+        if let (Ok(line), Ok(mut file)) = (serde_json::to_string(&Record::from(&visit)), OpenOptions::new().create(true).append(true).open(&self.path)) {
+            let _ = writeln!(file, "{}", line);
+        }
+        self.visits.push(visit);
+    }
+
+    fn compact(&self) {
+        let body: String = self.visits.iter()
+            .filter_map(|v| serde_json::to_string(&Record::from(v)).ok())
+            .map(|l| l + "\n")
+            .collect();
+        let _ = fs::write(&self.path, body);
+    }
+}
+
+// Newest first under a heading per day (UTC), narrowed to the visits whose URL or title
+// contains every word of `filter`
+pub fn history_page(log: &VisitLog, filter: &str) -> String {
+    let words: Vec<String> = filter.split_whitespace().map(str::to_lowercase).collect();
+    let matching: Vec<&HistoryEntry> = log.visits.iter().rev()
+        .filter(|v| {
+            let haystack = format!("{} {}", v.url, v.title.as_deref().unwrap_or_default()).to_lowercase();
+            words.iter().all(|w| haystack.contains(w))
+        })
+        .collect();
+
+    let mut html = String::from("<title>History</title><h1>History</h1><p>");
+    match words.is_empty() {
+        true => html.push_str(&format!("{} visit(s)", matching.len())),
+        false => html.push_str(&format!("{} of {} visit(s) matching <b>{}</b> — <a href=\"about:history\">show all</a>",
+            matching.len(), log.visits.len(), escape_html(filter))),
+    }
+    if matching.len() > MAX_SHOWN {
+        html.push_str(&format!(", the newest {} shown", MAX_SHOWN));
+    }
+    html.push_str(". <code>:history &lt;words&gt;</code> narrows the list.</p>");
+
+    let mut day = String::new();
+    for visit in matching.into_iter().take(MAX_SHOWN) {
+        let stamp = iso8601(visit.visited_at);
+        if stamp[..10] != day {
+            if !day.is_empty() {
+                html.push_str("</ul>");
+            }
+            day = stamp[..10].to_string();
+            html.push_str(&format!("<h2>{}</h2><ul>", day));
+        }
+        let title = visit.title.as_ref().unwrap_or(&visit.url);
+        html.push_str(&format!("<li>{2} <a href=\"{0}\">{1}</a><br>{0}</li>", escape_html(&visit.url), escape_html(title), &stamp[11..16]));
+    }
+    if !day.is_empty() {
+        html.push_str("</ul>");
+    }
+    html
+}
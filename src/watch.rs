@@ -0,0 +1,122 @@
+use crate::app::App;
+use crate::cache::Body;
+use crate::i18n::tr;
+use crate::types::ToastKind;
+use std::error::Error;
+use std::time::{Duration, Instant};
+use tokio::sync::oneshot;
+use url::Url;
+
+// `:watch <secs>`: the current page re-fetched on an interval, redrawn only when its text changes
+pub struct Watch {
+    url: String,
+    interval: Duration,
+    next: Instant,
+    text: String,
+    // (content type, body) of the fetch in progress
+    in_flight: Option<oneshot::Receiver<Result<(String, String), String>>>,
+    // A change that came in while an alternate view covered the page, drawn once it closes
+    pending: Option<(String, String)>,
+}
+
+impl App {
+    pub fn start_watch(&mut self, arg: &str) -> Result<(), Box<dyn Error>> {
+        if arg.is_empty() || arg == "0" || arg == "off" {
+            self.status = match self.watch.take() {
+                Some(w) => tr!("Stopped watching {}", w.url),
+                None => tr!("watch: not watching anything"),
+            };
+            return Ok(());
+        }
+        let secs: u64 = arg.parse().map_err(|_| format!("expected seconds, got {:?}", arg))?;
+        let base_url = Url::parse(&self.current_url)?;
+        let text = self.rendered_text(&self.raw_html, &base_url);
+        let interval = Duration::from_secs(secs.max(1));
+        self.watch = Some(Watch { url: self.current_url.clone(), interval, next: Instant::now() + interval, text, in_flight: None, pending: None });
+        self.status = tr!("Watching {} every {}s (:watch off to stop)", self.current_url, secs.max(1));
+        Ok(())
+    }
+
+    // Called every pass of the event loop. A background tab's change is rendered into that tab.
+    pub fn watch_tick(&mut self) {
+        self.tick_watch();
+        for idx in 0..self.tabs.len() {
+            if idx != self.active_tab && self.tabs[idx].watching() {
+                let peek = self.peek_tab(idx);
+                self.tick_watch();
+                self.unpeek_tab(peek);
+            }
+        }
+    }
+
+    fn tick_watch(&mut self) {
+        let Some(mut watch) = self.watch.take() else { return };
+        if watch.url != self.current_url {
+            self.status = tr!("Stopped watching {}", watch.url);
+            return;
+        }
+        if self.saved_view.is_none() && let Some((mime, body)) = watch.pending.take() {
+            self.render_watched(&watch.url, &mime, body);
+        }
+        if let Some(mut rx) = watch.in_flight.take() {
+            match rx.try_recv() {
+                Ok(Ok((mime, body))) => self.apply_watch(&mut watch, &mime, body),
+                Ok(Err(e)) => self.status = tr!("watch: {}", e),
+                Err(oneshot::error::TryRecvError::Empty) => watch.in_flight = Some(rx),
+                Err(oneshot::error::TryRecvError::Closed) => {}
+            }
+        } else if Instant::now() >= watch.next {
+            match self.network() {
+                Ok(net) => {
+                    let (net, url) = (net.clone(), watch.url.clone());
+                    let (tx, rx) = oneshot::channel();
+                    tokio::spawn(async move {
+                        let result = async {
+                            let res = net.send(net.get(&url)).await?.error_for_status()?;
+                            let mime = res.headers().get(reqwest::header::CONTENT_TYPE).and_then(|v| v.to_str().ok()).unwrap_or("").to_string();
+                            Ok::<_, reqwest::Error>((mime, res.text().await?))
+                        }.await;
+                        let _ = tx.send(result.map_err(|e| e.to_string()));
+                    });
+                    watch.in_flight = Some(rx);
+                }
+                Err(e) => {
+                    self.status = tr!("watch: {}", e);
+                    return;
+                }
+            }
+            watch.next = Instant::now() + watch.interval;
+        }
+        self.watch = Some(watch);
+    }
+
+    fn apply_watch(&mut self, watch: &mut Watch, mime: &str, body: String) {
+        let Ok(base_url) = Url::parse(&watch.url) else { return };
+        let is_html = mime.is_empty() || mime.contains("html") || mime.contains("xml");
+        let html = if is_html { body.clone() } else { format!("<pre>{}</pre>", crate::about::escape_html(&body)) };
+        let text = self.rendered_text(&html, &base_url);
+        if text == watch.text {
+            return;
+        }
+        watch.text = text;
+        // Leave an alternate view (source, diff) alone; the page underneath is redrawn on return
+        if self.saved_view.is_some() {
+            watch.pending = Some((mime.to_string(), body));
+            self.notify(ToastKind::Info, tr!("Page changed: {} (redrawn when you return to it)", watch.url));
+            return;
+        }
+        self.render_watched(&watch.url, mime, body);
+    }
+
+    fn render_watched(&mut self, url: &str, mime: &str, body: String) {
+        let Ok(base_url) = Url::parse(url) else { return };
+        let (scroll, selected) = (self.scroll, self.selected_link_idx);
+        if let Err(e) = self.render_body(mime, &Body::Text(body), &base_url) {
+            self.status = tr!("watch: {}", e);
+            return;
+        }
+        self.scroll = scroll.min(self.max_scroll());
+        self.selected_link_idx = selected.min(self.links.len().saturating_sub(1));
+        self.notify(ToastKind::Info, tr!("Page changed: {} (:diff shows what)", url));
+    }
+}
@@ -0,0 +1,298 @@
+use crate::dom;
+use markup5ever_rcdom::{Handle, Node, NodeData};
+use std::collections::HashMap;
+use std::rc::Rc;
+
+// XPath 1.0 subset: location paths with / and //, child/descendant/parent/ancestor/
+// following-sibling/preceding-sibling/self axes, name tests, text() and @attr steps,
+// and predicates of the form [n], [last()], [@a], [@a='v'], [text()='v'],
+// [contains(@a|text(),'v')] and [starts-with(...)], combinable with `and`.
+
+#[derive(Clone)]
+pub enum XItem {
+    Node(Handle),
+    Value(String),
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Axis {
+    Child,
+    Descendant,
+    DescendantOrSelf,
+    Parent,
+    Ancestor,
+    FollowingSibling,
+    PrecedingSibling,
+    Current,
+}
+
+enum Test {
+    Name(String),
+    Any,
+    Text,
+    Attr(String),
+}
+
+enum Operand {
+    Attr(String),
+    Text,
+}
+
+enum Pred {
+    Index(usize),
+    Last,
+    Has(Operand),
+    Equals(Operand, String),
+    Contains(Operand, String),
+    StartsWith(Operand, String),
+    And(Vec<Pred>),
+}
+
+struct Step {
+    // `//x` abbreviates descendant-or-self::node()/child::x, so predicates stay per-parent
+    deep: bool,
+    axis: Axis,
+    test: Test,
+    preds: Vec<Pred>,
+}
+
+pub struct XPath(Vec<Step>);
+
+fn parse_operand(s: &str) -> Result<Operand, String> {
+    let s = s.trim();
+    if let Some(a) = s.strip_prefix('@') {
+        Ok(Operand::Attr(a.to_string()))
+    } else if s == "text()" || s == "." {
+        Ok(Operand::Text)
+    } else {
+        Err(format!("unsupported operand {:?}", s))
+    }
+}
+
+fn unquote(s: &str) -> Result<String, String> {
+    let s = s.trim();
+    if s.len() >= 2 && (s.starts_with('\'') && s.ends_with('\'') || s.starts_with('"') && s.ends_with('"')) {
+        Ok(s[1..s.len() - 1].to_string())
+    } else {
+        Err(format!("expected a quoted string, got {:?}", s))
+    }
+}
+
+fn parse_pred(s: &str) -> Result<Pred, String> {
+    let s = s.trim();
+    let parts: Vec<&str> = s.split(" and ").collect();
+    if parts.len() > 1 {
+        return Ok(Pred::And(parts.into_iter().map(parse_pred).collect::<Result<_, _>>()?));
+    }
+    if let Ok(n) = s.parse::<usize>() {
+        return Ok(Pred::Index(n));
+    }
+    if s == "last()" {
+        return Ok(Pred::Last);
+    }
+    for (name, ctor) in [("contains(", Pred::Contains as fn(Operand, String) -> Pred), ("starts-with(", Pred::StartsWith)] {
+        if let Some(args) = s.strip_prefix(name).and_then(|r| r.strip_suffix(')')) {
+            let (a, b) = args.split_once(',').ok_or("expected two arguments")?;
+            return Ok(ctor(parse_operand(a)?, unquote(b)?));
+        }
+    }
+    if let Some((a, b)) = s.split_once('=') {
+        return Ok(Pred::Equals(parse_operand(a)?, unquote(b)?));
+    }
+    Ok(Pred::Has(parse_operand(s)?))
+}
+
+fn parse_step(s: &str, deep: bool) -> Result<Step, String> {
+    let axis = Axis::Child;
+    let (head, mut rest) = match s.find('[') {
+        Some(i) => (&s[..i], &s[i..]),
+        None => (s, ""),
+    };
+    let mut preds = Vec::new();
+    while let Some(body) = rest.strip_prefix('[') {
+        let end = body.find(']').ok_or("unclosed predicate")?;
+        preds.push(parse_pred(&body[..end])?);
+        rest = &body[end + 1..];
+    }
+    let (axis, name) = match head.split_once("::") {
+        Some((ax, name)) => (match ax {
+            "child" => Axis::Child,
+            "descendant" => Axis::Descendant,
+            "descendant-or-self" => Axis::DescendantOrSelf,
+            "parent" => Axis::Parent,
+            "ancestor" => Axis::Ancestor,
+            "following-sibling" => Axis::FollowingSibling,
+            "preceding-sibling" => Axis::PrecedingSibling,
+            "self" => Axis::Current,
+            other => return Err(format!("unsupported axis {}", other)),
+        }, name),
+        None => (axis, head),
+    };
+    let (axis, test) = match name {
+        "." => (Axis::Current, Test::Any),
+        ".." => (Axis::Parent, Test::Any),
+        "*" => (axis, Test::Any),
+        "text()" => (axis, Test::Text),
+        n if n.starts_with('@') => (axis, Test::Attr(n[1..].to_string())),
+        n if !n.is_empty() => (axis, Test::Name(n.to_lowercase())),
+        _ => return Err("empty step".to_string()),
+    };
+    Ok(Step { deep, axis, test, preds })
+}
+
+impl XPath {
+    pub fn parse(expr: &str) -> Result<Self, String> {
+        let expr = expr.trim();
+        if expr.is_empty() {
+            return Err("empty expression".to_string());
+        }
+        let mut steps = Vec::new();
+        let mut rest = expr;
+        // Relative paths are evaluated from the document like `//`
+        let mut deep = !rest.starts_with('/');
+        loop {
+            if let Some(r) = rest.strip_prefix("//") {
+                deep = true;
+                rest = r;
+            } else if let Some(r) = rest.strip_prefix('/') {
+                rest = r;
+            }
+            // Split at the next '/' outside quotes and brackets
+            let (mut depth, mut quote, mut end) = (0, None, rest.len());
+            for (i, c) in rest.char_indices() {
+                match (c, quote) {
+                    ('\'' | '"', None) => quote = Some(c),
+                    (q, Some(open)) if q == open => quote = None,
+                    ('[', None) => depth += 1,
+                    (']', None) => depth -= 1,
+                    ('/', None) if depth == 0 => { end = i; break; }
+                    _ => {}
+                }
+            }
+            steps.push(parse_step(&rest[..end], deep)?);
+            deep = false;
+            rest = &rest[end..];
+            if rest.is_empty() { break; }
+        }
+        Ok(XPath(steps))
+    }
+
+    pub fn evaluate(&self, document: &Handle) -> Vec<XItem> {
+        let order: HashMap<*const Node, usize> = std::iter::once(document.clone()).chain(all_descendants(document))
+            .enumerate()
+            .map(|(i, n)| (Rc::as_ptr(&n), i))
+            .collect();
+        let mut context = vec![XItem::Node(document.clone())];
+        for step in &self.0 {
+            let mut next: Vec<XItem> = Vec::new();
+            let nodes = context.iter().filter_map(|item| match item {
+                XItem::Node(n) => Some(n.clone()),
+                XItem::Value(_) => None,
+            });
+            let nodes: Vec<Handle> = if step.deep { nodes.flat_map(|n| axis_nodes(&n, Axis::DescendantOrSelf)).collect() } else { nodes.collect() };
+            for node in &nodes {
+                // Attributes live on the context node itself rather than along an axis
+                let axis = if matches!(step.test, Test::Attr(_)) { Axis::Current } else { step.axis };
+                let candidates = apply_test(&step.test, axis_nodes(node, axis));
+                for found in filter(candidates, &step.preds) {
+                    let dup = matches!(&found, XItem::Node(a) if next.iter().any(|n| matches!(n, XItem::Node(b) if Rc::ptr_eq(a, b))));
+                    if !dup { next.push(found); }
+                }
+            }
+            next.sort_by_key(|item| match item {
+                XItem::Node(n) => order.get(&Rc::as_ptr(n)).copied().unwrap_or(usize::MAX),
+                XItem::Value(_) => usize::MAX,
+            });
+            context = next;
+        }
+        context
+    }
+}
+
+fn axis_nodes(node: &Handle, axis: Axis) -> Vec<Handle> {
+    match axis {
+        Axis::Child => node.children.borrow().clone(),
+        Axis::Descendant => all_descendants(node),
+        Axis::DescendantOrSelf => std::iter::once(node.clone()).chain(all_descendants(node)).collect(),
+        Axis::Parent => dom::parent(node).into_iter().collect(),
+        Axis::Ancestor => {
+            let mut out = Vec::new();
+            let mut cur = dom::parent(node);
+            while let Some(p) = cur {
+                cur = dom::parent(&p);
+                out.push(p);
+            }
+            out
+        }
+        Axis::FollowingSibling | Axis::PrecedingSibling => {
+            let Some(parent) = dom::parent(node) else { return Vec::new() };
+            let siblings = parent.children.borrow().clone();
+            let pos = siblings.iter().position(|s| Rc::ptr_eq(s, node)).unwrap_or(0);
+            if axis == Axis::FollowingSibling {
+                siblings[pos + 1..].to_vec()
+            } else {
+                siblings[..pos].iter().rev().cloned().collect()
+            }
+        }
+        Axis::Current => vec![node.clone()],
+    }
+}
+
+fn all_descendants(node: &Handle) -> Vec<Handle> {
+    let mut out = Vec::new();
+    let mut stack: Vec<Handle> = node.children.borrow().iter().rev().cloned().collect();
+    while let Some(n) = stack.pop() {
+        stack.extend(n.children.borrow().iter().rev().cloned());
+        out.push(n);
+    }
+    out
+}
+
+fn apply_test(test: &Test, nodes: Vec<Handle>) -> Vec<XItem> {
+    match test {
+        Test::Attr(name) => nodes.iter()
+            .filter_map(|n| dom::attr(n, name).map(XItem::Value))
+            .collect(),
+        Test::Text => nodes.into_iter()
+            .filter_map(|n| match n.data {
+                NodeData::Text { ref contents } if !contents.borrow().trim().is_empty() => Some(XItem::Value(contents.borrow().trim().to_string())),
+                _ => None,
+            })
+            .collect(),
+        Test::Any => nodes.into_iter().filter(|n| dom::tag_name(n).is_some()).map(XItem::Node).collect(),
+        Test::Name(name) => nodes.into_iter().filter(|n| dom::tag_name(n).as_deref() == Some(name)).map(XItem::Node).collect(),
+    }
+}
+
+fn operand_value(node: &Handle, op: &Operand) -> Option<String> {
+    match op {
+        Operand::Attr(a) => dom::attr(node, a),
+        Operand::Text => Some(dom::text_content(node).trim().to_string()),
+    }
+}
+
+fn pred_matches(pred: &Pred, node: &Handle, position: usize, size: usize) -> bool {
+    match pred {
+        Pred::Index(n) => position == *n,
+        Pred::Last => position == size,
+        Pred::Has(op) => operand_value(node, op).is_some_and(|v| !v.is_empty()),
+        Pred::Equals(op, v) => operand_value(node, op).is_some_and(|x| x == *v),
+        Pred::Contains(op, v) => operand_value(node, op).is_some_and(|x| x.contains(v.as_str())),
+        Pred::StartsWith(op, v) => operand_value(node, op).is_some_and(|x| x.starts_with(v.as_str())),
+        Pred::And(all) => all.iter().all(|p| pred_matches(p, node, position, size)),
+    }
+}
+
+fn filter(mut items: Vec<XItem>, preds: &[Pred]) -> Vec<XItem> {
+    for pred in preds {
+        let size = items.len();
+        items = items.into_iter().enumerate()
+            .filter(|(i, item)| match item {
+                XItem::Node(n) => pred_matches(pred, n, i + 1, size),
+                XItem::Value(_) => matches!(pred, Pred::Index(n) if *n == i + 1) || matches!(pred, Pred::Last if i + 1 == size),
+            })
+            .map(|(_, item)| item)
+            .collect();
+    }
+    items
+}
@@ -0,0 +1,76 @@
+use crate::app::App;
+use crate::i18n::tr;
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+// Columns the page is laid out in at zoom level 0
+pub const BASE_WIDTH: usize = 100;
+const STEP: usize = 10;
+const MAX_LEVEL: i32 = 6;
+
+// Zoom level per host, written as one JSON object. Each level in makes the text column
+// ten characters narrower, each level out ten wider.
+pub struct SiteZoom {
+    path: PathBuf,
+    levels: BTreeMap<String, i32>,
+}
+
+impl SiteZoom {
+    pub fn load(path: PathBuf) -> Self {
+        let levels = fs::read_to_string(&path).ok().and_then(|data| serde_json::from_str(&data).ok()).unwrap_or_default();
+        Self { path, levels }
+    }
+
+    pub fn level(&self, host: &str) -> i32 {
+        self.levels.get(host).copied().unwrap_or(0)
+    }
+
+    fn set(&mut self, host: &str, level: i32) -> io::Result<()> {
+        if level == 0 {
+            self.levels.remove(host);
+        } else {
+            self.levels.insert(host.to_string(), level);
+        }
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(&self.path, serde_json::to_string_pretty(&self.levels)?)
+    }
+}
+
+pub fn width_for(level: i32) -> usize {
+    (BASE_WIDTH as i32 - level * STEP as i32) as usize
+}
+
+impl App {
+    fn current_host(&self) -> Option<String> {
+        url::Url::parse(&self.current_url).ok()?.host_str().map(str::to_string)
+    }
+
+    pub fn zoom_level(&self) -> i32 {
+        self.current_host().map(|h| self.zoom.level(&h)).unwrap_or(0)
+    }
+
+    // Width the page is laid out in; None at the default zoom, where it fills the pane
+    pub fn zoomed_width(&self) -> Option<usize> {
+        Some(self.zoom_level()).filter(|&l| l != 0).map(width_for)
+    }
+
+    pub fn zoom_by(&mut self, delta: i32) {
+        let Some(host) = self.current_host() else {
+            self.status = tr!("Zoom is remembered per site; this page has no host");
+            return;
+        };
+        let level = (self.zoom.level(&host) + delta).clamp(-MAX_LEVEL, MAX_LEVEL);
+        if let Err(e) = self.zoom.set(&host, level) {
+            self.status = tr!("Zoom not saved: {}", e);
+        }
+        self.rerender();
+        self.status = match level {
+            0 => tr!("Zoom reset for {}", host),
+            _ => tr!("Zoom {} for {}: {} columns", format!("{:+}", level), host, width_for(level)),
+        };
+    }
+}